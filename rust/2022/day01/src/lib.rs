@@ -0,0 +1,170 @@
+//! Day 1: Calorie Counting.
+//!
+//! The input is the calories each elf is carrying, one number per line, with a
+//! blank line between elves. [`Expedition::parse`] reads it, and the query
+//! methods answer both parts of the puzzle.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::parse;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 1;
+
+/// An elf and the total calories it is carrying.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elf {
+    /// The elf's number, counting from 1 in the order of the input.
+    pub i: i16,
+    pub calories: i32,
+}
+
+impl Elf {
+    fn sort_field(&self) -> i32 {
+        self.calories
+    }
+}
+
+/// All the elves in the input.
+#[derive(Debug, Clone)]
+pub struct Expedition {
+    elves: Vec<Elf>,
+}
+
+impl Expedition {
+    /// Reads the puzzle input. Fails on anything that isn't a number, or if an
+    /// elf is carrying more calories than fit in an `i32`.
+    pub fn parse(s: &str) -> Result<Expedition, String> {
+        let mut elves: Vec<Elf> = Vec::new();
+        let mut i: i16 = 1;
+        for (_, elfcals) in parse::blocks(s) {
+            let mut sum: i32 = 0;
+            for calorie in elfcals {
+                let num = match calorie.trim().parse::<i32>() {
+                    Ok(num) => num,
+                    Err(_) => return Err(format!("elf {}: {:?} is not a number", i, calorie)),
+                };
+                sum = match sum.checked_add(num) {
+                    Some(sum) => sum,
+                    None => return Err(format!("elf {}: too many calories", i)),
+                };
+            }
+            elves.push(Elf {
+                i: i,
+                calories: sum,
+            });
+            i = match i.checked_add(1) {
+                Some(i) => i,
+                None => return Err("too many elves".to_string()),
+            };
+        }
+        Ok(Expedition { elves })
+    }
+
+    /// The elves in the order of the input.
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The elves from the fewest calories to the most.
+    pub fn sorted_by_calories(&self) -> Vec<Elf> {
+        let mut elves = self.elves.clone();
+        elves.sort_by_key(|k| k.sort_field());
+        elves
+    }
+
+    /// The `n` elves carrying the most calories, from the fewest to the most
+    /// (or all of them if there are fewer than `n`).
+    pub fn top(&self, n: usize) -> Vec<Elf> {
+        let elves = self.sorted_by_calories();
+        elves[elves.len().saturating_sub(n)..].to_vec()
+    }
+
+    /// The calories carried by the `n` elves carrying the most. Part 1 is
+    /// `top_calories(1)` and part 2 is `top_calories(3)`.
+    pub fn top_calories(&self, n: usize) -> i64 {
+        self.top_calories_traced(n, &mut Trace::default())
+    }
+
+    /// [`Expedition::top_calories`], recording each of the top elves, the
+    /// most first, and the total in `trace` (see [`narrate`]).
+    pub fn top_calories_traced(&self, n: usize, trace: &mut Trace) -> i64 {
+        let top = self.top(n);
+        for elf in top.iter().rev() {
+            trace.event("elf", &[("elf", &elf.i), ("calories", &elf.calories)]);
+        }
+        let calories = top.into_iter().map(|elf| elf.calories as i64);
+        let total = checked::sum("day01 calories of the top elves", calories);
+        trace.event("total", &[("n", &n), ("calories", &total)]);
+        total
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Expedition::top_calories_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "elf" => format!(
+            "Elf {} is carrying {} Calories.",
+            event.get("elf"),
+            event.get("calories")
+        ),
+        "total" => format!(
+            "The top {} Elves are carrying {} Calories in total.",
+            event.get("n"),
+            event.get("calories")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    #[test]
+    fn test_example() {
+        let expedition = Expedition::parse(EXAMPLE).unwrap();
+        let calories: Vec<i32> = expedition.elves().iter().map(|elf| elf.calories).collect();
+        assert_eq!(calories, [6000, 4000, 11000, 24000, 10000]);
+        let top: Vec<i16> = expedition.top(3).iter().map(|elf| elf.i).collect();
+        assert_eq!(top, [5, 3, 4]);
+        assert_eq!(expedition.top_calories(1), 24000);
+        assert_eq!(expedition.top_calories(3), 45000);
+        // there are only five elves
+        assert_eq!(expedition.top_calories(10), 55000);
+    }
+
+    #[test]
+    fn test_explain_the_top_three() {
+        let expedition = Expedition::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(expedition.top_calories_traced(3, &mut trace), 45000);
+        assert_eq!(
+            trace.render(narrate),
+            "Elf 4 is carrying 24000 Calories.\n\
+             Elf 3 is carrying 11000 Calories.\n\
+             Elf 5 is carrying 10000 Calories.\n\
+             The top 3 Elves are carrying 45000 Calories in total.\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Expedition::parse("1000\n\n20x0\n").err(),
+            Some("elf 2: \"20x0\" is not a number".to_string())
+        );
+        assert_eq!(
+            Expedition::parse("2147483647\n1\n").err(),
+            Some("elf 1: too many calories".to_string())
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use y2022_day01::shared;
+use y2022_day01::Expedition;
+use y2022_day01::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, None));
+    let filename = shared::cli::filename().unwrap_or("input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day01", &filename, |s| Expedition::parse(s).map(|_| ()));
+        return;
+    }
+
+    let path = Path::new(&filename);
+    let display = path.display();
+
+    // Open the path in read-only mode, returns `io::Result<File>`
+    let mut file = match File::open(&path) {
+        Err(why) => panic!("couldn't open {}: {}", display, why),
+        Ok(file) => file,
+    };
+
+    let mut s = String::new();
+    let expedition: Expedition = match file.read_to_string(&mut s) {
+        Err(why) => panic!("couldn't read {}: {}", display, why),
+        Ok(_) => match Expedition::parse(&s) {
+            Err(why) => panic!("couldn't parse {}: {}", display, why),
+            Ok(expedition) => expedition,
+        },
+    };
+    for elf in expedition.elves() {
+        println!("elf {}: {}", elf.i, elf.calories);
+    }
+
+    for elf in &expedition.sorted_by_calories() {
+        println!("elf[{:03}]: {}", elf.i, elf.calories);
+    }
+
+    let last3 = expedition.top(3);
+
+    println!("---------------\nLast 3:");
+    for elf in &last3 {
+        println!("elf[{:03}]: {}", elf.i, elf.calories);
+    }
+
+    // The answer I think is: 197291
+    println!(
+        "Calories of last 3 combined: {}",
+        expedition.top_calories(3)
+    );
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        expedition.top_calories_traced(3, &mut trace);
+        println!("\n{}", trace.render(y2022_day01::narrate));
+    }
+}
@@ -0,0 +1,279 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! Each line of the strategy guide is the opponent's choice (A, B or C) and the
+//! outcome needed (X to lose, Y to draw, Z to win). [`Strategy::parse`] reads
+//! the guide and [`Strategy::total_score`] is the answer.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 2;
+
+/// One round of the strategy guide. The choices are `'R'`, `'P'` or `'S'`.
+#[derive(Debug, Clone)]
+pub struct RockPaperScissors {
+    /// The round's number, counting from 1.
+    pub game_number: u32,
+    /// The line of the guide the round was read from.
+    pub raw: String,
+    pub opponent_choice: char,
+    /// The choice that gives the outcome the guide asks for.
+    pub my_choice: char,
+}
+
+// this is really verbose, but it works
+impl RockPaperScissors {
+    /// 6 for a win, 3 for a draw and 0 for a loss.
+    pub fn outcome_score(&self) -> i32 {
+        // win = 6, draw = 3, lose = 0
+        if self.my_choice == self.opponent_choice {
+            3
+        } else if self.my_choice == 'R' && self.opponent_choice == 'S' {
+            6
+        } else if self.my_choice == 'S' && self.opponent_choice == 'P' {
+            6
+        } else if self.my_choice == 'P' && self.opponent_choice == 'R' {
+            6
+        } else {
+            0
+        }
+    }
+
+    /// 1 for rock, 2 for paper and 3 for scissors.
+    pub fn shape_score(&self) -> i32 {
+        // shape score = (1 for Rock, 2 for Paper, and 3 for Scissors)
+        if self.my_choice == 'R' {
+            1
+        } else if self.my_choice == 'P' {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// The outcome score plus the shape score.
+    pub fn round_score(&self) -> i32 {
+        // single round score = (outcome_score) + (shape score)
+        self.outcome_score() + self.shape_score()
+    }
+}
+
+// this is a translation layer to work out from
+// (A or B or C) (X or Y or Z) what the right response should be
+fn translate_outcome_needed_to_my_choice(opponent_choice: &char, outcome_needed: &char) -> char {
+    if *opponent_choice == 'A' {
+        // opponent = 'R'
+        if *outcome_needed == 'X' {
+            // lose
+            'S'
+        } else if *outcome_needed == 'Y' {
+            // draw
+            'R'
+        } else {
+            // win
+            'P'
+        }
+    } else if *opponent_choice == 'B' {
+        // Opponent = 'P'
+        if *outcome_needed == 'X' {
+            // lose
+            'R'
+        } else if *outcome_needed == 'Y' {
+            // draw
+            'P'
+        } else {
+            // win
+            'S'
+        }
+    } else {
+        // Opponent = 'S'
+        if *outcome_needed == 'X' {
+            // lose
+            'P'
+        } else if *outcome_needed == 'Y' {
+            // draw
+            'S'
+        } else {
+            // win
+            'R'
+        }
+    }
+}
+
+fn translate_choice(c: &char) -> char {
+    if *c == 'A' {
+        'R'
+    // } else if *c == 'X' {
+    //     'R'
+    } else if *c == 'B' {
+        'P'
+    } else if *c == 'C' {
+        'S'
+    // } else if *c == 'Y' {
+    //     'P'
+    } else {
+        'Q' // this shouldn't happen
+    }
+}
+
+/// The rounds of the strategy guide.
+#[derive(Debug, Clone)]
+pub struct Strategy {
+    games: Vec<RockPaperScissors>,
+}
+
+impl Strategy {
+    /// Reads the strategy guide. Fails on a line that isn't `"A X"` with a
+    /// known choice and outcome.
+    pub fn parse(s: &str) -> Result<Strategy, String> {
+        Ok(Strategy {
+            games: parse_games(s)?,
+        })
+    }
+
+    /// The rounds in the order of the guide.
+    pub fn games(&self) -> &[RockPaperScissors] {
+        &self.games
+    }
+
+    /// The total score from following the guide.
+    pub fn total_score(&self) -> i64 {
+        self.total_score_traced(&mut Trace::default())
+    }
+
+    /// [`Strategy::total_score`], recording what is played in each round and
+    /// what it scores in `trace` (see [`narrate`]).
+    pub fn total_score_traced(&self, trace: &mut Trace) -> i64 {
+        for game in &self.games {
+            trace.event(
+                "round",
+                &[
+                    ("round", &game.game_number),
+                    ("line", &game.raw),
+                    ("opponent", &game.opponent_choice),
+                    ("me", &game.my_choice),
+                    ("shape", &game.shape_score()),
+                    ("outcome", &game.outcome_score()),
+                ],
+            );
+        }
+        let scores = self.games.iter().map(|game| game.round_score() as i64);
+        let total = checked::sum("day02 total score", scores);
+        trace.event("total", &[("score", &total)]);
+        total
+    }
+}
+
+fn shape_name(choice: &str) -> &str {
+    match choice {
+        "R" => "Rock",
+        "P" => "Paper",
+        "S" => "Scissors",
+        _ => choice,
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Strategy::total_score_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "round" => {
+            let outcome = match event.get("outcome") {
+                "6" => "win",
+                "3" => "draw",
+                _ => "loss",
+            };
+            let shape: i32 = event.get("shape").parse().unwrap_or(0);
+            let outcome_score: i32 = event.get("outcome").parse().unwrap_or(0);
+            format!(
+                "Round {} ({}): they choose {} and you choose {}, a {} scoring {} + {} = {}.",
+                event.get("round"),
+                event.get("line"),
+                shape_name(event.get("opponent")),
+                shape_name(event.get("me")),
+                outcome,
+                shape,
+                outcome_score,
+                shape + outcome_score
+            )
+        }
+        "total" => format!("Your total score is {}.", event.get("score")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// each line is the opponent's choice (A, B or C), a space, and the outcome needed (X, Y or Z)
+fn parse_games(s: &str) -> Result<Vec<RockPaperScissors>, String> {
+    // vector of RockPaperScissors
+    let mut games: Vec<RockPaperScissors> = Vec::new();
+    let mut i: u32 = 1;
+
+    for line in s.lines() {
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        if chars.len() != 3 || chars[1] != ' ' {
+            return Err(format!("line {}: expected \"A X\", got {:?}", i, line));
+        }
+        let oc = chars[0];
+        let outcome_needed = chars[2];
+        if !"ABC".contains(oc) || !"XYZ".contains(outcome_needed) {
+            return Err(format!("line {}: unknown choice in {:?}", i, line));
+        }
+
+        games.push(RockPaperScissors {
+            game_number: i,
+            raw: line.to_string(),
+            opponent_choice: translate_choice(&oc),
+            my_choice: translate_outcome_needed_to_my_choice(&oc, &outcome_needed),
+        });
+
+        i += 1;
+    }
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        let strategy = Strategy::parse("A Y\nB X\nC Z\n").unwrap();
+        // the second column is the outcome needed, so every round is a draw, loss or win
+        let choices: Vec<char> = strategy.games().iter().map(|g| g.my_choice).collect();
+        assert_eq!(choices, ['R', 'R', 'R']);
+        let scores: Vec<i32> = strategy.games().iter().map(|g| g.round_score()).collect();
+        assert_eq!(scores, [4, 1, 7]);
+        assert_eq!(strategy.total_score(), 12);
+    }
+
+    #[test]
+    fn test_explain_the_rounds() {
+        let strategy = Strategy::parse("A Y\nB X\nC Z\n").unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(strategy.total_score_traced(&mut trace), 12);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "Round 1 (A Y): they choose Rock and you choose Rock, a draw scoring 1 + 3 = 4.\n\
+             Round 2 (B X): they choose Paper and you choose Rock, a loss scoring 1 + 0 = 1.\n"
+        ));
+        assert!(explained.ends_with("Your total score is 12.\n"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Strategy::parse("A Y\nAY\n").err(),
+            Some("line 2: expected \"A X\", got \"AY\"".to_string())
+        );
+        assert_eq!(
+            Strategy::parse("D Y\n").err(),
+            Some("line 1: unknown choice in \"D Y\"".to_string())
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::fs;
+
+use y2022_day02::shared;
+use y2022_day02::Strategy;
+use y2022_day02::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, None));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day02", &filename, |s| Strategy::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let strategy: Strategy = match Strategy::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(strategy) => strategy,
+    };
+
+    for game in strategy.games() {
+        println!(
+            "Game[{:03}]: {:?} = {}",
+            game.game_number,
+            game,
+            game.round_score()
+        );
+    }
+    println!("Total score: {}", strategy.total_score());
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        strategy.total_score_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day02::narrate));
+    }
+}
@@ -0,0 +1,185 @@
+//! Day 3: Rucksack Reorganization.
+//!
+//! Each line is the items in one elf's rucksack. The elves are in groups of
+//! three, and a group's badge is the one item all three are carrying.
+//! [`Rucksacks::parse`] reads the input and [`Rucksacks::badge_priorities`]
+//! sums to the answer.
+
+use std::collections::HashSet;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 3;
+
+/// The priority of an item. Lowercase item types a through z have priorities
+/// 1 through 26, and uppercase item types A through Z have priorities 27
+/// through 52.
+pub fn translate_char_to_priority(c: &char) -> u16 {
+    let b = *c as u16;
+    if b > 64 && b < 91 {
+        b - 64 + 26
+    } else {
+        b - 96
+    }
+}
+
+fn get_intersection(a: Vec<char>, b: Vec<char>) -> Vec<char> {
+    let unique_a = a.iter().collect::<HashSet<_>>();
+    let unique_b = b.iter().collect::<HashSet<_>>();
+    let intersection: Vec<char> = unique_a.intersection(&unique_b).map(|x| **x).collect();
+    intersection
+}
+
+/// The rucksacks in the input, in order.
+#[derive(Debug, Clone)]
+pub struct Rucksacks {
+    sacks: Vec<Vec<char>>,
+}
+
+impl Rucksacks {
+    /// Reads the puzzle input. Fails on anything other than the letters a-z
+    /// and A-Z.
+    pub fn parse(s: &str) -> Result<Rucksacks, String> {
+        Ok(Rucksacks {
+            sacks: parse_sacks(s)?,
+        })
+    }
+
+    /// The items in each rucksack.
+    pub fn sacks(&self) -> &[Vec<char>] {
+        &self.sacks
+    }
+
+    /// The badge of each group of three elves. A trailing group of fewer than
+    /// three is ignored. Fails if a group has no item in common.
+    pub fn badges(&self) -> Result<Vec<char>, String> {
+        let mut badges: Vec<char> = Vec::new();
+        for (group, elf_sacks) in self.sacks.chunks_exact(3).enumerate() {
+            let intersection = get_intersection(elf_sacks[0].clone(), elf_sacks[1].clone());
+            let intersection2 = get_intersection(intersection, elf_sacks[2].clone());
+            match intersection2.first() {
+                Some(badge) => badges.push(*badge),
+                None => return Err(format!("group {} has no item in common", group + 1)),
+            }
+        }
+        Ok(badges)
+    }
+
+    /// The priority of each group's badge.
+    pub fn badge_priorities(&self) -> Result<Vec<u16>, String> {
+        Ok(self
+            .badges()?
+            .iter()
+            .map(translate_char_to_priority)
+            .collect())
+    }
+
+    /// The sum of the badges' priorities. Part 2.
+    pub fn badge_priority_sum(&self) -> Result<u64, String> {
+        self.badge_priority_sum_traced(&mut Trace::default())
+    }
+
+    /// [`Rucksacks::badge_priority_sum`], recording each group's badge and
+    /// its priority in `trace` (see [`narrate`]).
+    pub fn badge_priority_sum_traced(&self, trace: &mut Trace) -> Result<u64, String> {
+        let badges = self.badges()?;
+        for (group, badge) in badges.iter().enumerate() {
+            trace.event(
+                "badge",
+                &[
+                    ("group", &(group + 1)),
+                    ("item", badge),
+                    ("priority", &translate_char_to_priority(badge)),
+                ],
+            );
+        }
+        let priorities = badges
+            .iter()
+            .map(|badge| u64::from(translate_char_to_priority(badge)));
+        let sum = checked::sum("day03 sum of priorities", priorities);
+        trace.event("sum", &[("sum", &sum)]);
+        Ok(sum)
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Rucksacks::badge_priority_sum_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "badge" => format!(
+            "Group {} all carry {}, which has priority {}.",
+            event.get("group"),
+            event.get("item"),
+            event.get("priority")
+        ),
+        "sum" => format!("The priorities add up to {}.", event.get("sum")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// one rucksack per line, and items are the letters a-z and A-Z
+fn parse_sacks(s: &str) -> Result<Vec<Vec<char>>, String> {
+    let mut sacks: Vec<Vec<char>> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let chars: Vec<char> = line.trim_end().chars().collect();
+        if let Some(c) = chars.iter().find(|c| !c.is_ascii_alphabetic()) {
+            return Err(format!("line {}: {:?} is not an item", i + 1, c));
+        }
+        sacks.push(chars);
+    }
+    Ok(sacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
+                           jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
+                           PmmdzqPrVvPwwTWBwg\n\
+                           wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
+                           ttgJtRGJQctTZtZT\n\
+                           CrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    #[test]
+    fn test_example() {
+        let rucksacks = Rucksacks::parse(EXAMPLE).unwrap();
+        assert_eq!(rucksacks.sacks().len(), 6);
+        assert_eq!(rucksacks.badges(), Ok(vec!['r', 'Z']));
+        assert_eq!(rucksacks.badge_priorities(), Ok(vec![18, 52]));
+        assert_eq!(rucksacks.badge_priority_sum(), Ok(70));
+
+        let mut trace = Trace::new(true);
+        assert_eq!(rucksacks.badge_priority_sum_traced(&mut trace), Ok(70));
+        assert_eq!(
+            trace.render(narrate),
+            "Group 1 all carry r, which has priority 18.\n\
+             Group 2 all carry Z, which has priority 52.\n\
+             The priorities add up to 70.\n"
+        );
+    }
+
+    #[test]
+    fn test_priorities() {
+        let priorities: Vec<u16> = ['a', 'z', 'A', 'Z']
+            .iter()
+            .map(translate_char_to_priority)
+            .collect();
+        assert_eq!(priorities, [1, 26, 27, 52]);
+        assert_eq!(
+            Rucksacks::parse("ab\ncd\nef\n").unwrap().badges(),
+            Err("group 1 has no item in common".to_string())
+        );
+        assert_eq!(
+            Rucksacks::parse("abc\na1c\n").err(),
+            Some("line 2: '1' is not an item".to_string())
+        );
+    }
+}
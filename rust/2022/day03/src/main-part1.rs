@@ -47,7 +47,10 @@ fn main() {
 
     println!("Priorities: {:?}", priorities);
 
-    println!("Sum of priorities: {}", priorities.iter().sum::<u16>());
+    println!(
+        "Sum of priorities: {}",
+        priorities.iter().map(|p| *p as u64).sum::<u64>()
+    );
 }
 
 // The output is wrapped in a Result to allow matching on errors
@@ -0,0 +1,56 @@
+use std::fs;
+
+use y2022_day03::shared;
+use y2022_day03::{translate_char_to_priority, Rucksacks};
+use y2022_day03::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, None));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day03", &filename, |s| Rucksacks::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let rucksacks = match Rucksacks::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(rucksacks) => rucksacks,
+    };
+
+    let badges = match rucksacks.badges() {
+        Err(why) => panic!("couldn't find the badges in {}: {}", filename, why),
+        Ok(badges) => badges,
+    };
+    for badge in &badges {
+        println!("Intersection: {:?}", [badge]);
+    }
+
+    let priorities: Vec<u16> = badges.iter().map(translate_char_to_priority).collect();
+
+    println!("Priorities: {:?}", priorities);
+
+    // summed as a u64, the priorities' u16 would overflow on a big input
+    match rucksacks.badge_priority_sum() {
+        Err(why) => panic!("couldn't find the badges in {}: {}", filename, why),
+        Ok(sum) => println!("Sum of priorities: {}", sum),
+    }
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        // the badges were all found above
+        let _ = rucksacks.badge_priority_sum_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day03::narrate));
+    }
+}
@@ -0,0 +1,297 @@
+//! Day 4: Camp Cleanup.
+//!
+//! Each line is the sections assigned to a pair of elves, e.g. `2-4,6-8`.
+//! [`Assignments::parse`] reads the input, and the counts of the pairs where
+//! one range contains the other, and where they overlap at all, are the
+//! answers.
+
+#[cfg(test)]
+use std::collections::HashSet;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::invariant;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 4;
+
+/// The section ranges assigned to a pair of elves. Both ranges include their
+/// ends.
+#[derive(Debug, Copy, Clone)]
+pub struct AssignmentPair {
+    pub a1_start: i32,
+    pub a1_end: i32,
+    pub a2_start: i32,
+    pub a2_end: i32,
+}
+
+impl AssignmentPair {
+    pub fn new(a1_start: i32, a1_end: i32, a2_start: i32, a2_end: i32) -> AssignmentPair {
+        AssignmentPair {
+            a1_start,
+            a1_end,
+            a2_start,
+            a2_end,
+        }
+    }
+
+    /// Whether the ranges share any section. They overlap if each one starts
+    /// before the other one ends.
+    pub fn is_containing_overlapping_assignments(&self) -> bool {
+        self.a1_start <= self.a2_end && self.a2_start <= self.a1_end
+    }
+
+    /// Whether one of the ranges contains the other.
+    pub fn is_containing_fully_overlapping_assignments(&self) -> bool {
+        (self.a1_start <= self.a2_start && self.a2_end <= self.a1_end)
+            || (self.a2_start <= self.a1_start && self.a1_end <= self.a2_end)
+    }
+
+    // the same checks using a set of every section, which is slow for big
+    // ranges but obviously right, so the tests check the ones above against them
+    #[cfg(test)]
+    fn is_containing_overlapping_assignments_by_set(&self) -> bool {
+        let a = get_hashset(&self.a1_start, &self.a1_end);
+        let b = get_hashset(&self.a2_start, &self.a2_end);
+
+        // if there is any intersection, there is overlap
+        a.intersection(&b).next().is_some()
+    }
+
+    #[cfg(test)]
+    fn is_containing_fully_overlapping_assignments_by_set(&self) -> bool {
+        let a = get_hashset(&self.a1_start, &self.a1_end);
+        let b = get_hashset(&self.a2_start, &self.a2_end);
+
+        // check if a is a subset of b
+        if a.is_subset(&b) {
+            return true;
+        } else {
+            // check if b is a subset of a
+            return b.is_subset(&a);
+        }
+    }
+}
+
+#[cfg(test)]
+fn get_hashset(start: &i32, end: &i32) -> HashSet<i32> {
+    let mut hs = HashSet::new();
+    for i in *start..*end + 1 {
+        hs.insert(i);
+    }
+    hs
+}
+
+fn get_assignment_vec(elf_assignment: &str) -> Result<(i32, i32), String> {
+    let (start_str, end_str) = match elf_assignment.split_once('-') {
+        Some(parts) => parts,
+        None => return Err(format!("{:?} is not a range", elf_assignment)),
+    };
+    let start = match start_str.trim().parse::<i32>() {
+        Ok(start) => start,
+        Err(_) => return Err(format!("{:?} is not a number", start_str)),
+    };
+    let end = match end_str.trim().parse::<i32>() {
+        Ok(end) => end,
+        Err(_) => return Err(format!("{:?} is not a number", end_str)),
+    };
+    if start > end {
+        return Err(format!("{:?} ends before it starts", elf_assignment));
+    }
+    Ok((start, end))
+}
+
+/// The assignment pairs in the input, in order.
+#[derive(Debug, Clone)]
+pub struct Assignments {
+    pairs: Vec<AssignmentPair>,
+}
+
+impl Assignments {
+    /// Reads the puzzle input. Fails on a line that isn't two ranges, or on a
+    /// range that ends before it starts.
+    pub fn parse(s: &str) -> Result<Assignments, String> {
+        Ok(Assignments {
+            pairs: parse_pairs(s)?,
+        })
+    }
+
+    pub fn pairs(&self) -> &[AssignmentPair] {
+        &self.pairs
+    }
+
+    /// The number of pairs where one range contains the other.
+    pub fn fully_overlapping_count(&self) -> usize {
+        self.fully_overlapping_count_traced(&mut Trace::default())
+    }
+
+    /// [`Assignments::fully_overlapping_count`], recording each pair where one
+    /// range contains the other in `trace` (see [`narrate`]).
+    pub fn fully_overlapping_count_traced(&self, trace: &mut Trace) -> usize {
+        let mut count = 0;
+        for (i, pair) in self.pairs.iter().enumerate() {
+            if pair.is_containing_fully_overlapping_assignments() {
+                if trace.is_enabled() {
+                    let first = format!("{}-{}", pair.a1_start, pair.a1_end);
+                    let second = format!("{}-{}", pair.a2_start, pair.a2_end);
+                    // when the ranges are equal either one contains the other
+                    let (outer, inner) =
+                        if pair.a1_start <= pair.a2_start && pair.a2_end <= pair.a1_end {
+                            (first, second)
+                        } else {
+                            (second, first)
+                        };
+                    trace.event(
+                        "contains",
+                        &[("pair", &(i + 1)), ("outer", &outer), ("inner", &inner)],
+                    );
+                }
+                // so there can't be more of these than overlapping pairs
+                invariant::check(
+                    "day04 a pair that fully overlaps overlaps",
+                    || pair.is_containing_overlapping_assignments(),
+                    || {
+                        format!(
+                            "pair {} is {}-{},{}-{}",
+                            i + 1,
+                            pair.a1_start,
+                            pair.a1_end,
+                            pair.a2_start,
+                            pair.a2_end
+                        )
+                    },
+                );
+                count += 1;
+            }
+        }
+        invariant::check(
+            "day04 fully overlapping <= overlapping",
+            || count <= self.overlapping_count(),
+            || {
+                format!(
+                    "{} pairs fully overlap, {} overlap",
+                    count,
+                    self.overlapping_count()
+                )
+            },
+        );
+        trace.event("count", &[("count", &count)]);
+        count
+    }
+
+    /// The number of pairs whose ranges overlap at all.
+    pub fn overlapping_count(&self) -> usize {
+        self.pairs
+            .iter()
+            .filter(|pair| pair.is_containing_overlapping_assignments())
+            .count()
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Assignments::fully_overlapping_count_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "contains" => format!(
+            "In pair {}, {} fully contains {}.",
+            event.get("pair"),
+            event.get("outer"),
+            event.get("inner")
+        ),
+        "count" => format!(
+            "In {} pairs one range fully contains the other.",
+            event.get("count")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// each line is a pair of ranges, e.g. 2-4,6-8
+fn parse_pairs(s: &str) -> Result<Vec<AssignmentPair>, String> {
+    let mut assignment_pairs: Vec<AssignmentPair> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let assignments: Vec<&str> = line.split(",").collect::<Vec<&str>>();
+        if assignments.len() != 2 {
+            return Err(format!(
+                "line {}: expected two ranges, got {:?}",
+                i + 1,
+                line
+            ));
+        }
+        let (a1_start, a1_end) = get_assignment_vec(&assignments[0])
+            .map_err(|why| format!("line {}: {}", i + 1, why))?;
+        let (a2_start, a2_end) = get_assignment_vec(&assignments[1])
+            .map_err(|why| format!("line {}: {}", i + 1, why))?;
+        assignment_pairs.push(AssignmentPair::new(a1_start, a1_end, a2_start, a2_end));
+    }
+    Ok(assignment_pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::property;
+    use shared::rng::Rng;
+
+    fn random_pair(rng: &mut Rng) -> AssignmentPair {
+        let (a1_start, a2_start) = (rng.range(1, 20) as i32, rng.range(1, 20) as i32);
+        AssignmentPair::new(
+            a1_start,
+            a1_start + rng.range(0, 10) as i32,
+            a2_start,
+            a2_start + rng.range(0, 10) as i32,
+        )
+    }
+
+    #[test]
+    fn test_overlapping_agrees_with_sets() {
+        property::check("overlapping", 2000, random_pair, |pair| {
+            property::agree(
+                pair.is_containing_overlapping_assignments(),
+                pair.is_containing_overlapping_assignments_by_set(),
+            )
+        });
+    }
+
+    #[test]
+    fn test_fully_overlapping_agrees_with_sets() {
+        property::check("fully overlapping", 2000, random_pair, |pair| {
+            property::agree(
+                pair.is_containing_fully_overlapping_assignments(),
+                pair.is_containing_fully_overlapping_assignments_by_set(),
+            )
+        });
+    }
+
+    #[test]
+    fn test_example() {
+        let assignments =
+            Assignments::parse("2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n").unwrap();
+        let pairs = assignments.pairs();
+        let full: Vec<bool> = pairs
+            .iter()
+            .map(|p| p.is_containing_fully_overlapping_assignments())
+            .collect();
+        let partial: Vec<bool> = pairs
+            .iter()
+            .map(|p| p.is_containing_overlapping_assignments())
+            .collect();
+        assert_eq!(full, vec![false, false, false, true, true, false]);
+        assert_eq!(partial, vec![false, false, true, true, true, true]);
+        assert_eq!(assignments.fully_overlapping_count(), 2);
+        assert_eq!(assignments.overlapping_count(), 4);
+
+        let mut trace = Trace::new(true);
+        assert_eq!(assignments.fully_overlapping_count_traced(&mut trace), 2);
+        assert_eq!(
+            trace.render(narrate),
+            "In pair 4, 2-8 fully contains 3-7.\n\
+             In pair 5, 4-6 fully contains 6-6.\n\
+             In 2 pairs one range fully contains the other.\n"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+use y2022_day04::shared;
+use y2022_day04::Assignments;
+use y2022_day04::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, None));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day04", &filename, |s| Assignments::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let assignments: Assignments = match Assignments::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(assignments) => assignments,
+    };
+
+    println!(
+        "Fully Overlapping Count: {:?}",
+        assignments.fully_overlapping_count()
+    );
+    println!(
+        "Partially Overlapping Count: {:?}",
+        assignments.overlapping_count()
+    );
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        assignments.fully_overlapping_count_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day04::narrate));
+    }
+}
@@ -0,0 +1,559 @@
+//! Day 5: Supply Stacks.
+//!
+//! The input is a drawing of the stacks of crates, a blank line, and then the
+//! commands for the crane. [`ShipYard::parse`] reads it, and the shipyard is a
+//! [`Simulation`] that runs one command per step. The crates on top of each
+//! stack at the end are the answer.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::invariant;
+use shared::lint::{Lint, Problem};
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 5;
+
+/// A stack of crates, from the bottom to the top.
+#[derive(Debug)]
+pub struct Stack {
+    pub crates: Vec<char>,
+    /// The number under the stack in the drawing.
+    pub name: String,
+}
+
+impl Stack {
+    fn new(name: String) -> Stack {
+        Stack {
+            crates: Vec::new(),
+            name: name,
+        }
+    }
+
+    // add a crate to the top (end) of the stack
+    fn add_crate(&mut self, c: char) {
+        self.crates.push(c);
+    }
+
+    fn remove_crates(&mut self, num_crates: usize) -> Vec<char> {
+        let removed: Vec<char> = self
+            .crates
+            .split_off(self.crates.len() - num_crates)
+            .to_vec();
+        removed
+    }
+
+    fn add_crates(&mut self, crates: Vec<char>) {
+        for c in crates {
+            self.add_crate(c);
+        }
+    }
+}
+
+/// The stacks of crates and the crane's commands.
+pub struct ShipYard {
+    stacks: Vec<Stack>,
+    is_accepting_commands: bool,
+    commands: Vec<(u16, u16, u16)>, // the commands to run, in order
+    commands_done: usize,           // how many of the commands have been run
+}
+
+impl ShipYard {
+    fn new() -> ShipYard {
+        ShipYard {
+            stacks: Vec::new(),
+            is_accepting_commands: false,
+            commands: Vec::new(),
+            commands_done: 0,
+        }
+    }
+
+    fn get_stack(&mut self, stack_num: u16) -> Option<&mut Stack> {
+        for x in &mut self.stacks {
+            if x.name == stack_num.to_string() {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    fn stack_index(&self, stack_num: u16) -> Result<usize, String> {
+        self.stacks
+            .iter()
+            .position(|x| x.name == stack_num.to_string())
+            .ok_or(format!("there is no stack {}", stack_num))
+    }
+
+    fn parse_shipyard_data(&mut self, lines: Vec<String>) -> Result<(), String> {
+        if lines.is_empty() {
+            return Err("there is no drawing of the stacks".to_string());
+        }
+        // all the data is held at index = (n*4) + 1 e.g. 1, 5, 9 etc
+        let mut firstline: bool = true;
+        for line in lines {
+            let chars: Vec<char> = line.chars().collect();
+            let mut i: u16 = 1;
+            for n in (1..chars.len().saturating_sub(1)).step_by(4) {
+                // first line contains the number of stacks, so let's create them
+                if firstline {
+                    let name: String = (i).to_string();
+                    self.stacks.push(Stack::new(name));
+                } else {
+                    // every other line is a stack to display
+                    let x = chars[n];
+                    if x != ' ' {
+                        match self.get_stack(i) {
+                            Some(c) => c.add_crate(x),
+                            None => return Err(format!("crate {} is not above a stack", x)),
+                        }
+                    }
+                }
+                i = match i.checked_add(1) {
+                    Some(i) => i,
+                    None => return Err("too many stacks".to_string()),
+                };
+            }
+            firstline = false;
+        }
+        self.is_accepting_commands = true;
+        Ok(())
+    }
+
+    fn parse_command(&self, command: &str) -> Result<(u16, u16, u16), String> {
+        // command is "move N from S1 to S2"
+        // println!("\nCommand: {}", command);
+        // self.display_shipyard_status();
+        let command_parts: Vec<&str> = command.split_whitespace().collect::<Vec<&str>>();
+        // println!("Command parts: {:?}", command_parts);
+        if command_parts.len() != 6
+            || command_parts[0] != "move"
+            || command_parts[2] != "from"
+            || command_parts[4] != "to"
+        {
+            return Err(format!("{:?} is not a move command", command));
+        }
+        let mut nums: Vec<u16> = Vec::new();
+        for part in [command_parts[1], command_parts[3], command_parts[5]] {
+            match part.parse::<u16>() {
+                Ok(num) => nums.push(num),
+                Err(_) => return Err(format!("{:?} is not a number", part)),
+            }
+        }
+        // return the number of crates to move, the from stack, and the to stack
+        Ok((nums[0], nums[1], nums[2]))
+    }
+
+    /// Runs the rest of the commands.
+    pub fn run(&mut self) {
+        self.run_traced(&mut Trace::default())
+    }
+
+    /// [`ShipYard::run`], recording each move of the crane and the crates on
+    /// top at the end in `trace` (see [`narrate`]).
+    pub fn run_traced(&mut self, trace: &mut Trace) {
+        while !self.is_done() {
+            let (num_crates, from_stack, to_stack) = self.commands[self.commands_done];
+            self.step();
+            if trace.is_enabled() {
+                // the moved crates are now on top of the to stack
+                let to = &self.stacks[self.stack_index(to_stack).unwrap()].crates;
+                let moved: String = to[to.len() - num_crates as usize..].iter().collect();
+                trace.event(
+                    "move",
+                    &[
+                        ("count", &num_crates),
+                        ("crates", &moved),
+                        ("from", &from_stack),
+                        ("to", &to_stack),
+                    ],
+                );
+            }
+        }
+        trace.event("top", &[("crates", &self.top_of_each_stack())]);
+    }
+
+    /// The stacks, in the order of the drawing.
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    /// The commands as (number of crates, from stack, to stack).
+    pub fn commands(&self) -> &[(u16, u16, u16)] {
+        &self.commands
+    }
+
+    /// The crate on top of each stack, from the first stack to the last. Empty
+    /// stacks are skipped.
+    pub fn top_of_each_stack(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.crates.last())
+            .collect()
+    }
+
+    /// One line per stack: its name, how many crates it has and the crates
+    /// from the bottom up.
+    pub fn shipyard_status(&self) -> String {
+        let mut s = String::new();
+        for stack in &self.stacks {
+            let mut crate_chars: Vec<String> = Vec::new();
+            for c in stack.crates.iter() {
+                crate_chars.push(c.to_string().clone());
+            }
+            s.push_str(&format!(
+                "{}: [{}] {}\n",
+                stack.name,
+                crate_chars.len(),
+                crate_chars.join(" ")
+            ));
+        }
+        s
+    }
+
+    // fails if a stack is missing or the crane would take more crates than
+    // there are
+    fn execute_command(&mut self, command: (u16, u16, u16)) -> Result<(), String> {
+        let (num_crates, from_stack, to_stack) = command;
+        let from = self.stack_index(from_stack)?;
+        let to = self.stack_index(to_stack)?;
+        if self.stacks[from].crates.len() < num_crates as usize {
+            return Err(format!(
+                "stack {} has fewer than {} crates",
+                from_stack, num_crates
+            ));
+        }
+        let total = |stacks: &Vec<Stack>| stacks.iter().map(|s| s.crates.len()).sum::<usize>();
+        let before = total(&self.stacks);
+
+        // move the crates from the from stack to the to stack
+        // cratemover 9001 can move multiple crates, not one at a time
+        let crates = self.stacks[from].remove_crates(num_crates as usize);
+        self.stacks[to].add_crates(crates);
+        invariant::check(
+            "day05 crates are conserved",
+            || total(&self.stacks) == before,
+            || {
+                format!(
+                    "move {} from {} to {}: {} crates before, {} after",
+                    num_crates,
+                    from_stack,
+                    to_stack,
+                    before,
+                    total(&self.stacks)
+                )
+            },
+        );
+        Ok(())
+    }
+}
+
+// each step runs the next command
+impl Simulation for ShipYard {
+    type State = Vec<String>; // the crates in each stack, bottom to top
+
+    fn step(&mut self) {
+        let command = self.commands[self.commands_done];
+        // parse has checked every command against the stacks
+        if let Err(why) = self.execute_command(command) {
+            panic!("command {}: {}", self.commands_done + 1, why);
+        }
+        self.commands_done += 1;
+    }
+
+    fn is_done(&self) -> bool {
+        self.commands_done >= self.commands.len()
+    }
+
+    fn state(&self) -> Vec<String> {
+        self.stacks
+            .iter()
+            .map(|stack| stack.crates.iter().collect::<String>())
+            .collect()
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.commands_done as u64
+    }
+
+    fn total_steps(&self) -> Option<u64> {
+        Some(self.commands.len() as u64)
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`ShipYard::run_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "move" => format!(
+            "The crane moves {} {} ({}) from stack {} to stack {}.",
+            event.get("count"),
+            if event.get("count") == "1" {
+                "crate"
+            } else {
+                "crates"
+            },
+            event.get("crates"),
+            event.get("from"),
+            event.get("to")
+        ),
+        "top" => format!("The crates on top are {}.", event.get("crates")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// a recording starts with one line per stack, its name and then its crates from
+// the bottom up, and each step is the command that was run, "N from to"
+impl Record for ShipYard {
+    const KIND: &'static str = "crates";
+
+    fn record_start(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| format!("{} {}", stack.name, stack.crates.iter().collect::<String>()))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn record_step(&self) -> String {
+        let (num_crates, from_stack, to_stack) = self.commands[self.commands_done - 1];
+        format!("{} {} {}", num_crates, from_stack, to_stack)
+    }
+
+    fn replay_start(start: &str) -> Result<ShipYard, String> {
+        let mut shipyard = ShipYard::new();
+        for line in start.lines() {
+            let (name, crates) = line.split_once(' ').unwrap_or((line, ""));
+            let mut stack = Stack::new(name.to_string());
+            stack.add_crates(crates.chars().collect());
+            shipyard.stacks.push(stack);
+        }
+        shipyard.is_accepting_commands = true;
+        Ok(shipyard)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        let nums: Vec<Result<u16, _>> = step.split_whitespace().map(|n| n.parse()).collect();
+        let command = match nums[..] {
+            [Ok(num_crates), Ok(from_stack), Ok(to_stack)] => (num_crates, from_stack, to_stack),
+            _ => return Err(format!("{:?} is not a command", step)),
+        };
+        self.execute_command(command)?;
+        self.commands.push(command);
+        self.commands_done += 1;
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}Top of each stack: {}",
+            self.shipyard_status(),
+            self.top_of_each_stack()
+        )
+    }
+}
+
+impl ShipYard {
+    /// Reads the drawing of the stacks, a blank line, and then one command per
+    /// line. Fails if the blank line is missing, a crate isn't above a stack,
+    /// a command isn't `move N from S1 to S2` for stacks that are there, or a
+    /// command moves more crates than the stack will have by then.
+    pub fn parse(s: &str) -> Result<ShipYard, String> {
+        let mut shipyard = ShipYard::new();
+        let mut shipyard_data: Vec<String> = Vec::new();
+        // how many crates each stack will have when the command is run
+        let mut heights: Vec<usize> = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            // push data onto shipyard data vec until we hit a blank line or shipyard is accepting commands
+            if (!line.trim().is_empty()) && !shipyard.is_accepting_commands {
+                shipyard_data.push(line.to_string());
+                continue;
+            } else if !shipyard.is_accepting_commands {
+                // start from the bottom of the crates and work up
+                shipyard_data.reverse();
+                shipyard.parse_shipyard_data(shipyard_data.clone())?;
+                heights = shipyard.stacks.iter().map(|s| s.crates.len()).collect();
+                continue;
+            } else if line.trim().is_empty() {
+                continue;
+            }
+            // we get here when the shipyard is accepting commands and the data has been parsed
+            let command = match shipyard.parse_command(line) {
+                Ok(command) => command,
+                Err(why) => return Err(format!("line {}: {}", i + 1, why)),
+            };
+            for stack in [command.1, command.2] {
+                if stack == 0 || stack as usize > shipyard.stacks.len() {
+                    return Err(format!("line {}: there is no stack {}", i + 1, stack));
+                }
+            }
+            let (num_crates, from, to) = (
+                command.0 as usize,
+                command.1 as usize - 1,
+                command.2 as usize - 1,
+            );
+            if num_crates > heights[from] {
+                return Err(format!(
+                    "line {}: moves {} crates from stack {}, which has {}",
+                    i + 1,
+                    num_crates,
+                    from + 1,
+                    heights[from]
+                ));
+            }
+            heights[from] -= num_crates;
+            heights[to] += num_crates;
+            shipyard.commands.push(command);
+        }
+        if !shipyard.is_accepting_commands {
+            return Err("there is no blank line after the stacks".to_string());
+        }
+        Ok(shipyard)
+    }
+}
+
+/// Checks the input the way [`ShipYard::parse`] reads it, but finds every
+/// problem rather than the first. It also follows how many crates are on each
+/// stack, so a command that moves more crates than a stack has by then is
+/// reported too.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let lines: Vec<&str> = s.lines().collect();
+    let blank = match lines.iter().position(|line| line.trim().is_empty()) {
+        Some(0) => {
+            lint.at(0, "there is no drawing of the stacks");
+            return lint.problems();
+        }
+        Some(blank) => blank,
+        None => {
+            lint.whole("there is no blank line after the stacks");
+            return lint.problems();
+        }
+    };
+
+    // the last line of the drawing has the stack numbers, in the same columns as the crates
+    let numbers = lines[blank - 1];
+    let columns: Vec<usize> = (1..numbers.len().saturating_sub(1)).step_by(4).collect();
+    let names: Vec<&str> = numbers.split_whitespace().collect();
+    let numbered = names.len() == columns.len()
+        && names
+            .iter()
+            .enumerate()
+            .all(|(n, name)| *name == (n + 1).to_string());
+    if !numbered {
+        lint.at(
+            blank - 1,
+            format!("the stacks aren't numbered 1 to {}", columns.len()),
+        );
+    }
+    let mut heights: Vec<usize> = vec![0; columns.len()];
+    for (i, line) in lines[..blank - 1].iter().enumerate() {
+        for (n, c) in line.chars().skip(1).step_by(4).enumerate() {
+            if c == ' ' {
+                continue;
+            }
+            match heights.get_mut(n) {
+                Some(height) => *height += 1,
+                None => lint.at(i, format!("crate {} is not above a stack", c)),
+            }
+        }
+    }
+
+    let shipyard = ShipYard::new();
+    for (i, line) in lines.iter().enumerate().skip(blank + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (num_crates, from, to) = match shipyard.parse_command(line) {
+            Ok(command) => command,
+            Err(why) => {
+                lint.at(i, why);
+                continue;
+            }
+        };
+        let mut stacks_exist = true;
+        for stack in [from, to] {
+            if stack == 0 || stack as usize > heights.len() {
+                lint.at(i, format!("there is no stack {}", stack));
+                stacks_exist = false;
+            }
+        }
+        if !stacks_exist {
+            continue;
+        }
+        let (from, to) = (from as usize - 1, to as usize - 1);
+        let num_crates = num_crates as usize;
+        if num_crates > heights[from] {
+            lint.at(
+                i,
+                format!(
+                    "moves {} crates from stack {}, which has {}",
+                    num_crates,
+                    from + 1,
+                    heights[from]
+                ),
+            );
+        }
+        // carry on with the crates that are there
+        let moved = num_crates.min(heights[from]);
+        heights[from] -= moved;
+        heights[to] += moved;
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                           move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\n\
+                           move 1 from 1 to 2\n";
+
+    #[test]
+    fn test_example() {
+        let mut shipyard = ShipYard::parse(EXAMPLE).unwrap();
+        assert_eq!(shipyard.commands().len(), 4);
+        assert_eq!(shipyard.top_of_each_stack(), "NDP");
+        shipyard.run();
+        // the CrateMover 9001 moves the crates in one go, so they keep their order
+        assert_eq!(shipyard.top_of_each_stack(), "MCD");
+        assert_eq!(
+            shipyard.shipyard_status(),
+            "1: [1] M\n2: [1] C\n3: [4] P Z N D\n"
+        );
+    }
+
+    #[test]
+    fn test_explain_the_moves() {
+        let mut shipyard = ShipYard::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        shipyard.run_traced(&mut trace);
+        assert_eq!(
+            trace.render(narrate),
+            "The crane moves 1 crate (D) from stack 2 to stack 1.\n\
+             The crane moves 3 crates (ZND) from stack 1 to stack 3.\n\
+             The crane moves 2 crates (MC) from stack 2 to stack 1.\n\
+             The crane moves 1 crate (C) from stack 1 to stack 2.\n\
+             The crates on top are MCD.\n"
+        );
+    }
+
+    #[test]
+    fn test_moving_more_crates_than_there_are() {
+        let too_many = EXAMPLE.replace("move 2 from 2 to 1", "move 3 from 2 to 1");
+        assert_eq!(
+            ShipYard::parse(&too_many).err(),
+            Some("line 8: moves 3 crates from stack 2, which has 2".to_string())
+        );
+        let missing = EXAMPLE.replace("move 1 from 2 to 1", "move 1 from 4 to 1");
+        assert_eq!(
+            ShipYard::parse(&missing).err(),
+            Some("line 6: there is no stack 4".to_string())
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs;
+
+use y2022_day05::shared;
+use y2022_day05::ShipYard;
+use y2022_day05::{DAY, YEAR};
+
+use shared::replay::Recorder;
+use shared::simulation::{self, Runner};
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, None));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day05", &filename, |s| ShipYard::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let mut shipyard = match ShipYard::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(shipyard) => shipyard,
+    };
+    // shipyard will now be parsing data
+    println!("*******\nShipyard is accepting commands\n*******");
+    display_shipyard_status(&shipyard);
+
+    simulation::run_from_args(&mut shipyard, Runner::new().observe(Recorder::from_args()));
+    println!("*******\nShipyward commands completed\n*******");
+    display_shipyard_status(&shipyard);
+    display_top_of_each_stack(&shipyard);
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        // the simulation above has already run, so run the commands again
+        if let Ok(mut shipyard) = ShipYard::parse(&data) {
+            shipyard.run_traced(&mut trace);
+            println!("\n{}", trace.render(y2022_day05::narrate));
+        }
+    }
+}
+
+fn display_top_of_each_stack(shipyard: &ShipYard) {
+    let mut s: String = String::new();
+    for stack in shipyard.stacks() {
+        let top = stack.crates.last();
+        match top {
+            Some(x) => s.push(*x),
+            None => print!("  "),
+        }
+    }
+    println!("Crates at top of each stack (from first to last): {}", s);
+}
+
+fn display_shipyard_status(shipyard: &ShipYard) {
+    println!("Shipyard status:");
+    print!("{}", shipyard.shipyard_status());
+    println!("-----");
+}
@@ -0,0 +1,228 @@
+//! Day 6: Tuning Trouble.
+//!
+//! The input is a signal of lowercase letters. A marker is the first window of
+//! characters that are all different: 4 for the start of a packet and 14 for
+//! the start of a message. [`Signal::parse`] reads the signal and
+//! [`Signal::find_marker`] finds a marker.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 6;
+
+/// The number of different characters that start a packet.
+pub const PACKET_MARKER_SIZE: usize = 4;
+/// The number of different characters that start a message.
+pub const MESSAGE_MARKER_SIZE: usize = 14;
+
+struct LifoQueue {
+    stack: Vec<char>,
+    max_size: usize,
+}
+
+impl LifoQueue {
+    fn new(max_size: usize) -> LifoQueue {
+        LifoQueue {
+            stack: Vec::new(),
+            max_size: max_size,
+        }
+    }
+
+    fn push(&mut self, c: char) {
+        self.stack.push(c);
+        if self.len() > self.max_size {
+            self.stack.remove(0);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn is_unique(&self) -> bool {
+        let mut unique = true;
+        for c in &self.stack {
+            // if the stack contains more than one of this character, it's not unique
+            // it's only 14 characters, so we can just check each one
+            if self.stack.iter().filter(|&x| x == c).count() > 1 {
+                // println!("{} is not unique", c);
+                unique = false;
+                break;
+            }
+        }
+        unique
+    }
+}
+
+/// The characters of the signal.
+#[derive(Debug, Clone)]
+pub struct Signal {
+    chars: Vec<char>,
+}
+
+impl Signal {
+    /// Reads the signal. Fails if it is empty or has anything other than
+    /// lowercase letters.
+    pub fn parse(s: &str) -> Result<Signal, String> {
+        Ok(Signal {
+            chars: parse_signal(s)?,
+        })
+    }
+
+    /// Every character of the signal, in order.
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    /// The 0-index of the last character of the first window of `queue_size`
+    /// different characters, or None if there isn't one.
+    pub fn find_marker(&self, queue_size: usize) -> Option<usize> {
+        self.find_marker_traced(queue_size, &mut Trace::default())
+    }
+
+    /// [`Signal::find_marker`], recording each full window with a repeated
+    /// character and then the marker in `trace` (see [`narrate`]).
+    pub fn find_marker_traced(&self, queue_size: usize, trace: &mut Trace) -> Option<usize> {
+        let marker = find_marker(&self.chars, queue_size, trace);
+        if let Some(end) = marker {
+            let window: String = self.chars[end + 1 - queue_size..=end].iter().collect();
+            trace.event("marker", &[("read", &(end + 1)), ("window", &window)]);
+        }
+        marker
+    }
+
+    /// The window of `queue_size` characters ending at the 0-index `end`, or
+    /// None if the window would start before the signal or end after it.
+    pub fn window(&self, end: usize, queue_size: usize) -> Option<&[char]> {
+        let start = (end + 1).checked_sub(queue_size)?;
+        self.chars.get(start..end + 1)
+    }
+}
+
+// the signal is one line of lowercase letters
+fn parse_signal(s: &str) -> Result<Vec<char>, String> {
+    let signal: Vec<char> = s.trim().chars().collect();
+    if signal.is_empty() {
+        return Err("the signal is empty".to_string());
+    }
+    if let Some(c) = signal.iter().find(|c| !c.is_ascii_lowercase()) {
+        return Err(format!("{:?} is not part of a signal", c));
+    }
+    Ok(signal)
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Signal::find_marker_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "repeat" => format!(
+            "After {} characters the last ones are {}, and {} is repeated.",
+            event.get("read"),
+            event.get("window"),
+            event.get("char")
+        ),
+        "marker" => format!(
+            "After {} characters the last ones are {}, which are all different.",
+            event.get("read"),
+            event.get("window")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// returns the 0-index of the last character of the first unique window
+fn find_marker(signal: &[char], queue_size: usize, trace: &mut Trace) -> Option<usize> {
+    let mut q: LifoQueue = LifoQueue::new(queue_size);
+    for _i in 0..signal.len() {
+        q.push(signal[_i]);
+        if q.len() == queue_size && q.is_unique() {
+            return Some(_i);
+        }
+        if q.len() == queue_size && trace.is_enabled() {
+            let repeated = q
+                .stack
+                .iter()
+                .find(|&c| q.stack.iter().filter(|&x| x == c).count() > 1);
+            let window: String = q.stack.iter().collect();
+            trace.event(
+                "repeat",
+                &[
+                    ("read", &(_i + 1)),
+                    ("window", &window),
+                    ("char", &repeated.unwrap()),
+                ],
+            );
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        // each example and the number of characters read to the end of each marker
+        let examples = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+        for (input, packet, message) in examples {
+            let signal = Signal::parse(input).unwrap();
+            assert_eq!(
+                signal.find_marker(PACKET_MARKER_SIZE),
+                Some(packet - 1),
+                "{}",
+                input
+            );
+            assert_eq!(
+                signal.find_marker(MESSAGE_MARKER_SIZE),
+                Some(message - 1),
+                "{}",
+                input
+            );
+        }
+        let signal = Signal::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        assert_eq!(signal.window(6, 4), Some(&['j', 'p', 'q', 'm'][..]));
+        assert_eq!(signal.window(2, 4), None);
+    }
+
+    #[test]
+    fn test_explain_the_packet_marker() {
+        let signal = Signal::parse("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(
+            signal.find_marker_traced(PACKET_MARKER_SIZE, &mut trace),
+            Some(6)
+        );
+        assert_eq!(
+            trace.render(narrate),
+            "After 4 characters the last ones are mjqj, and j is repeated.\n\
+             After 5 characters the last ones are jqjp, and j is repeated.\n\
+             After 6 characters the last ones are qjpq, and q is repeated.\n\
+             After 7 characters the last ones are jpqm, which are all different.\n"
+        );
+    }
+
+    #[test]
+    fn test_no_marker() {
+        let signal = Signal::parse("aaaaabbbbb").unwrap();
+        assert_eq!(signal.find_marker(PACKET_MARKER_SIZE), None);
+        assert_eq!(
+            Signal::parse(" \n").err(),
+            Some("the signal is empty".to_string())
+        );
+        assert_eq!(
+            Signal::parse("abC").err(),
+            Some("'C' is not part of a signal".to_string())
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::fs;
+
+use y2022_day06::shared;
+use y2022_day06::{Signal, MESSAGE_MARKER_SIZE};
+use y2022_day06::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, Some(2)));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    let queue_size = MESSAGE_MARKER_SIZE;
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day06", &filename, |s| {
+            Signal::parse(s).map(|signal| {
+                signal.find_marker(queue_size);
+            })
+        });
+        return;
+    }
+
+    let signal_data =
+        fs::read_to_string(&filename).expect("Should have been able to read the file");
+    let signal: Signal = match Signal::parse(&signal_data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(signal) => signal,
+    };
+
+    match signal.find_marker(queue_size) {
+        Some(_i) => {
+            println!("***************************************");
+            println!("Unique signal found");
+            if let Some(window) = signal.window(_i, queue_size) {
+                println!("Stack: {:?}", window);
+            }
+            println!(
+                "0-Index {} - Position {} - Character {}",
+                _i,
+                _i + 1,
+                signal.chars()[_i]
+            );
+        }
+        None => {
+            println!("***************************************");
+            println!("No unique signal found");
+        }
+    }
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        signal.find_marker_traced(queue_size, &mut trace);
+        println!("\n{}", trace.render(y2022_day06::narrate));
+    }
+}
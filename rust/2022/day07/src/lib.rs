@@ -0,0 +1,395 @@
+//! Day 7: No Space Left On Device.
+//!
+//! The input is the output of `cd` and `ls` commands run on the elves' device.
+//! [`ElfFs::parse`] rebuilds the directories from it, and
+//! [`ElfFs::get_dir_size`] gives the size of a directory including
+//! everything under it.
+
+use std::collections::HashMap;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::invariant;
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 7;
+
+/// The size of the device.
+pub const TOTAL_SPACE: u64 = 70_000_000;
+/// The free space the update needs.
+pub const FREE_SPACE_NEEDED: u64 = 30_000_000;
+
+/// The directories on the device, by path. The root is `/` and the others
+/// are like `/a/e`.
+pub struct ElfFs {
+    dirs: HashMap<String, Dir>,
+}
+
+impl ElfFs {
+    fn new() -> ElfFs {
+        ElfFs {
+            dirs: HashMap::new(),
+        }
+    }
+
+    /// The paths of all the directories, sorted.
+    pub fn dir_paths(&self) -> Vec<&String> {
+        let mut all_dirs: Vec<&String> = self.dirs.keys().collect();
+        all_dirs.sort();
+        all_dirs
+    }
+
+    /// The directory at `path`, if the commands visited or listed it.
+    pub fn dir(&self, path: &str) -> Option<&Dir> {
+        self.dirs.get(path)
+    }
+
+    /// The size of the directory at `path` and everything under it, or 0 if
+    /// there is no such directory.
+    pub fn get_dir_size(&self, path: String) -> u64 {
+        let mut size: u64 = 0;
+        if self.dirs.contains_key(&path) {
+            let dir: &Dir = self.dirs.get(&path).unwrap();
+            size = checked::add(SIZE, size, dir.size());
+            for f in dir.files.iter() {
+                if f.filetype.eq("dir") {
+                    let subdir_size = if path.eq("/") {
+                        self.get_dir_size(format!("/{}", f.name))
+                    } else {
+                        self.get_dir_size(format!("{}/{}", path, f.name))
+                    };
+                    size = checked::add(SIZE, size, subdir_size);
+                }
+            }
+        }
+        if path.eq("/") {
+            // a directory that was visited but never listed in its parent, or
+            // listed twice, would be left out or counted twice
+            let files = || checked::sum(SIZE, self.dirs.values().map(|d| d.size()));
+            invariant::check(
+                "day07 / holds every file",
+                || size == files(),
+                || format!("/ is {}, all the files add up to {}", size, files()),
+            );
+        }
+        size
+    }
+
+    /// The path and size of the smallest directory that frees up enough space
+    /// for the update, or None if none is big enough.
+    pub fn dir_to_delete(&self) -> Option<(&String, u64)> {
+        self.dir_to_delete_traced(&mut Trace::default())
+    }
+
+    /// [`ElfFs::dir_to_delete`], recording the space to free, what deleting
+    /// each directory would free and the one chosen in `trace` (see
+    /// [`narrate`]).
+    pub fn dir_to_delete_traced(&self, trace: &mut Trace) -> Option<(&String, u64)> {
+        let used_space = self.get_dir_size("/".to_string());
+        // nothing needs to be removed if there's already enough space
+        let space_to_find = used_space.saturating_sub(TOTAL_SPACE - FREE_SPACE_NEEDED);
+        trace.event(
+            "used",
+            &[
+                ("used", &used_space),
+                ("total", &TOTAL_SPACE),
+                ("find", &space_to_find),
+            ],
+        );
+        let mut smallest: Option<(&String, u64)> = None;
+        for path in self.dir_paths() {
+            let size = self.get_dir_size(path.to_string());
+            let enough = size >= space_to_find;
+            trace.event(
+                "dir",
+                &[("path", path), ("size", &size), ("enough", &enough)],
+            );
+            if enough && smallest.is_none_or(|(_, smallest_size)| size < smallest_size) {
+                smallest = Some((path, size));
+            }
+        }
+        if let Some((path, size)) = smallest {
+            trace.event("delete", &[("path", path), ("size", &size)]);
+        }
+        smallest
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`ElfFs::dir_to_delete_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "used" => format!(
+            "The device is using {} of {}, so {} more must be freed.",
+            event.get("used"),
+            event.get("total"),
+            event.get("find")
+        ),
+        "dir" => format!(
+            "Deleting {} would free {}, which is {}.",
+            event.get("path"),
+            event.get("size"),
+            if event.get("enough") == "true" {
+                "enough"
+            } else {
+                "not enough"
+            }
+        ),
+        "delete" => format!(
+            "The smallest directory that frees enough is {}, of {}.",
+            event.get("path"),
+            event.get("size")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+/// A directory and what `ls` listed in it.
+pub struct Dir {
+    pub path: String,
+    pub files: Vec<ElfFile>,
+}
+
+impl Dir {
+    fn new(path: String) -> Dir {
+        Dir {
+            path: path,
+            files: Vec::new(),
+        }
+    }
+
+    /// The size of the files directly in the directory.
+    pub fn size(&self) -> u64 {
+        checked::sum(SIZE, self.files.iter().map(|f| f.size))
+    }
+}
+
+/// A file or directory listed by `ls`.
+#[derive(Debug)]
+pub struct ElfFile {
+    pub name: String,
+    pub size: u64,        // is 0 for dir
+    pub filetype: String, // file or dir
+}
+
+// what the sizes are called when they overflow in --checked mode
+const SIZE: &str = "day07 directory size";
+
+// names can't be empty or walk somewhere else
+fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.eq(".") || name.eq("..") || name.contains('/') {
+        return Err(format!("{:?} is not a valid name", name));
+    }
+    Ok(())
+}
+
+impl ElfFs {
+    /// Reads the terminal output, one command or listing per line. Fails on
+    /// an unknown command, a listing without `ls`, or a name that isn't valid.
+    pub fn parse(s: &str) -> Result<ElfFs, String> {
+        parse_terminal(s)
+    }
+}
+
+/// Checks the terminal output the way [`ElfFs::parse`] reads it, but finds
+/// every problem rather than the first. It also reports what the parser lets
+/// through: an `ls` before any `cd`, which the parser takes to be in `/`, and
+/// a `cd ..` out of `/`.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut depth: usize = 0;
+    let mut seen_cd = false;
+    let mut read_data = false;
+    for (i, line) in s.lines().enumerate() {
+        let data: Vec<&str> = line.split(' ').collect();
+        if data.len() < 2 {
+            lint.at(i, format!("{:?} is not a command or a listing", line));
+            continue;
+        }
+        if data[0] == "$" {
+            read_data = false;
+            if data[1] == "cd" && data.len() == 3 {
+                if data[2] == ".." {
+                    if !seen_cd || depth == 0 {
+                        lint.at(i, "cd .. out of /");
+                    }
+                    depth = depth.saturating_sub(1);
+                } else if data[2] == "/" {
+                    depth = 0;
+                } else {
+                    if let Err(why) = check_name(data[2]) {
+                        lint.at(i, why);
+                    }
+                    depth += 1;
+                }
+                seen_cd = true;
+            } else if data[1] == "ls" && data.len() == 2 {
+                if !seen_cd {
+                    lint.at(
+                        i,
+                        "ls before any cd, so which directory it lists isn't known",
+                    );
+                }
+                read_data = true;
+            } else {
+                lint.at(i, format!("unknown command {:?}", line));
+            }
+            continue;
+        }
+        if !read_data {
+            lint.at(i, "listing without ls");
+        }
+        if data.len() != 2 {
+            lint.at(i, format!("{:?} is not a listing", line));
+            continue;
+        }
+        if let Err(why) = check_name(data[1]) {
+            lint.at(i, why);
+        }
+        if data[0] != "dir" && data[0].parse::<u64>().is_err() {
+            lint.at(i, format!("{:?} is not a size", data[0]));
+        }
+    }
+    lint.problems()
+}
+
+// the terminal output, one command or listing per line
+fn parse_terminal(s: &str) -> Result<ElfFs, String> {
+    let mut wd: Vec<String> = Vec::new();
+    // let mut path: String = String::new();
+    // let mut dirs: HashMap<String, Dir> = HashMap::new(); // ref by path (wd)
+    let mut elffs: ElfFs = ElfFs::new();
+
+    let root_path = "/".to_string();
+    elffs
+        .dirs
+        .insert(root_path.clone(), Dir::new(root_path.clone()));
+    let mut current_dir: &mut Dir = elffs.dirs.get_mut(&root_path).unwrap();
+    let mut read_data: bool = false;
+
+    for (i, s) in s.lines().enumerate() {
+        let data: Vec<&str> = s.split(' ').collect::<Vec<&str>>();
+        if data.len() < 2 {
+            return Err(format!(
+                "line {}: {:?} is not a command or a listing",
+                i + 1,
+                s
+            ));
+        }
+        if data[0].eq("$") {
+            read_data = false;
+            // println!("Command: {}", s);
+            if data[1].eq("cd") && data.len() == 3 {
+                if data[2].eq("..") {
+                    wd.pop();
+                } else if data[2].eq("/") {
+                    wd.clear();
+                } else {
+                    check_name(data[2]).map_err(|why| format!("line {}: {}", i + 1, why))?;
+                    wd.push(data[2].to_string());
+                }
+                let path = format!("/{}", wd.join("/"));
+                // println!("New path: {}", path);
+                if !elffs.dirs.contains_key(&path) {
+                    elffs.dirs.insert(path.clone(), Dir::new(path.clone()));
+                }
+                current_dir = elffs.dirs.get_mut(&path).unwrap();
+            } else if data[1].eq("ls") && data.len() == 2 {
+                read_data = true;
+            } else {
+                return Err(format!("line {}: unknown command {:?}", i + 1, s));
+            }
+        } else {
+            if !read_data {
+                return Err(format!("line {}: listing without ls", i + 1));
+            }
+            if data.len() != 2 {
+                return Err(format!("line {}: {:?} is not a listing", i + 1, s));
+            }
+            check_name(data[1]).map_err(|why| format!("line {}: {}", i + 1, why))?;
+            if data[0].eq("dir") {
+                // it's a directory
+                current_dir.files.push(ElfFile {
+                    name: data[1].to_string(),
+                    size: 0,
+                    filetype: "dir".to_string(),
+                });
+            } else {
+                // it's a file
+                let size = match data[0].parse::<u64>() {
+                    Ok(size) => size,
+                    Err(_) => return Err(format!("line {}: {:?} is not a size", i + 1, data[0])),
+                };
+                current_dir.files.push(ElfFile {
+                    name: data[1].to_string(),
+                    size: size,
+                    filetype: "file".to_string(),
+                });
+            }
+        }
+    }
+    Ok(elffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n\
+                           $ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n\
+                           584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n\
+                           5626152 d.ext\n7214296 k\n";
+
+    #[test]
+    fn test_example() {
+        let elffs = ElfFs::parse(EXAMPLE).unwrap();
+        assert_eq!(elffs.dir_paths(), ["/", "/a", "/a/e", "/d"]);
+        let sizes: Vec<u64> = elffs
+            .dir_paths()
+            .iter()
+            .map(|path| elffs.get_dir_size(path.to_string()))
+            .collect();
+        assert_eq!(sizes, [48381165, 94853, 584, 24933642]);
+        assert_eq!(elffs.dir("/a/e").unwrap().size(), 584);
+        assert_eq!(elffs.get_dir_size("/x".to_string()), 0);
+
+        // part 1 adds up the directories of at most 100000, and part 2 is the
+        // smallest directory that frees up enough space
+        let small: u64 = sizes.iter().filter(|size| **size <= 100_000).sum();
+        assert_eq!(small, 95437);
+        let space_to_find = sizes[0] - (TOTAL_SPACE - FREE_SPACE_NEEDED);
+        let smallest = sizes.iter().filter(|size| **size >= space_to_find).min();
+        assert_eq!(smallest, Some(&24933642));
+        assert_eq!(elffs.dir_to_delete(), Some((&"/d".to_string(), 24933642)));
+    }
+
+    #[test]
+    fn test_explain_the_dir_to_delete() {
+        let elffs = ElfFs::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        elffs.dir_to_delete_traced(&mut trace);
+        assert_eq!(
+            trace.render(narrate),
+            "The device is using 48381165 of 70000000, so 8381165 more must be freed.\n\
+             Deleting / would free 48381165, which is enough.\n\
+             Deleting /a would free 94853, which is not enough.\n\
+             Deleting /a/e would free 584, which is not enough.\n\
+             Deleting /d would free 24933642, which is enough.\n\
+             The smallest directory that frees enough is /d, of 24933642.\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            ElfFs::parse("$ cd /\n$ cd ../etc\n").err(),
+            Some("line 2: \"../etc\" is not a valid name".to_string())
+        );
+    }
+}
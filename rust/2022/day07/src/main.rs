@@ -0,0 +1,84 @@
+use std::fs;
+
+use y2022_day07::shared;
+use y2022_day07::{ElfFs, FREE_SPACE_NEEDED, TOTAL_SPACE};
+use y2022_day07::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}", shared::event::banner(YEAR, DAY, Some(2)));
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day07", &filename, |s| ElfFs::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let elffs: ElfFs = match ElfFs::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(elffs) => elffs,
+    };
+
+    let all_dirs: Vec<&String> = elffs.dir_paths();
+
+    let used_space: u64 = elffs.get_dir_size("/".to_string());
+    let total_space: u64 = TOTAL_SPACE;
+    let free_space_needed: u64 = FREE_SPACE_NEEDED;
+    // nothing needs to be removed if there's already enough space
+    let space_to_find: u64 = used_space.saturating_sub(total_space - free_space_needed);
+
+    println!("\nUsed space: {}", used_space);
+    println!("Total space: {}", total_space);
+    println!("Free space needed: {}", free_space_needed);
+    println!("Need to remove: {}", space_to_find);
+
+    let mut diff: u64 = used_space.clone();
+    let mut closest_path: String = String::new();
+
+    println!("\nDirectories that can be removed to allow update:");
+
+    println!(
+        "{:>10}\t{:>15}\t{:>10}\t{}",
+        "Dir Size", "Space After Del", "Diff (needed)", "Path"
+    );
+    for d_path in all_dirs.iter() {
+        let rec_size: u64 = elffs.get_dir_size(d_path.to_string());
+        let space_after: u64 = used_space - rec_size;
+        if rec_size < space_to_find {
+            continue;
+        }
+        let d_diff: u64 = rec_size - space_to_find;
+        println!(
+            "{:>10}\t{:>15}\t{:>10}\t{}",
+            rec_size, space_after, d_diff, d_path
+        );
+        if d_diff < diff && d_diff > 0 {
+            diff = d_diff.clone();
+            closest_path = d_path.to_string();
+        }
+    }
+
+    println!(
+        "\nPath of smallest directory to remove to allow update: {}",
+        closest_path
+    );
+    println!("Diff: {}", diff);
+    let rec_size = elffs.get_dir_size(closest_path.to_string());
+    println!("Dir Size: {}", rec_size);
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        elffs.dir_to_delete_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day07::narrate));
+    }
+}
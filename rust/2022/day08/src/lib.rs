@@ -0,0 +1,476 @@
+//! Day 8: Treetop Tree House.
+//!
+//! The input is a square grid of tree heights. [`Forest::parse`] reads it.
+//! Part 1 is the number of trees visible from outside the grid and part 2 is
+//! the highest scenic score, the product of how far a tree can see in each
+//! direction.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 8;
+
+use shared::checked;
+use shared::grid;
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+use shared::viewport::{Grid, Viewport};
+
+fn get_visible_trees(tree_heights: &mut Vec<i16>) -> Vec<u8> {
+    let mut current_visible_height: i16 = -1; // start below 0
+    let mut visible_grid: Vec<u8> = Vec::new();
+    for tree_height in tree_heights {
+        if *tree_height > current_visible_height {
+            // set the current visible height to this height
+            current_visible_height = tree_height.clone();
+            // increment the number of visible trees
+            visible_grid.push(1);
+        } else {
+            visible_grid.push(0);
+        }
+    }
+    visible_grid
+}
+
+fn get_visible_trees_both_ways(trees: &mut Vec<i16>, _h: &i16) -> Vec<i8> {
+    let west_visible = get_visible_trees(trees);
+    trees.reverse();
+    let mut east_visible = get_visible_trees(trees);
+    let mut visible_trees: Vec<i8> = Vec::new();
+    east_visible.reverse();
+    for i in 0..trees.len() {
+        if west_visible[i] == 1 || east_visible[i] == 1 {
+            visible_trees.push(1);
+        } else {
+            visible_trees.push(0);
+        }
+    }
+    trees.reverse(); // just for completeness so we haven't just left it reversed
+
+    visible_trees
+}
+
+#[cfg(test)]
+fn get_scenic_score(tree_data: &[i16], start_height: &i16) -> i32 {
+    let mut score: i32 = 0;
+
+    // this is the number of trees the current tree can "see"
+    // the tree_data is the height of the trees leading away from the
+    // tree in whatever direction is required
+    for i in 0..tree_data.len() {
+        score += 1;
+        // if we hit a tree that is greater than or equal to the current
+        // start_height then we can't see any more trees
+        if tree_data[i] >= *start_height {
+            break;
+        }
+    }
+
+    score
+}
+
+// the tree heights moving away from pos in both directions along a row or
+// column: first towards the start, then towards the end
+fn get_tree_data_sets(data: &[i16], pos: usize) -> (Vec<i16>, Vec<i16>) {
+    // take in a list of tree heights and a position
+    // and return two sets from the position
+    // that are the tree heights moving away from that position
+    let mut reverse_set = data.to_vec();
+    let forward_set = reverse_set.split_off(pos + 1);
+    if reverse_set.len() > 0 {
+        reverse_set.pop();
+    }
+    reverse_set.reverse();
+    (reverse_set, forward_set)
+}
+
+/// How far each tree can see to the north, south, east and west.
+///
+/// This walks each row and column once, keeping a stack of the trees that
+/// could still block the view of the trees after them, so it doesn't need the
+/// tree data sets for every tree.
+pub fn get_viewing_distances(tree_grid_h: &Vec<Vec<i16>>) -> Vec<Vec<[i32; 4]>> {
+    let height = tree_grid_h.len();
+    let width = if height > 0 { tree_grid_h[0].len() } else { 0 };
+    let mut distances: Vec<Vec<[i32; 4]>> = vec![vec![[0; 4]; width]; height];
+
+    // looks along a line of trees (given as a list of (h, v) positions) and
+    // sets how far each tree can see back towards the start of the line
+    let mut look_back = |line: Vec<(usize, usize)>, direction: usize| {
+        let mut blocking: Vec<usize> = Vec::new();
+        for i in 0..line.len() {
+            let (h, v) = line[i];
+            let tree_height = tree_grid_h[h][v];
+            // trees smaller than this one can't block anything after it
+            while let Some(&j) = blocking.last() {
+                let (bh, bv) = line[j];
+                if tree_grid_h[bh][bv] >= tree_height {
+                    break;
+                }
+                blocking.pop();
+            }
+            distances[h][v][direction] = match blocking.last() {
+                Some(&j) => (i - j) as i32,
+                None => i as i32, // can see all the way to the edge
+            };
+            blocking.push(i);
+        }
+    };
+
+    for h in 0..height {
+        let row: Vec<(usize, usize)> = (0..width).map(|v| (h, v)).collect();
+        look_back(row.iter().rev().cloned().collect(), 2); // east
+        look_back(row, 3); // west
+    }
+    for v in 0..width {
+        let column: Vec<(usize, usize)> = (0..height).map(|h| (h, v)).collect();
+        look_back(column.iter().rev().cloned().collect(), 1); // south
+        look_back(column, 0); // north
+    }
+
+    distances
+}
+
+// the tree heights in a row as a string of digits
+fn get_row_as_string(row: &[i16]) -> String {
+    row.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+/// The grid of tree heights, by row (h) and then column (v).
+#[derive(Debug, Clone)]
+pub struct Forest {
+    rows: Vec<Vec<i16>>,
+}
+
+impl Forest {
+    /// Reads one row of tree heights per line. Fails on anything that isn't a
+    /// digit, or if the grid isn't square.
+    pub fn parse(s: &str) -> Result<Forest, String> {
+        Ok(Forest {
+            rows: parse_rows(s)?,
+        })
+    }
+
+    pub fn rows(&self) -> &Vec<Vec<i16>> {
+        &self.rows
+    }
+
+    /// The tree heights in column `v`, from north to south.
+    pub fn column(&self, v: usize) -> Vec<i16> {
+        self.rows.iter().map(|row| row[v]).collect()
+    }
+
+    /// The number of trees along each side of the grid.
+    pub fn size(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// A grid with a 1 for each tree that can be seen from outside the grid
+    /// and a 0 for the others.
+    pub fn visible_trees(&self) -> Vec<Vec<i8>> {
+        // trees visible will hold the trees in the horizontal (east-west)
+        let mut trees_visible: Vec<Vec<i8>> = Vec::new();
+        for (h, row) in self.rows.iter().enumerate() {
+            trees_visible.push(get_visible_trees_both_ways(&mut row.clone(), &(h as i16)));
+        }
+
+        // then loop over the grid rotated by 90 degrees
+        for v in 0..self.size() {
+            let visible_trees_v = get_visible_trees_both_ways(&mut self.column(v), &(v as i16));
+            for h in 0..trees_visible.len() {
+                if visible_trees_v[h] > 0 {
+                    trees_visible[h][v] = 1;
+                }
+            }
+        }
+        trees_visible
+    }
+
+    /// The number of trees that can be seen from outside the grid. Part 1.
+    pub fn visible_count(&self) -> u64 {
+        let visible = self.visible_trees().into_iter().flatten().map(|x| x as u64);
+        checked::sum("day08 visible trees", visible)
+    }
+
+    /// How far each tree can see to the north, south, east and west.
+    pub fn viewing_distances(&self) -> Vec<Vec<[i32; 4]>> {
+        get_viewing_distances(&self.rows)
+    }
+
+    /// The highest scenic score and the (h, v) of the first tree with it.
+    /// Part 2.
+    pub fn max_scenic_score(&self) -> (u64, (usize, usize)) {
+        self.max_scenic_score_traced(&mut Trace::default())
+    }
+
+    /// [`Forest::max_scenic_score`], recording each tree's view in every
+    /// direction and its score in `trace` (see [`narrate`]).
+    pub fn max_scenic_score_traced(&self, trace: &mut Trace) -> (u64, (usize, usize)) {
+        let mut max_scenic_score: u64 = 0;
+        let mut max_point: (usize, usize) = (0, 0);
+        for (h, row) in self.viewing_distances().iter().enumerate() {
+            for (v, distances) in row.iter().enumerate() {
+                self.trace_tree(trace, h, v, distances);
+                // four distances multiplied can be too big for an i32 on a large forest
+                let distances_u64 = distances.iter().map(|d| *d as u64);
+                let score = checked::product("day08 scenic score", distances_u64);
+                trace.event("score", &[("h", &h), ("v", &v), ("score", &score)]);
+                if score > max_scenic_score {
+                    max_scenic_score = score;
+                    max_point = (h, v);
+                }
+            }
+        }
+        (max_scenic_score, max_point)
+    }
+
+    // the whole grid with the tree at (h, v) marked .
+    fn marked_grid(&self, h: usize, v: usize) -> String {
+        let mut grid = String::new();
+        for (inside_h, row) in self.rows.iter().enumerate() {
+            let mut data = row.clone();
+            if inside_h != h {
+                grid.push_str(&get_row_as_string(&data));
+            } else {
+                let data_end = data.split_off(v + 1);
+                data.pop();
+                grid.push_str(&get_row_as_string(&data));
+                grid.push_str(".");
+                grid.push_str(&get_row_as_string(&data_end));
+            }
+            grid.push_str("\n");
+        }
+        grid
+    }
+
+    // the grid with the tree at (h, v) marked, and the trees it can see in
+    // each direction, or only the window the viewport flags give around it
+    fn trace_tree(&self, trace: &mut Trace, h: usize, v: usize, distances: &[i32; 4]) {
+        if !trace.is_enabled() {
+            return;
+        }
+        let height = self.rows[h][v];
+        let grid = match Viewport::from_args() {
+            Some(view) => view.render(&TreeView { forest: self, h, v }),
+            None => self.marked_grid(h, v),
+        };
+        trace.event(
+            "tree",
+            &[("h", &h), ("v", &v), ("height", &height), ("grid", &grid)],
+        );
+
+        let (north, south) = get_tree_data_sets(&self.column(v), h);
+        let (west, east) = get_tree_data_sets(&self.rows[h], v);
+        let directions = [
+            ("North", north),
+            ("South", south),
+            ("East", east),
+            ("West", west),
+        ];
+        for (i, (direction, trees)) in directions.iter().enumerate() {
+            trace.event(
+                "look",
+                &[
+                    ("direction", direction),
+                    ("score", &distances[i]),
+                    ("height", &height),
+                    ("trees", &get_row_as_string(trees)),
+                ],
+            );
+        }
+    }
+}
+
+// the forest as the viewport sees it, x along a row (v) and y down the rows
+// (h), with the tree being looked at marked . and followed
+struct TreeView<'a> {
+    forest: &'a Forest,
+    h: usize,
+    v: usize,
+}
+
+impl Grid for TreeView<'_> {
+    const PRIORITY: &'static str = ".9876543210";
+
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        let last = self.forest.size() as i64 - 1;
+        ((0, 0), (last, last))
+    }
+
+    fn glyph(&self, x: i64, y: i64) -> char {
+        let (h, v) = (y as usize, x as usize);
+        if (h, v) == (self.h, self.v) {
+            '.'
+        } else {
+            char::from_digit(self.forest.rows[h][v] as u32, 10).unwrap_or('?')
+        }
+    }
+
+    fn focus(&self) -> Option<(i64, i64)> {
+        Some((self.v as i64, self.h as i64))
+    }
+
+    fn marks(&self) -> Vec<((i64, i64), char)> {
+        vec![((self.v as i64, self.h as i64), '.')]
+    }
+}
+
+/// The lines the old `print_full` debugging printed, for an event recorded
+/// by [`Forest::max_scenic_score_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "tree" => format!(
+            "h: {}, v: {}\n******\nTree Height: {}\n\n{}",
+            event.get("h"),
+            event.get("v"),
+            event.get("height"),
+            event.get("grid")
+        ),
+        "look" => format!(
+            "{:>9} score: {:>3} {}-{}",
+            event.get("direction"),
+            event.get("score"),
+            event.get("height"),
+            event.get("trees")
+        ),
+        "score" => format!(
+            "Scenic Score ({},{}): {:>3}",
+            event.get("h"),
+            event.get("v"),
+            event.get("score")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// one row of tree heights per line, the same number of trees in every row and column
+fn parse_rows(s: &str) -> Result<Vec<Vec<i16>>, String> {
+    let trees = grid::Grid::parse(s, "a tree height", |c| c.to_digit(10).map(|h| h as i16))?;
+    if trees.width() != trees.height() {
+        return Err(format!(
+            "the grid is {} trees wide and {} rows high, it should be square",
+            trees.width(),
+            trees.height()
+        ));
+    }
+    Ok(trees.rows().map(|row| row.to_vec()).collect())
+}
+
+/// Checks the grid the way [`Forest::parse`] reads it, but finds every
+/// problem rather than the first: each character that isn't a height (with
+/// its column), and each row that isn't as wide as the first.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut width: Option<usize> = None;
+    let mut rows = 0;
+    for (i, line) in s.lines().enumerate() {
+        rows += 1;
+        for (column, c) in line.chars().enumerate() {
+            if !c.is_ascii_digit() {
+                lint.at(
+                    i,
+                    format!("{:?} in column {} is not a tree height", c, column + 1),
+                );
+            }
+        }
+        let trees = line.chars().count();
+        match width {
+            _ if trees == 0 => lint.at(i, "there are no trees"),
+            None => width = Some(trees),
+            Some(width) if trees != width => lint.at(
+                i,
+                format!("has {} trees, the first row has {}", trees, width),
+            ),
+            Some(_) => (),
+        }
+    }
+    match width {
+        None => lint.whole("there are no trees"),
+        Some(width) if width != rows => lint.whole(format!(
+            "the grid is {} trees wide and {} rows high, it should be square",
+            width, rows
+        )),
+        Some(_) => (),
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::property;
+    use shared::rng::Rng;
+
+    fn random_grid(rng: &mut Rng) -> Vec<Vec<i16>> {
+        let size = rng.range(1, 12) as usize;
+        // a small range of heights so there are plenty of equal trees
+        let max_height = rng.range(0, 9);
+        (0..size)
+            .map(|_| (0..size).map(|_| rng.range(0, max_height) as i16).collect())
+            .collect()
+    }
+
+    // the viewing distances worked out one tree at a time from the tree data sets
+    fn get_viewing_distances_per_cell(tree_grid_h: &Vec<Vec<i16>>) -> Vec<Vec<[i32; 4]>> {
+        let size = tree_grid_h.len();
+        let mut distances = vec![vec![[0; 4]; size]; size];
+        for h in 0..size {
+            let column_data = |v: usize| (0..size).map(|h| tree_grid_h[h][v]).collect::<Vec<i16>>();
+            for v in 0..size {
+                let tree_height = tree_grid_h[h][v];
+                let (north_tree_data, south_tree_data) = get_tree_data_sets(&column_data(v), h);
+                let (west_tree_data, east_tree_data) = get_tree_data_sets(&tree_grid_h[h], v);
+                distances[h][v] = [
+                    get_scenic_score(&north_tree_data, &tree_height),
+                    get_scenic_score(&south_tree_data, &tree_height),
+                    get_scenic_score(&east_tree_data, &tree_height),
+                    get_scenic_score(&west_tree_data, &tree_height),
+                ];
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn test_viewing_distances_agree_with_per_cell() {
+        property::check("viewing distances", 500, random_grid, |grid| {
+            property::agree(
+                get_viewing_distances(grid),
+                get_viewing_distances_per_cell(grid),
+            )
+        });
+    }
+
+    #[test]
+    fn test_example() {
+        let forest = Forest::parse("30373\n25512\n65332\n33549\n35390\n").unwrap();
+        let distances = forest.viewing_distances();
+        assert_eq!(distances[1][2], [1, 2, 2, 1]);
+        assert_eq!(distances[3][2], [2, 1, 2, 2]);
+        assert_eq!(forest.visible_count(), 21);
+        assert_eq!(forest.max_scenic_score(), (8, (3, 2)));
+    }
+
+    #[test]
+    fn test_lint_finds_every_problem() {
+        assert!(lint("30373\n25512\n65332\n33549\n35390\n").is_empty());
+        let problems: Vec<String> = lint("303x3\n2551\n65332\n3354a\n35390\n")
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "line 1: 'x' in column 4 is not a tree height",
+                "line 2: has 4 trees, the first row has 5",
+                "line 4: 'a' in column 5 is not a tree height",
+            ]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs;
+
+use y2022_day08::shared;
+use y2022_day08::Forest;
+use y2022_day08::{DAY, YEAR};
+
+use shared::memory::{self, CountingAllocator};
+use shared::trace::Trace;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(2)));
+    // let filename = "./test-grid.txt";
+    // let filename = "./test-grid-part2.txt";
+    let filename = shared::cli::filename().unwrap_or("./input.txt".to_string());
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day08", &filename, |s| Forest::parse(s).map(|_| ()));
+        return;
+    }
+
+    let phase = memory::Phase::start("parse");
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let forest = match Forest::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(forest) => forest,
+    };
+    phase.finish();
+
+    let phase = memory::Phase::start("visibility");
+    println!("Total trees visible: {}", forest.visible_count());
+    phase.finish();
+
+    println!("\n--- Part 2 ---\n");
+    let phase = memory::Phase::start("scenic scores");
+
+    let v_size = forest.size();
+    let h_size = forest.size();
+
+    let distances = forest.viewing_distances();
+
+    let mut north_score: i32 = 0;
+    let mut south_score: i32 = 0;
+    let mut east_score: i32 = 0;
+    let mut west_score: i32 = 0;
+
+    let mut max_scenic_score: u64 = 0;
+    let mut max_point: (usize, usize) = (0, 0);
+
+    for h in 0..h_size {
+        for v in 0..v_size {
+            north_score = distances[h][v][0];
+            south_score = distances[h][v][1];
+            east_score = distances[h][v][2];
+            west_score = distances[h][v][3];
+
+            let point_scenic_score: u64 = [north_score, south_score, east_score, west_score]
+                .iter()
+                .map(|d| *d as u64)
+                .product();
+
+            if point_scenic_score > max_scenic_score {
+                println!(
+                    "New max scenic score: {} ({},{}) N {} * E {} * S {} * W {}",
+                    point_scenic_score, h, v, north_score, east_score, south_score, west_score
+                );
+                max_scenic_score = point_scenic_score;
+                max_point = (h, v);
+            }
+        }
+    }
+
+    phase.finish();
+
+    println!("Max scenic score: {}", max_scenic_score);
+    println!("Max point: ({}, {})", max_point.0, max_point.1);
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        forest.max_scenic_score_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day08::narrate));
+    }
+}
@@ -0,0 +1,566 @@
+//! Day 9: Rope Bridge.
+//!
+//! The input is the moves of the head of a rope. Each knot follows the one in
+//! front of it, and the answer is the number of places the tail visits.
+//! [`Rope::parse`] reads the moves into a rope, which is a [`Simulation`]
+//! that moves the head one place per step.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+// use std::thread;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::invariant;
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+use shared::viewport::{Grid, Viewport};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 9;
+/// The furthest a single move can go. The puzzle's moves are all under 20,
+/// and every step of every knot is kept, so a much longer move would only
+/// run out of memory.
+pub const MAX_DISTANCE: u32 = 1_000_000;
+
+/// A place on the grid. y goes up.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x: x, y: y }
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// The knots of a rope, from the head to the tail, and every place each knot
+/// has been.
+pub struct Rope {
+    knots: Vec<Point>, // model as Vec of connected Points
+    history: Vec<Vec<Point>>,
+    pending_moves: VecDeque<(String, u32)>, // the head's moves still to make, and how far each has left
+    steps: u64,
+}
+
+impl Rope {
+    /// A rope of `size` knots, all at 0,0, with no moves to make.
+    pub fn new(size: usize) -> Rope {
+        Rope {
+            knots: vec![Point::new(0, 0); size],
+            history: vec![vec![Point::new(0, 0)]; size],
+            pending_moves: VecDeque::new(),
+            steps: 0,
+        }
+    }
+
+    /// Reads one move per line, a direction (U, D, L or R) and a distance,
+    /// into a rope of `size` knots. Fails on a line that isn't a move, or a
+    /// distance over [`MAX_DISTANCE`].
+    pub fn parse(s: &str, size: usize) -> Result<Rope, String> {
+        let mut rope = Rope::new(size);
+        for (direction, distance) in parse_moves(s)? {
+            rope.queue_knot_moves(&direction, &distance);
+        }
+        Ok(rope)
+    }
+
+    /// Queues up the moves to be made one step at a time by the simulation.
+    pub fn queue_knot_moves(&mut self, direction: &String, distance: &u32) {
+        if *distance > 0 {
+            self.pending_moves.push_back((direction.clone(), *distance));
+        }
+    }
+
+    /// Moves the head one place and the rest of the knots after it. The
+    /// direction is U, D, L or R.
+    pub fn move_head(&mut self, direction: &String) {
+        // only move first Point in Rope
+        // then propagate if it needs to move
+
+        let head_position = &self.knots[0];
+        let x: i32 = head_position.x;
+        let y: i32 = head_position.y;
+
+        let mov = match direction.as_str() {
+            "U" => (0, 1),
+            "D" => (0, -1),
+            "L" => (-1, 0),
+            "R" => (1, 0),
+            _ => panic!("Invalid direction"),
+        };
+
+        let new_position = Point::new(x + mov.0, y + mov.1);
+        self.knots[0] = new_position;
+        self.history[0].push(self.knots[0]);
+
+        // let (min, max) = get_grid_min_max();
+
+        // print_grid(&self, min, max, &self.get_unique_visited(1), false, 50);
+
+        if self.knots.len() > 1 {
+            for i in 1..self.knots.len() {
+                let a_knot = &self.knots[i - 1];
+                let b_knot = &self.knots[i];
+
+                // only move if this is true
+                if (a_knot.x - b_knot.x).abs() > 1 || (a_knot.y - b_knot.y).abs() > 1 {
+                    let mut p: Point = Point::new(0, 0);
+
+                    // there are 8 places the knot can go to
+                    p.x = a_knot.x - b_knot.x;
+                    p.y = a_knot.y - b_knot.y;
+
+                    // make sure x and y are only 1 or -1
+                    if p.x.abs() > 0 {
+                        p.x /= p.x.abs();
+                    }
+                    if p.y != 0 {
+                        p.y /= p.y.abs();
+                    }
+
+                    let new_point = Point::new(b_knot.x + p.x, b_knot.y + p.y);
+                    self.knots[i] = new_point;
+                    self.history[i].push(new_point);
+                }
+            }
+
+            // print_grid(&self, min, max, &self.get_unique_visited(1), false, 50);
+        }
+        for (i, pair) in self.knots.windows(2).enumerate() {
+            invariant::check(
+                "day09 the knots stay next to each other",
+                || (pair[0].x - pair[1].x).abs() <= 1 && (pair[0].y - pair[1].y).abs() <= 1,
+                || {
+                    format!(
+                        "after moving {}, knot {} is at {} and knot {} at {}",
+                        direction,
+                        i,
+                        pair[0],
+                        i + 1,
+                        pair[1]
+                    )
+                },
+            );
+        }
+    }
+
+    /// Makes the rest of the moves.
+    pub fn run(&mut self) {
+        self.run_traced(&mut Trace::default())
+    }
+
+    /// [`Rope::run`], recording each time the tail reaches a place it hasn't
+    /// been before, and the count at the end, in `trace` (see [`narrate`]).
+    pub fn run_traced(&mut self, trace: &mut Trace) {
+        let tail = self.knots.len() - 1;
+        let mut visited: HashSet<(i32, i32)> =
+            self.history[tail].iter().map(|p| (p.x, p.y)).collect();
+        while !self.is_done() {
+            self.step();
+            let p = self.knots[tail];
+            if trace.is_enabled() && visited.insert((p.x, p.y)) {
+                trace.event("tail", &[("step", &self.steps), ("at", &p)]);
+            }
+        }
+        trace.event("visited", &[("count", &self.tail_visited_count())]);
+    }
+
+    /// Where each knot is, from the head to the tail.
+    pub fn knots(&self) -> &[Point] {
+        &self.knots
+    }
+
+    /// Every place knot `knot_index` has been, in order, starting at 0,0.
+    pub fn history(&self, knot_index: usize) -> &[Point] {
+        &self.history[knot_index]
+    }
+
+    /// The number of different places knot `knot_index` has been.
+    pub fn visited_count(&self, knot_index: usize) -> usize {
+        let mut visited: HashSet<String> = HashSet::new();
+        for point in &self.history[knot_index] {
+            visited.insert(point.to_string());
+        }
+        visited.len()
+    }
+
+    /// The number of different places the tail has been.
+    pub fn tail_visited_count(&self) -> usize {
+        self.visited_count(self.knots.len() - 1)
+    }
+
+    // fn get_unique_visited(&self, knot_index: usize) -> HashSet<String> {
+    //     let mut visited = HashSet::new();
+    //     for knot in &self.history[knot_index] {
+    //         visited.insert(format!("{}", knot));
+    //     }
+    //     visited
+    // }
+}
+
+// each step moves the head one place, and the rest of the knots follow
+impl Simulation for Rope {
+    type State = Vec<Point>; // where each knot is
+
+    fn step(&mut self) {
+        if let Some((direction, remaining)) = self.pending_moves.pop_front() {
+            self.move_head(&direction);
+            self.steps += 1;
+            if remaining > 1 {
+                self.pending_moves.push_front((direction, remaining - 1));
+            }
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pending_moves.is_empty()
+    }
+
+    fn state(&self) -> Vec<Point> {
+        self.knots.clone()
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.steps
+    }
+
+    fn total_steps(&self) -> Option<u64> {
+        let remaining: u64 = self.pending_moves.iter().map(|(_, n)| *n as u64).sum();
+        Some(self.steps + remaining)
+    }
+}
+
+/// One line of the explanation for an event recorded by [`Rope::run_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "tail" => format!(
+            "After {} steps the tail moves to {}, where it hasn't been before.",
+            event.get("step"),
+            event.get("at")
+        ),
+        "visited" => format!("The tail has visited {} places.", event.get("count")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// a recording starts with where each knot is, and each step is where each knot
+// is after it, as "x,y" from the head to the tail
+impl Record for Rope {
+    const KIND: &'static str = "rope";
+
+    fn record_start(&self) -> String {
+        points_to_string(&self.knots)
+    }
+
+    fn record_step(&self) -> String {
+        points_to_string(&self.knots)
+    }
+
+    fn replay_start(start: &str) -> Result<Rope, String> {
+        let knots = points_from_string(start)?;
+        if knots.is_empty() {
+            return Err("the rope has no knots".to_string());
+        }
+        let mut rope = Rope::new(knots.len());
+        rope.knots = knots.clone();
+        rope.history = knots.into_iter().map(|knot| vec![knot]).collect();
+        Ok(rope)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        let knots = points_from_string(step)?;
+        if knots.len() != self.knots.len() {
+            return Err(format!(
+                "{} knots, but the rope has {}",
+                knots.len(),
+                self.knots.len()
+            ));
+        }
+        // the history only grows when a knot moves, as it does when simulating
+        for (i, knot) in knots.into_iter().enumerate() {
+            if knot != self.knots[i] {
+                self.history[i].push(knot);
+                self.knots[i] = knot;
+            }
+        }
+        self.steps += 1;
+        Ok(())
+    }
+
+    // the knots (H for the head, then numbered) over the places the tail has
+    // been (#), with s for the start, y going up, or the window the viewport
+    // flags give
+    fn render(&self) -> String {
+        let tail = self.knots.len() - 1;
+        let visited: HashSet<(i32, i32)> = self.history[tail].iter().map(|p| (p.x, p.y)).collect();
+        if let Some(view) = Viewport::from_args() {
+            let rope = RopeView {
+                rope: self,
+                visited,
+            };
+            return format!(
+                "{}Tail visited count: {}",
+                view.render(&rope),
+                self.tail_visited_count()
+            );
+        }
+        let everywhere = self.history.iter().flatten();
+        let min_x = everywhere.clone().map(|p| p.x).min().unwrap_or(0);
+        let max_x = everywhere.clone().map(|p| p.x).max().unwrap_or(0);
+        let min_y = everywhere.clone().map(|p| p.y).min().unwrap_or(0);
+        let max_y = everywhere.map(|p| p.y).max().unwrap_or(0);
+        let mut s = String::new();
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                let p = Point::new(x, y);
+                let c = match self.knots.iter().position(|knot| *knot == p) {
+                    Some(0) => 'H',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+                    None if x == 0 && y == 0 => 's',
+                    None if visited.contains(&(x, y)) => '#',
+                    None => '.',
+                };
+                s.push(c);
+            }
+            s.push('\n');
+        }
+        s.push_str(&format!(
+            "Tail visited count: {}",
+            self.tail_visited_count()
+        ));
+        s
+    }
+}
+
+// the rope as the viewport sees it, following the head, with the places the
+// tail has been as a set so each cell is quick to look up
+struct RopeView<'a> {
+    rope: &'a Rope,
+    visited: HashSet<(i32, i32)>,
+}
+
+impl Grid for RopeView<'_> {
+    const PRIORITY: &'static str = "H1234567890s#.";
+
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        let everywhere = self.rope.history.iter().flatten();
+        let min_x = everywhere.clone().map(|p| p.x).min().unwrap_or(0);
+        let max_x = everywhere.clone().map(|p| p.x).max().unwrap_or(0);
+        let min_y = everywhere.clone().map(|p| p.y).min().unwrap_or(0);
+        let max_y = everywhere.map(|p| p.y).max().unwrap_or(0);
+        ((min_x as i64, min_y as i64), (max_x as i64, max_y as i64))
+    }
+
+    fn glyph(&self, x: i64, y: i64) -> char {
+        let p = Point::new(x as i32, y as i32);
+        match self.rope.knots.iter().position(|knot| *knot == p) {
+            Some(0) => 'H',
+            Some(i) => char::from_digit(i as u32 % 10, 10).unwrap(),
+            None if x == 0 && y == 0 => 's',
+            None if self.visited.contains(&(p.x, p.y)) => '#',
+            None => '.',
+        }
+    }
+
+    fn focus(&self) -> Option<(i64, i64)> {
+        let head = self.rope.knots[0];
+        Some((head.x as i64, head.y as i64))
+    }
+
+    // the knots, which are easy to miss zoomed out
+    fn marks(&self) -> Vec<((i64, i64), char)> {
+        self.rope
+            .knots
+            .iter()
+            .map(|knot| {
+                let (x, y) = (knot.x as i64, knot.y as i64);
+                ((x, y), self.glyph(x, y))
+            })
+            .collect()
+    }
+
+    fn y_up(&self) -> bool {
+        true
+    }
+}
+
+fn points_to_string(points: &[Point]) -> String {
+    points
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn points_from_string(s: &str) -> Result<Vec<Point>, String> {
+    let mut points: Vec<Point> = vec![];
+    for p in s.split_whitespace() {
+        let xy = p
+            .split_once(',')
+            .and_then(|(x, y)| Some(Point::new(x.parse().ok()?, y.parse().ok()?)));
+        match xy {
+            Some(point) => points.push(point),
+            None => return Err(format!("{:?} is not a point", p)),
+        }
+    }
+    Ok(points)
+}
+
+// fn print_grid(
+//     rope: &Rope,
+//     min_point: Point,
+//     max_point: Point,
+//     visited: &HashSet<String>,
+//     final_grid: bool,
+//     millis: u64,
+// ) -> () {
+//     print!("\x1B[2J\x1B[1;1H");
+//     let max_i = max_point.x;
+//     let max_j = max_point.y;
+//     let min_i = min_point.x;
+//     let min_j = min_point.y;
+//     let tail_index = rope.knots.len() - 1;
+//     let hx = rope.knots[0].x.clone();
+//     let hy = rope.knots[0].y.clone();
+//     let tx = rope.knots[tail_index].x.clone();
+//     let ty = rope.knots[tail_index].y.clone();
+
+//     for j in min_j..max_j {
+//         for i in min_i..max_i {
+//             let x = i; // this is the x axis value
+//             let y = max_j - j - 1 - min_j.abs(); // this is the y axis value
+//             let mut knot_displayed = false;
+//             let mut knot_num: String = "".to_string();
+//             for n in 0..rope.knots.len() {
+//                 let knot = &rope.knots[n];
+//                 if knot.x == x && knot.y == y {
+//                     knot_num = format!("{}", n);
+//                     knot_displayed = true;
+//                 }
+//             }
+//             if knot_displayed {
+//                 print!("{}", knot_num);
+
+//             // } else if tail_visited.contains(&format!("{},{}", x, y)) {
+//             //     print!("o");
+//             } else {
+//                 print!(".");
+//             }
+//         }
+//         println!("");
+//     }
+//     println!("");
+//     thread::sleep(std::time::Duration::from_millis(millis));
+// }
+
+// fn get_grid_min_max() -> (Point, Point) {
+//     (Point::new(-20, -10), Point::new(20, 20))
+// }
+
+// one move per line, a direction (U, D, L or R) and a distance
+fn parse_moves(s: &str) -> Result<Vec<(String, u32)>, String> {
+    let mut moves: Vec<(String, u32)> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let data: Vec<&str> = line.split_whitespace().collect();
+        if data.len() != 2 {
+            return Err(format!("line {}: {:?} is not a move", i + 1, line));
+        }
+        if !["U", "D", "L", "R"].contains(&data[0]) {
+            return Err(format!("line {}: {:?} is not a direction", i + 1, data[0]));
+        }
+        let count = match data[1].parse::<u32>() {
+            Ok(count) => count,
+            Err(_) => return Err(format!("line {}: {:?} is not a distance", i + 1, data[1])),
+        };
+        if count > MAX_DISTANCE {
+            return Err(format!(
+                "line {}: {} is further than a move can go ({})",
+                i + 1,
+                count,
+                MAX_DISTANCE
+            ));
+        }
+        moves.push((data[0].to_string(), count));
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::fuzz::{fuzz_one, FuzzOutcome};
+
+    const EXAMPLE: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+
+    #[test]
+    fn test_example() {
+        let mut rope = Rope::parse(EXAMPLE, 2).unwrap();
+        rope.run();
+        assert_eq!(rope.tail_visited_count(), 13);
+        let mut rope = Rope::parse(EXAMPLE, 10).unwrap();
+        rope.run();
+        assert_eq!(rope.tail_visited_count(), 1);
+    }
+
+    #[test]
+    fn test_explain_where_the_tail_goes() {
+        let mut rope = Rope::parse(EXAMPLE, 2).unwrap();
+        let mut trace = Trace::new(true);
+        rope.run_traced(&mut trace);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "After 2 steps the tail moves to 1,0, where it hasn't been before.\n\
+             After 3 steps the tail moves to 2,0, where it hasn't been before.\n\
+             After 4 steps the tail moves to 3,0, where it hasn't been before.\n\
+             After 6 steps the tail moves to 4,1, where it hasn't been before.\n"
+        ));
+        // one of the 13 places is where the tail starts
+        assert_eq!(explained.lines().count(), 12 + 1);
+        assert!(explained.ends_with("The tail has visited 13 places.\n"));
+    }
+
+    #[test]
+    fn test_long_moves_are_rejected() {
+        // these used to run out of memory rather than give an error
+        let parse = |s: &str| Rope::parse(s, 10).map(|_| ());
+        for input in ["R 2147483648\n", "U 4000000000\n"] {
+            match fuzz_one(input.as_bytes(), &parse) {
+                FuzzOutcome::Rejected(why) => assert!(why.contains("further"), "{}", why),
+                outcome => panic!("{:?} gave {:?}", input, outcome),
+            }
+        }
+        assert_eq!(fuzz_one(b"R 1000000\n", &parse), FuzzOutcome::Parsed);
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut rope = Rope::parse(EXAMPLE, 2).unwrap();
+        let mut recording = format!("# aoc recording rope\n{}\n---\n", rope.record_start());
+        while !rope.is_done() {
+            rope.step();
+            recording.push_str(&rope.record_step());
+            recording.push('\n');
+        }
+
+        let replayed: Rope = shared::replay::replay_to(&recording, usize::MAX).unwrap();
+        assert_eq!(replayed.knots(), rope.knots());
+        assert_eq!(replayed.tail_visited_count(), 13);
+        let halfway: Rope = shared::replay::replay_to(&recording, 4).unwrap();
+        assert_eq!(halfway.knots()[0], Point::new(4, 0));
+
+        assert!(shared::replay::replay_to::<Rope>("# aoc recording cave\n---\n", 1).is_err());
+    }
+}
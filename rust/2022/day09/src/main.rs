@@ -0,0 +1,68 @@
+use std::fs;
+
+use y2022_day09::shared;
+use y2022_day09::Rope;
+use y2022_day09::{DAY, YEAR};
+
+use shared::memory::{self, CountingAllocator};
+use shared::replay::Recorder;
+use shared::simulation::{self, Runner};
+use shared::trace::Trace;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+
+    let mut filename = "./test-data.txt".to_string();
+    match shared::cli::filename() {
+        Some(arg) => {
+            filename = arg;
+        }
+        None => {
+            println!("No filename given, using test data\n");
+        }
+    }
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day09", &filename, |s| Rope::parse(s, 10).map(|_| ()));
+        return;
+    }
+
+    let phase = memory::Phase::start("parse");
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let mut rope: Rope = match Rope::parse(&data, 10) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(rope) => rope,
+    };
+    phase.finish();
+
+    let phase = memory::Phase::start("simulate");
+    simulation::run_from_args(&mut rope, Runner::new().observe(Recorder::from_args()));
+    phase.finish();
+
+    // let (min, max) = get_grid_min_max();
+
+    // print_grid(&rope, min, max, &rope.get_unique_visited(1), true, 50);
+
+    // println!("Tail history: {:?}", rope.history(9));
+    println!("Tail visited count: {}", rope.tail_visited_count());
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        // the simulation above has already run, so make the moves again
+        if let Ok(mut rope) = Rope::parse(&data, 10) {
+            rope.run_traced(&mut trace);
+            println!("\n{}", trace.render(y2022_day09::narrate));
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! Day 10: Cathode-Ray Tube.
+//!
+//! The input is a program of `noop` and `addx` instructions for the CPU in the
+//! elves' device. [`CPU::parse`] loads the program into a CPU, which is a
+//! [`Simulation`] that runs one instruction per step. Part 1 is the sum of
+//! the signal strengths during the 20th, 60th, 100th, 140th, 180th and 220th
+//! cycles, and part 2 is the letters the CRT draws. With tracing on, the CPU
+//! records each cycle and instruction as it runs (see [`narrate`]).
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 10;
+
+/// The CPU and the CRT it draws on.
+pub struct CPU {
+    x: i32,
+    cycle: i32,
+    signal_strength_data: Vec<(i32, i32, i32)>,
+    crt: Vec<Vec<char>>, // either '.' or '#'
+    program: Vec<(String, i32)>,
+    pc: usize,    // index of the next instruction in the program
+    trace: Trace, // each cycle and instruction, when tracing is on
+}
+
+impl CPU {
+    fn new() -> CPU {
+        CPU {
+            x: 1, // middle pixel of 3
+            cycle: 0,
+            signal_strength_data: vec![],
+            crt: vec![vec!['.'; 40]; 6], // 6 rows of 40 pixels filled with '.'
+            program: vec![],
+            pc: 0,
+            trace: Trace::default(),
+        }
+    }
+
+    /// Loads a program of one instruction per line, either `noop` or `addx`
+    /// with a number. Fails on anything else.
+    pub fn parse(s: &str) -> Result<CPU, String> {
+        let mut cpu = CPU::new();
+        cpu.load_program(&parse_program(s)?);
+        Ok(cpu)
+    }
+
+    /// Whether to record an event for each cycle and instruction as it runs
+    /// (see [`CPU::trace`]). Off by default.
+    pub fn set_trace(&mut self, trace: bool) {
+        self.trace = Trace::new(trace);
+    }
+
+    /// The events traced so far, oldest first, if tracing is on.
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// Runs the rest of the program.
+    pub fn run(&mut self) {
+        while !self.is_done() {
+            self.step();
+        }
+    }
+
+    /// The value of the X register.
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// The cycle the CPU is on, starting at 1 once the program runs.
+    pub fn cycle(&self) -> i32 {
+        self.cycle
+    }
+
+    /// (cycle, X, signal strength) during the 20th cycle and every 40th
+    /// cycle after it.
+    pub fn signal_strengths(&self) -> &[(i32, i32, i32)] {
+        &self.signal_strength_data
+    }
+
+    /// The sum of the signal strengths so far. Part 1.
+    pub fn signal_strength_sum(&self) -> i64 {
+        let strengths = self.signal_strength_data.iter().map(|x| x.2 as i64);
+        checked::sum("day10 sum of signal strengths", strengths)
+    }
+
+    /// The rows of the CRT, with `#` for a lit pixel and `.` for a dark one.
+    /// Part 2.
+    pub fn crt_lines(&self) -> Vec<String> {
+        self.crt.iter().map(|row| row.iter().collect()).collect()
+    }
+
+    /// The CRT as the binary shows it, a `[drawCRT]` line for each row.
+    pub fn draw_crt(&self) -> String {
+        self.crt_lines()
+            .iter()
+            .map(|row| format!("\t\t[drawCRT] {:?}\n", row))
+            .collect()
+    }
+
+    fn write_crt(&mut self) {
+        let pos = (self.cycle - 1) % 40;
+        let row = ((self.cycle - pos) + 1) / 40;
+        // the CRT is only 6 rows, so the rest of a longer program draws nothing
+        if row as usize >= self.crt.len() {
+            return;
+        }
+        // println!(
+        //     "\t\t\t\t\t[drawCRT] crt_position={}, row={}, x={}",
+        //     pos, row, self.x
+        // );
+        // we know the row, and we know the position of X and current_crt_position
+        if (self.x - pos as i32).abs() < 2 {
+            // then we add a '#' to current row
+            self.crt[row as usize][pos as usize] = '#';
+        } else {
+            // then we add a '.' to current row
+            self.crt[row as usize][pos as usize] = '.';
+        }
+        // println!(
+        //     "\t\t\t\t\t[drawCRT] {:?}",
+        //     self.crt[row as usize]
+        //         .iter()
+        //         .map(|x| x.to_string())
+        //         .collect::<String>()
+        // );
+    }
+
+    fn store_signal_strength(&mut self) {
+        self.signal_strength_data
+            .push((self.cycle, self.x, self.get_signal_strength()));
+    }
+
+    fn get_signal_strength(&self) -> i32 {
+        checked::mul("day10 signal strength", self.x, self.cycle)
+    }
+
+    fn end_cycle(&mut self) {
+        self.trace
+            .event("end", &[("cycle", &self.cycle), ("x", &self.x)]);
+    }
+
+    fn start_cycle(&mut self) {
+        self.trace
+            .event("start", &[("cycle", &self.cycle), ("x", &self.x)]);
+    }
+
+    fn increment_cycle(&mut self) {
+        if self.cycle > 0 {
+            self.end_cycle();
+        }
+        self.cycle += 1;
+        self.start_cycle();
+    }
+
+    /// Replaces the program. The next step runs its first instruction.
+    pub fn load_program(&mut self, program: &Vec<(String, i32)>) {
+        self.trace.event("load", &[("length", &program.len())]);
+        self.program = program.clone();
+        self.pc = 0;
+    }
+
+    fn execute(&mut self, instruction: &(String, i32)) {
+        match instruction.0.as_str() {
+            "addx" => {
+                self.increment_cycle();
+                self.trace.event("addx", &[("num", &instruction.1)]);
+                self.write_crt();
+                if (self.cycle - 20) % 40 == 0 {
+                    // store the signal strength
+                    self.store_signal_strength();
+                }
+                self.increment_cycle();
+                self.write_crt();
+                if (self.cycle - 20) % 40 == 0 {
+                    // store the signal strength
+                    self.store_signal_strength();
+                }
+                self.x = checked::add("day10 X register", self.x, instruction.1);
+                self.trace
+                    .event("added", &[("num", &instruction.1), ("x", &self.x)]);
+            }
+            "noop" => {
+                self.increment_cycle();
+                self.trace.event("noop", &[]);
+                self.write_crt();
+                if (self.cycle - 20) % 40 == 0 {
+                    // store the signal strength
+                    self.store_signal_strength();
+                }
+            }
+            _ => self
+                .trace
+                .event("unknown", &[("instruction", &instruction.0)]),
+        }
+    }
+}
+
+// each step runs one instruction of the program
+impl Simulation for CPU {
+    type State = (i32, i32); // (cycle, X)
+
+    fn step(&mut self) {
+        let instruction = self.program[self.pc].clone();
+        self.execute(&instruction);
+        self.pc += 1;
+        if self.is_done() {
+            self.end_cycle();
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pc >= self.program.len()
+    }
+
+    fn state(&self) -> (i32, i32) {
+        (self.cycle, self.x)
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.pc as u64
+    }
+
+    fn total_steps(&self) -> Option<u64> {
+        Some(self.program.len() as u64)
+    }
+}
+
+// a recording starts with the cycle and X before the program runs, and each
+// step is the cycle and X after an instruction, "cycle x". X only changes at
+// the end of an instruction, so the cycles in between are drawn with the X
+// from before it
+impl Record for CPU {
+    const KIND: &'static str = "cpu";
+
+    fn record_start(&self) -> String {
+        format!("{} {}", self.cycle, self.x)
+    }
+
+    fn record_step(&self) -> String {
+        format!("{} {}", self.cycle, self.x)
+    }
+
+    fn replay_start(start: &str) -> Result<CPU, String> {
+        let mut cpu = CPU::new();
+        (cpu.cycle, cpu.x) = parse_cycle_and_x(start)?;
+        Ok(cpu)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        let (cycle, x) = parse_cycle_and_x(step)?;
+        if cycle < self.cycle {
+            return Err(format!("cycle {} is before cycle {}", cycle, self.cycle));
+        }
+        while self.cycle < cycle {
+            self.cycle += 1;
+            self.write_crt();
+            if (self.cycle - 20) % 40 == 0 {
+                self.store_signal_strength();
+            }
+        }
+        self.x = x;
+        self.pc += 1;
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}\nCycle {}, X={}, signal strength sum {}",
+            self.crt_lines().join("\n"),
+            self.cycle,
+            self.x,
+            self.signal_strength_sum()
+        )
+    }
+}
+
+/// One line of the trace for an event recorded by a CPU with tracing on, in
+/// the words the binary has always printed them in.
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "load" => "Running program".to_string(),
+        "start" => format!("[{:0>4}] START (X={})", event.get("cycle"), event.get("x")),
+        "end" => format!("[{:0>4}] END (X={})", event.get("cycle"), event.get("x")),
+        "addx" => format!("\t[addx] Start executing addx {}", event.get("num")),
+        "added" => format!(
+            "\t[addx] End executing addx {}\n\t[addx] Set X to X + ({})\n\t[addx] X set to {}",
+            event.get("num"),
+            event.get("num"),
+            event.get("x")
+        ),
+        "noop" => "\t[noop]".to_string(),
+        "unknown" => format!("Unknown instruction: {}", event.get("instruction")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+fn parse_cycle_and_x(s: &str) -> Result<(i32, i32), String> {
+    let nums: Vec<Result<i32, _>> = s.split_whitespace().map(|n| n.parse()).collect();
+    match nums[..] {
+        [Ok(cycle), Ok(x)] => Ok((cycle, x)),
+        _ => Err(format!("{:?} is not a cycle and X", s)),
+    }
+}
+
+// one instruction per line, either noop or addx with a number
+fn parse_program(s: &str) -> Result<Vec<(String, i32)>, String> {
+    let mut instructions: Vec<(String, i32)> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let instruction_data: Vec<&str> = line.split_whitespace().collect();
+        match instruction_data[..] {
+            ["noop"] => instructions.push(("noop".to_string(), 0)),
+            ["addx", num] => match num.parse::<i32>() {
+                Ok(num) => instructions.push(("addx".to_string(), num)),
+                Err(_) => return Err(format!("line {}: {:?} is not a number", i + 1, num)),
+            },
+            _ => return Err(format!("line {}: {:?} is not an instruction", i + 1, line)),
+        }
+    }
+    Ok(instructions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\n\
+                           addx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\n\
+                           addx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\n\
+                           addx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\n\
+                           addx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\n\
+                           addx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\n\
+                           addx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\n\
+                           addx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\n\
+                           addx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\n\
+                           addx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\n\
+                           noop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\n\
+                           addx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\n\
+                           noop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\n\
+                           addx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\n\
+                           addx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\n\
+                           noop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\n\
+                           addx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\n\
+                           addx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\n\
+                           addx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop\n";
+
+    const CRT: &str = "##..##..##..##..##..##..##..##..##..##..\n\
+                       ###...###...###...###...###...###...###.\n\
+                       ####....####....####....####....####....\n\
+                       #####.....#####.....#####.....#####.....\n\
+                       ######......######......######......####\n\
+                       #######.......#######.......#######.....";
+
+    #[test]
+    fn test_example() {
+        let mut cpu = CPU::parse(PROGRAM).unwrap();
+        cpu.run();
+        let cycles: Vec<i32> = cpu.signal_strengths().iter().map(|s| s.0).collect();
+        assert_eq!(cycles, [20, 60, 100, 140, 180, 220]);
+        assert_eq!(cpu.signal_strengths()[0], (20, 21, 420));
+        assert_eq!(cpu.signal_strength_sum(), 13140);
+        assert_eq!(cpu.crt_lines().join("\n"), CRT);
+    }
+
+    #[test]
+    fn test_trace_small_program() {
+        let mut cpu = CPU::parse("noop\naddx 3\naddx -5\n").unwrap();
+        cpu.set_trace(true);
+        cpu.run();
+        assert_eq!((cpu.cycle(), cpu.x()), (5, -1));
+        let lines = cpu.trace().render(narrate);
+        assert!(lines.starts_with("[0001] START (X=1)\n\t[noop]\n[0001] END (X=1)\n"));
+        assert!(lines.ends_with("\t[addx] X set to -1\n[0005] END (X=-1)\n"));
+
+        // nothing is kept unless tracing is on
+        let mut cpu = CPU::parse("noop\naddx 3\naddx -5\n").unwrap();
+        cpu.run();
+        assert!(cpu.trace().events().is_empty());
+    }
+}
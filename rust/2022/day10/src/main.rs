@@ -0,0 +1,83 @@
+use std::fs;
+
+use y2022_day10::shared;
+use y2022_day10::CPU;
+use y2022_day10::{DAY, YEAR};
+
+use shared::replay::Recorder;
+use shared::simulation::{self, Observer, Runner, StopReason};
+
+// prints the CPU's trace as it goes
+struct TracePrinter {
+    printed: usize,
+}
+
+impl TracePrinter {
+    fn print_new_lines(&mut self, cpu: &CPU) {
+        for event in &cpu.trace().events()[self.printed..] {
+            println!("{}", y2022_day10::narrate(event));
+        }
+        self.printed = cpu.trace().events().len();
+    }
+}
+
+impl Observer<CPU> for TracePrinter {
+    fn on_step(&mut self, cpu: &CPU) {
+        self.print_new_lines(cpu);
+    }
+
+    fn on_finish(&mut self, cpu: &CPU, _reason: &StopReason) {
+        self.print_new_lines(cpu);
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+
+    let mut filename = "./test-data.txt".to_string();
+    match shared::cli::filename() {
+        Some(arg) => {
+            filename = arg;
+        }
+        None => {
+            println!("No filename given, using test data\n");
+        }
+    }
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day10", &filename, |s| CPU::parse(s).map(|_| ()));
+        return;
+    }
+
+    let data = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(data) => data,
+    };
+    let mut cpu: CPU = match CPU::parse(&data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(cpu) => cpu,
+    };
+    cpu.set_trace(true);
+    println!("Running program");
+    let runner = Runner::new()
+        .observe(TracePrinter { printed: 0 })
+        .observe(Recorder::from_args());
+    simulation::run_from_args(&mut cpu, runner);
+
+    // println!("signal strength: {:?}", cpu.signal_strength_data);
+
+    // let mut sum: i32 = 0;
+
+    // for (cycle, x, signal_strength) in cpu.signal_strength_data {
+    //     sum += signal_strength;
+    // }
+
+    // println!("sum of total: {}", sum);
+
+    print!("{}", cpu.draw_crt());
+}
@@ -0,0 +1,818 @@
+//! Day 11: Monkey in the Middle.
+//!
+//! The input is the notes on each monkey: the items it holds, how it changes
+//! the worry level of an item, and who it throws the item to. [`Monkeys::parse`]
+//! reads the notes, and the monkeys are a [`Simulation`] that runs one round
+//! per step. The answer is the monkey business after 10000 rounds, the
+//! product of the two highest inspection counts.
+
+use std::collections::VecDeque;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::checkpoint::{self, Checkpoint};
+use shared::invariant;
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 11;
+
+// what the items' values are called when they overflow in --checked mode
+const WORRY: &str = "day11 worry level";
+
+/// All the monkeys and how many rounds they have played.
+pub struct Monkeys {
+    monkeys: Vec<Monkey>,
+    divisors: Vec<u16>,
+    round: u64,                        // number of rounds done so far
+    max_rounds: u64,                   // number of rounds to run the simulation for
+    throws: Vec<(usize, usize, u128)>, // (from, to, item) in the last round
+}
+
+impl Monkeys {
+    fn new() -> Monkeys {
+        Monkeys {
+            monkeys: vec![],
+            divisors: vec![],
+            round: 0,
+            max_rounds: 10000,
+            throws: vec![],
+        }
+    }
+
+    /// Reads the notes on the monkeys, set up to play 10000 rounds. Fails if
+    /// a monkey is missing part of its notes, the monkeys are out of order,
+    /// or one throws to a monkey that isn't there.
+    pub fn parse(s: &str) -> Result<Monkeys, String> {
+        let mut monkeys = Monkeys::new();
+        monkeys.parse_monkey_data(s)?;
+        Ok(monkeys)
+    }
+
+    /// Plays the rest of the rounds.
+    pub fn run(&mut self) {
+        while !self.is_done() {
+            self.step();
+        }
+    }
+
+    /// [`Monkeys::run`], recording every inspection in the next round, then
+    /// how many items each monkey inspected and the monkey business, in
+    /// `trace` (see [`narrate`]). Each round is the same story, so only the
+    /// first one is told.
+    pub fn run_traced(&mut self, trace: &mut Trace) {
+        if !self.is_done() {
+            trace.event("round", &[("round", &(self.round + 1))]);
+            self.do_round(trace);
+        }
+        self.run();
+        for m in &self.monkeys {
+            trace.event(
+                "inspected",
+                &[("monkey", &m.index), ("count", &m.inspection_count)],
+            );
+        }
+        trace.event(
+            "business",
+            &[
+                ("rounds", &self.round),
+                ("business", &self.monkey_business()),
+            ],
+        );
+    }
+
+    /// The monkeys, in order.
+    pub fn monkeys(&self) -> &[Monkey] {
+        &self.monkeys
+    }
+
+    /// The number of rounds played so far.
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Changes the number of rounds to play.
+    pub fn set_max_rounds(&mut self, max_rounds: u64) {
+        self.max_rounds = max_rounds;
+    }
+
+    /// The number of items each monkey has inspected.
+    pub fn inspection_counts(&self) -> Vec<u64> {
+        self.monkeys.iter().map(|m| m.inspection_count).collect()
+    }
+
+    /// The two highest inspection counts multiplied together.
+    pub fn monkey_business(&self) -> u128 {
+        let mut inspection_count = self.inspection_counts();
+        inspection_count.sort();
+        let busiest = inspection_count.iter().rev().take(2).map(|x| *x as u128);
+        checked::product("day11 monkey business", busiest)
+    }
+
+    fn parse_monkey_data(&mut self, data: &str) -> Result<(), String> {
+        let mut current_index: usize = 0;
+
+        let mut monkey: Monkey = Monkey {
+            index: current_index,
+            items: VecDeque::new(),
+            operation: ("".to_string(), "".to_string()),
+            test: ("".to_string(), 0, 0, 0),
+            inspection_count: 0,
+            max_modulo: 0,
+        };
+
+        let mut divisors: Vec<u16> = vec![];
+
+        // load all the monkeys...
+        for (i, l) in data.lines().enumerate() {
+            // println!("{}", l);
+            if l.trim().len() == 0 {
+                continue;
+            }
+            let words: Vec<&str> = l.split_whitespace().collect();
+            let bad_line = || format!("line {}: {:?} is not monkey data", i + 1, l);
+            match words[..] {
+                ["Monkey", monkey_index_str] => {
+                    let monkey_index: usize = match monkey_index_str
+                        .strip_suffix(":")
+                        .and_then(|s| s.parse().ok())
+                    {
+                        Some(monkey_index) => monkey_index,
+                        None => return Err(bad_line()),
+                    };
+                    // monkeys throw to each other by position, so they have to be in order
+                    if monkey_index != current_index {
+                        return Err(format!(
+                            "line {}: expected monkey {}, found monkey {}",
+                            i + 1,
+                            current_index,
+                            monkey_index
+                        ));
+                    }
+                    monkey.index = monkey_index;
+                    // println!("{:?}", monkey_index);
+                }
+                ["Starting", "items:", ..] => {
+                    for s in &words[2..] {
+                        match s.trim_end_matches(",").parse::<u128>() {
+                            Ok(item) => monkey.items.push_back(item),
+                            Err(_) => {
+                                return Err(format!("line {}: {:?} is not an item", i + 1, s))
+                            }
+                        }
+                    }
+                    // println!("{:?}", monkey.items);
+                }
+                ["Operation:", "new", "=", "old", op, num] => {
+                    if !["+", "-", "*", "/"].contains(&op) {
+                        return Err(format!("line {}: unknown operation {:?}", i + 1, op));
+                    }
+                    if !num.eq("old") && num.parse::<u128>().is_err() {
+                        return Err(format!("line {}: {:?} is not a number", i + 1, num));
+                    }
+                    if op.eq("/") && num.parse::<u128>() == Ok(0) {
+                        return Err(format!("line {}: can't divide by 0", i + 1));
+                    }
+                    // an item's worry level can be 0
+                    if op.eq("/") && num.eq("old") {
+                        return Err(format!("line {}: can't divide by old, it can be 0", i + 1));
+                    }
+                    monkey.operation = (op.to_string(), num.to_string());
+                    // println!("{:?}", monkey.operation);
+                }
+                ["Test:", "divisible", "by", num] => {
+                    let divisor = match num.parse::<u16>() {
+                        Ok(divisor) if divisor > 0 => divisor,
+                        _ => return Err(format!("line {}: {:?} is not a divisor", i + 1, num)),
+                    };
+                    monkey.test.0 = "divisible by".to_string();
+                    monkey.test.1 = divisor as u128;
+                    divisors.push(divisor);
+                    // println!("{:?}", monkey.test);
+                }
+                ["If", outcome, "throw", "to", "monkey", num] => {
+                    let target = match num.parse::<usize>() {
+                        Ok(target) => target,
+                        Err(_) => return Err(format!("line {}: {:?} is not a monkey", i + 1, num)),
+                    };
+                    match outcome {
+                        "true:" => {
+                            monkey.test.2 = target;
+                        }
+                        "false:" => {
+                            monkey.test.3 = target;
+                            if monkey.operation.0.is_empty() || monkey.test.1 == 0 {
+                                return Err(format!(
+                                    "line {}: monkey {} has no operation or test",
+                                    i + 1,
+                                    current_index
+                                ));
+                            }
+                            // this is the last line, so add it
+                            self.add_monkey(monkey);
+                            // make a new monkey for the next loop
+                            current_index += 1;
+                            monkey = Monkey {
+                                index: current_index,
+                                items: VecDeque::new(),
+                                operation: ("".to_string(), "".to_string()),
+                                test: ("".to_string(), 0, 0, 0),
+                                inspection_count: 0,
+                                max_modulo: 0,
+                            };
+                        }
+                        _ => return Err(bad_line()),
+                    }
+                    // println!("{:?}", monkey.test);
+                }
+                _ => return Err(bad_line()),
+            }
+        }
+
+        if !monkey.items.is_empty() || !monkey.operation.0.is_empty() || monkey.test.1 != 0 {
+            return Err(format!("monkey {} is not finished", current_index));
+        }
+        // monkey business needs the two busiest monkeys
+        if self.monkeys.len() < 2 {
+            return Err("there need to be at least two monkeys".to_string());
+        }
+        for m in self.monkeys.iter() {
+            if m.test.2 >= self.monkeys.len() || m.test.3 >= self.monkeys.len() {
+                return Err(format!("monkey {} throws to a missing monkey", m.index));
+            }
+        }
+
+        self.divisors = divisors;
+        let mut n: u128 = 1;
+        for d in self.divisors.clone() {
+            n = match n.checked_mul(d as u128) {
+                Some(n) => n,
+                None => return Err("the divisors are too large".to_string()),
+            };
+        }
+        // set the max number for an item
+        for m in self.monkeys.iter_mut() {
+            m.max_modulo = n;
+        }
+        Ok(())
+    }
+
+    fn add_monkey(&mut self, monkey: Monkey) {
+        self.monkeys.push(monkey);
+    }
+
+    fn do_round(&mut self, trace: &mut Trace) {
+        // go in turn through each monkey
+        // inspect each of their items in turn
+        let monkey_count = self.monkeys.len();
+        self.throws.clear();
+        let held = |monkeys: &Vec<Monkey>| monkeys.iter().map(|m| m.items.len()).sum::<usize>();
+        // only counted when the invariant below is checked
+        let before = if invariant::is_enabled() {
+            held(&self.monkeys)
+        } else {
+            0
+        };
+        for m in 0..monkey_count {
+            // loop over the number of items the monkey has
+            for _ in 0..self.monkeys[m].items.len() {
+                // always take the first one (index 0) as we are removing it
+                // later in the loop
+                let old = self.monkeys[m].items[0];
+                let (test_passed, monkey_to_send_to_index) =
+                    self.monkeys[m].inspect_item_and_test(0);
+                if trace.is_enabled() {
+                    let monkey = &self.monkeys[m];
+                    trace.event(
+                        "inspect",
+                        &[
+                            ("monkey", &m),
+                            ("old", &old),
+                            ("op", &monkey.operation.0),
+                            ("num", &monkey.operation.1),
+                            ("new", &monkey.items[0]),
+                            ("divisor", &monkey.test.1),
+                            ("divisible", &test_passed),
+                            ("to", &monkey_to_send_to_index),
+                        ],
+                    );
+                }
+                // take the first item (index 0), and give it to the new monkey
+                let item = self.monkeys[m].items.pop_front().unwrap();
+                self.monkeys[monkey_to_send_to_index].items.push_back(item);
+                self.throws.push((m, monkey_to_send_to_index, item));
+            }
+        }
+        self.consolidate_items();
+        self.round += 1;
+        invariant::check(
+            "day11 the monkeys keep every item",
+            || held(&self.monkeys) == before,
+            || {
+                let holding: Vec<String> = self
+                    .monkeys
+                    .iter()
+                    .map(|m| m.items.len().to_string())
+                    .collect();
+                format!(
+                    "round {}: {} items before, {} after ({})",
+                    self.round,
+                    before,
+                    held(&self.monkeys),
+                    holding.join(", ")
+                )
+            },
+        );
+    }
+
+    fn consolidate_items(&mut self) {
+        // consolidate here
+        // this won't reduce the actual number for "worry" purposes
+        // but will for ability to complete the task
+        // if the number is a multiple of any of the *square* of the
+        // divisors (e.g. 9 is 3 squared or 121 is 11 squared) then
+        // you can divide it by that divisor, because they are all
+        // prime numbers and you are not affecting the result
+        // of any future results
+        for monkey in self.monkeys.iter_mut() {
+            for n in 0..monkey.items.len() {
+                monkey.items[n] = monkey.consolidate_item(monkey.items[n]);
+            }
+        }
+    }
+}
+
+// each step is a full round of every monkey taking their turn
+impl Simulation for Monkeys {
+    type State = Vec<u64>; // inspection count of each monkey
+
+    fn step(&mut self) {
+        self.do_round(&mut Trace::default());
+    }
+
+    fn is_done(&self) -> bool {
+        self.round >= self.max_rounds
+    }
+
+    fn state(&self) -> Vec<u64> {
+        self.monkeys.iter().map(|m| m.inspection_count).collect()
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.round
+    }
+
+    fn total_steps(&self) -> Option<u64> {
+        Some(self.max_rounds)
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Monkeys::run_traced`]. The worry levels are the ones the monkeys keep,
+/// which are the remainders after dividing by the product of the divisors.
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "round" => format!("Round {}:", event.get("round")),
+        "inspect" => {
+            let num = match event.get("num") {
+                "old" => "itself",
+                num => num,
+            };
+            let change = match event.get("op") {
+                "+" => format!("increases by {}", num),
+                "-" => format!("decreases by {}", num),
+                "*" => format!("is multiplied by {}", num),
+                _ => format!("is divided by {}", num),
+            };
+            format!(
+                "Monkey {} inspects an item with a worry level of {}, which {} to {}. \
+                 {} {} divisible by {}, so the item is thrown to monkey {}.",
+                event.get("monkey"),
+                event.get("old"),
+                change,
+                event.get("new"),
+                event.get("new"),
+                if event.get("divisible") == "true" {
+                    "is"
+                } else {
+                    "isn't"
+                },
+                event.get("divisor"),
+                event.get("to")
+            )
+        }
+        "inspected" => format!(
+            "Monkey {} inspected items {} times.",
+            event.get("monkey"),
+            event.get("count")
+        ),
+        "business" => format!(
+            "After {} rounds the monkey business is {}.",
+            event.get("rounds"),
+            event.get("business")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// a recording starts with a checkpoint, and each step is a round's throws in
+// order, "from>to=item" with the worry level the item was thrown with
+impl Record for Monkeys {
+    const KIND: &'static str = "monkeys";
+
+    fn record_start(&self) -> String {
+        self.to_checkpoint()
+    }
+
+    fn record_step(&self) -> String {
+        self.throws
+            .iter()
+            .map(|(from, to, item)| format!("{}>{}={}", from, to, item))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn replay_start(start: &str) -> Result<Monkeys, String> {
+        Monkeys::from_checkpoint(start)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        for throw in step.split_whitespace() {
+            let parsed = throw.split_once('>').and_then(|(from, rest)| {
+                let (to, item) = rest.split_once('=')?;
+                Some((from.parse().ok()?, to.parse().ok()?, item.parse().ok()?))
+            });
+            let (from, to, item): (usize, usize, u128) = match parsed {
+                Some(throw) => throw,
+                None => return Err(format!("{:?} is not a throw", throw)),
+            };
+            if to >= self.monkeys.len() {
+                return Err(format!("there is no monkey {}", to));
+            }
+            match self.monkeys.get_mut(from).map(|m| m.items.pop_front()) {
+                Some(Some(_)) => self.monkeys[from].inspection_count += 1,
+                Some(None) => return Err(format!("monkey {} has no items to throw", from)),
+                None => return Err(format!("there is no monkey {}", from)),
+            }
+            self.monkeys[to].items.push_back(item);
+        }
+        self.consolidate_items();
+        self.round += 1;
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        let mut s = format!("Round {}\n", self.round);
+        for m in &self.monkeys {
+            let items: Vec<String> = m.items.iter().map(|item| item.to_string()).collect();
+            s.push_str(&format!(
+                "Monkey {}: [{:>3}] {}\n",
+                m.index,
+                m.inspection_count,
+                items.join(", ")
+            ));
+        }
+        s.push_str(&format!("Monkey Business: {}", self.monkey_business()));
+        s
+    }
+}
+
+/// A monkey and the items it is holding.
+#[derive(Debug)]
+pub struct Monkey {
+    pub index: usize,
+    pub items: VecDeque<u128>,
+    pub operation: (String, String), // new = (do operation.0) on old with (operation.1) e.g. ("+", 1)
+    pub test: (String, u128, usize, usize), // e.g. ("divisible by", num, monkey to send to if true, monkey to send to if false)
+    pub inspection_count: u64,
+    pub max_modulo: u128,
+}
+
+// a checkpoint is the round number, the divisors, and then one line per monkey:
+// monkey index;items;operation;test;divisor;if true;if false;inspections;max modulo
+impl Checkpoint for Monkeys {
+    const KIND: &'static str = "monkeys";
+
+    fn to_checkpoint(&self) -> String {
+        let mut s = format!("round {}\n", self.round);
+        s.push_str(&format!(
+            "divisors {}\n",
+            checkpoint::join_list(&self.divisors)
+        ));
+        for m in &self.monkeys {
+            let items: Vec<u128> = m.items.iter().cloned().collect();
+            s.push_str(&format!(
+                "monkey {};{};{} {};{};{};{};{};{};{}\n",
+                m.index,
+                checkpoint::join_list(&items),
+                m.operation.0,
+                m.operation.1,
+                m.test.0,
+                m.test.1,
+                m.test.2,
+                m.test.3,
+                m.inspection_count,
+                m.max_modulo
+            ));
+        }
+        s
+    }
+
+    fn from_checkpoint(data: &str) -> Result<Monkeys, String> {
+        let mut monkeys = Monkeys::new();
+        monkeys.round = checkpoint::parse(checkpoint::value(data, "round")?, "round")?;
+        monkeys.divisors = checkpoint::parse_list(checkpoint::value(data, "divisors")?, "divisor")?;
+        for line in checkpoint::values(data, "monkey") {
+            let fields: Vec<&str> = line.split(';').collect();
+            if fields.len() != 9 {
+                return Err(format!("checkpoint has a bad monkey: {:?}", line));
+            }
+            let operation = match fields[2].split_once(' ') {
+                Some((op, num))
+                    if ["+", "-", "*", "/"].contains(&op)
+                        && (num == "old" || num.parse::<u128>().is_ok())
+                        && !(op == "/" && (num == "old" || num.parse::<u128>() == Ok(0))) =>
+                {
+                    (op.to_string(), num.to_string())
+                }
+                _ => return Err(format!("checkpoint has a bad operation: {:?}", fields[2])),
+            };
+            if fields[3] != "divisible by" {
+                return Err(format!("checkpoint has a bad test: {:?}", fields[3]));
+            }
+            monkeys.add_monkey(Monkey {
+                index: checkpoint::parse(fields[0], "monkey index")?,
+                items: checkpoint::parse_list(fields[1], "item")?
+                    .into_iter()
+                    .collect(),
+                operation,
+                test: (
+                    fields[3].to_string(),
+                    checkpoint::parse(fields[4], "divisor")?,
+                    checkpoint::parse(fields[5], "monkey index")?,
+                    checkpoint::parse(fields[6], "monkey index")?,
+                ),
+                inspection_count: checkpoint::parse(fields[7], "inspection count")?,
+                max_modulo: checkpoint::parse(fields[8], "max modulo")?,
+            });
+        }
+        monkeys.check_resumed()?;
+        Ok(monkeys)
+    }
+}
+
+impl Monkeys {
+    // a checkpoint has to have the monkeys parse would have made: at least
+    // two, in order, throwing to each other, with a divisor each and the
+    // product of the divisors as every monkey's modulus
+    fn check_resumed(&self) -> Result<(), String> {
+        if self.monkeys.len() < 2 {
+            return Err(format!(
+                "checkpoint has {} monkeys, there need to be at least two",
+                self.monkeys.len()
+            ));
+        }
+        if self.divisors.len() != self.monkeys.len() {
+            return Err(format!(
+                "checkpoint has {} divisors for {} monkeys",
+                self.divisors.len(),
+                self.monkeys.len()
+            ));
+        }
+        let mut modulus: u128 = 1;
+        for d in &self.divisors {
+            modulus = match modulus.checked_mul(*d as u128) {
+                Some(modulus) if *d > 0 => modulus,
+                _ => return Err("checkpoint has a bad divisor".to_string()),
+            };
+        }
+        for (i, m) in self.monkeys.iter().enumerate() {
+            if m.index != i {
+                return Err(format!(
+                    "checkpoint has monkey {} where {} should be",
+                    m.index, i
+                ));
+            }
+            if m.test.1 != self.divisors[i] as u128 {
+                return Err(format!("checkpoint has the wrong divisor for monkey {}", i));
+            }
+            if m.test.2 >= self.monkeys.len() || m.test.3 >= self.monkeys.len() {
+                return Err(format!(
+                    "checkpoint has monkey {} throwing to a missing monkey",
+                    i
+                ));
+            }
+            if m.max_modulo != modulus {
+                return Err(format!(
+                    "checkpoint has a modulus of {} for monkey {}, the divisors make {}",
+                    m.max_modulo, i, modulus
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Monkey {
+    fn consolidate_item(&self, item: u128) -> u128 {
+        // modulo is a wraparound operation.
+        // we can simply loop around the product of all the
+        // divisors (max_modulo). If the number is larger, taking the
+        // remainder after dividing by the product of all the
+        // divisors leaves the result the same
+        item % self.max_modulo
+    }
+
+    fn inspect_item_and_test(&mut self, item_index: usize) -> (bool, usize) {
+        self.do_operation(item_index);
+        self.reduce_worry(item_index);
+        let test_passed = self.is_test_true(item_index);
+        self.inspection_count += 1;
+        if test_passed {
+            return (true, self.test.2);
+        } else {
+            return (false, self.test.3);
+        }
+    }
+
+    fn is_test_true(&mut self, item_index: usize) -> bool {
+        let item = &mut self.items[item_index];
+        match self.test.0.as_str() {
+            "divisible by" => {
+                if *item % self.test.1 == 0 {
+                    return true;
+                }
+            }
+            // parse and the checkpoint only make divisibility tests
+            _ => unreachable!("unknown test {:?}", self.test.0),
+        }
+        return false;
+    }
+
+    fn reduce_worry(&mut self, _item_index: usize) {
+        ()
+        // let item = &mut self.items[item_index];
+        // let mut b: f32 = *item as f32;
+        // b = b / 3.0_f32;
+        // *item = b.floor() as u32;
+    }
+
+    fn do_operation(&mut self, item_index: usize) {
+        let item = &mut self.items[item_index];
+        let mut num: u128 = item.clone();
+
+        if !self.operation.1.eq("old") {
+            num = self.operation.1.parse().unwrap();
+        }
+        match self.operation.0.as_str() {
+            "+" => {
+                *item = checked::add(WORRY, *item, num);
+            }
+            "-" => {
+                *item = checked::sub(WORRY, *item, num);
+            }
+            "*" => {
+                *item = checked::mul(WORRY, *item, num);
+            }
+            "/" => {
+                // parse and the checkpoint never divide by 0 or by old
+                *item /= num;
+            }
+            // parse and the checkpoint only make these four
+            _ => unreachable!("unknown operation {:?}", self.operation.0),
+        }
+        // the remainder rather than taking max_modulo away until it's
+        // smaller, which took millions of goes once an item was squared
+        *item %= self.max_modulo;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_example() {
+        let mut monkeys = Monkeys::parse(EXAMPLE).unwrap();
+        monkeys.run();
+        assert_eq!(monkeys.monkey_business(), 2713310158);
+    }
+
+    #[test]
+    fn test_explain_the_first_round() {
+        let mut monkeys = Monkeys::parse(EXAMPLE).unwrap();
+        monkeys.set_max_rounds(20);
+        let mut trace = Trace::new(true);
+        monkeys.run_traced(&mut trace);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "Round 1:\n\
+             Monkey 0 inspects an item with a worry level of 79, which is multiplied by 19 \
+             to 1501. 1501 isn't divisible by 23, so the item is thrown to monkey 3.\n\
+             Monkey 0 inspects an item with a worry level of 98, which is multiplied by 19 \
+             to 1862. 1862 isn't divisible by 23, so the item is thrown to monkey 3.\n\
+             Monkey 1 inspects an item with a worry level of 54, which increases by 6 \
+             to 60. 60 isn't divisible by 19, so the item is thrown to monkey 0.\n"
+        ));
+        assert!(explained.ends_with(
+            "Monkey 0 inspected items 99 times.\n\
+             Monkey 1 inspected items 97 times.\n\
+             Monkey 2 inspected items 8 times.\n\
+             Monkey 3 inspected items 103 times.\n\
+             After 20 rounds the monkey business is 10197.\n"
+        ));
+    }
+
+    #[test]
+    fn test_resume_gives_the_same_answer() {
+        let mut monkeys = Monkeys::parse(EXAMPLE).unwrap();
+        for _ in 0..1234 {
+            monkeys.step();
+        }
+        let mut resumed = Monkeys::from_checkpoint(&monkeys.to_checkpoint()).unwrap();
+        assert_eq!(resumed.round(), 1234);
+        resumed.run();
+        assert_eq!(resumed.monkey_business(), 2713310158);
+    }
+
+    #[test]
+    fn test_resume_checks_the_monkeys() {
+        let monkeys = Monkeys::parse(EXAMPLE).unwrap();
+        let saved = monkeys.to_checkpoint();
+        // a monkey missing
+        let dropped: String = saved
+            .lines()
+            .filter(|l| !l.starts_with("monkey 3;"))
+            .map(|l| format!("{}\n", l))
+            .collect();
+        assert!(Monkeys::from_checkpoint(&dropped).is_err());
+        // a divisor that isn't the monkey's
+        let divisors = saved.replace("divisors 23 19 13 17", "divisors 23 19 13 7");
+        assert!(Monkeys::from_checkpoint(&divisors).is_err());
+        // a modulus that isn't the product of the divisors
+        let modulus = saved.replace(";96577\n", ";96578\n");
+        assert_ne!(modulus, saved);
+        assert!(Monkeys::from_checkpoint(&modulus).is_err());
+        // dividing by old, which can be 0
+        let divide = saved.replace("* old;", "/ old;");
+        assert_ne!(divide, saved);
+        assert!(Monkeys::from_checkpoint(&divide).is_err());
+    }
+
+    #[test]
+    fn test_operations_that_cant_be_done() {
+        assert_eq!(
+            Monkeys::parse(&EXAMPLE.replace("old * old", "old / old")).err(),
+            Some("line 17: can't divide by old, it can be 0".to_string())
+        );
+        assert_eq!(
+            Monkeys::parse(&EXAMPLE.replace("old * old", "old / 0")).err(),
+            Some("line 17: can't divide by 0".to_string())
+        );
+
+        // monkey 3 takes more away than its first item's worry level
+        checked::set_enabled(true);
+        let mut monkeys = Monkeys::parse(&EXAMPLE.replace("old + 3", "old - 100")).unwrap();
+        let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| monkeys.step()));
+        assert_eq!(
+            panicked.unwrap_err().downcast_ref::<String>().unwrap(),
+            "overflow in day11 worry level: 74 - 100"
+        );
+    }
+}
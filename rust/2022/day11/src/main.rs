@@ -0,0 +1,107 @@
+use std::fs;
+
+use y2022_day11::shared;
+use y2022_day11::Monkeys;
+use y2022_day11::{DAY, YEAR};
+
+use shared::checkpoint::Checkpointer;
+use shared::replay::Recorder;
+use shared::simulation::{self, CheckpointObserver, ProgressObserver, Runner};
+use shared::trace::Trace;
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+
+    let mut filename = "./test-data.txt".to_string();
+    match shared::cli::filename() {
+        Some(arg) => {
+            filename = arg;
+        }
+        None => {
+            println!("No filename given, using test data\n");
+        }
+    }
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day11", &filename, |s| Monkeys::parse(s).map(|_| ()));
+        return;
+    }
+
+    let mut checkpointer = Checkpointer::from_args("./day11.checkpoint");
+
+    let mut monkeys = match checkpointer.resume::<Monkeys>() {
+        Some(Ok(monkeys)) => {
+            println!(
+                "Resuming from {} at round {}\n",
+                checkpointer.path(),
+                monkeys.round()
+            );
+            monkeys
+        }
+        Some(Err(why)) => panic!("couldn't resume: {}", why),
+        None => {
+            let data = match fs::read_to_string(&filename) {
+                Err(why) => panic!("couldn't read {}: {}", filename, why),
+                Ok(data) => data,
+            };
+            match Monkeys::parse(&data) {
+                Err(why) => panic!("couldn't parse {}: {}", filename, why),
+                Ok(monkeys) => monkeys,
+            }
+        }
+    };
+
+    // for m in &monkeys.monkeys {
+    //     println!("{:?}", m);
+    // }
+
+    // println!("{:?}", monkeys.monkeys);
+
+    // the puzzle is 10000 rounds, but this can be changed with --rounds=N
+    if let Some(r) = shared::cli::flag_value("--rounds") {
+        monkeys.set_max_rounds(r.parse().expect("--rounds should be a number"));
+    }
+    let runner = Runner::new()
+        .observe(ProgressObserver::new("Rounds"))
+        .observe(CheckpointObserver::new(&mut checkpointer))
+        .observe(Recorder::from_args());
+    simulation::run_from_args(&mut monkeys, runner);
+
+    let mut inspection_count: Vec<u128> = Vec::new();
+    println!("\nRound {}", monkeys.round());
+    for monkey in monkeys.monkeys() {
+        inspection_count.push(monkey.inspection_count as u128);
+        println!("Monkey {}: [{:>3}]", monkey.index, monkey.inspection_count);
+    }
+    println!("");
+    // now output what data is needed
+    inspection_count.sort();
+    let highest = inspection_count.pop().unwrap();
+    let next_highest = inspection_count.pop().unwrap();
+    println!(
+        "Monkey Business: {} * {} = {}",
+        highest,
+        next_highest,
+        monkeys.monkey_business()
+    );
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        // the simulation above has already run, so play again from the notes
+        let explained = fs::read_to_string(&filename)
+            .map_err(|why| why.to_string())
+            .and_then(|data| Monkeys::parse(&data));
+        if let Ok(mut explained) = explained {
+            if let Some(r) = shared::cli::flag_value("--rounds") {
+                explained.set_max_rounds(r.parse().expect("--rounds should be a number"));
+            }
+            explained.run_traced(&mut trace);
+            println!("\n{}", trace.render(y2022_day11::narrate));
+        }
+    }
+}
@@ -0,0 +1,345 @@
+//! Day 12: Hill Climbing Algorithm.
+//!
+//! The input is a heightmap of the letters a-z, with the start marked S and
+//! the end marked E. Each step can go up at most one letter. [`HeightMap::parse`]
+//! reads the map, and [`HeightMap::steps_to_end`] finds the fewest steps to
+//! the end with a breadth first search.
+
+use std::collections::{HashMap, VecDeque};
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 12;
+
+/// The heights of the squares by (x, y), the squares each one can step to,
+/// and where the start and end are.
+pub struct HeightMap {
+    pub grid: HashMap<(i32, i32), i32>,
+    pub graph: HashMap<(i32, i32), Vec<(i32, i32, i32)>>,
+    pub start: (i32, i32),
+    pub end: (i32, i32),
+}
+
+impl HeightMap {
+    /// Reads the heightmap. Fails on anything other than a-z, S and E, or if
+    /// there is no S or no E.
+    pub fn parse(s: &str) -> Result<HeightMap, String> {
+        let (grid, graph, start, end) =
+            lines_to_graph(s.lines().map(|l| l.to_string()).collect())?;
+        Ok(HeightMap {
+            grid,
+            graph,
+            start,
+            end,
+        })
+    }
+
+    /// The fewest steps from `from` to the end, or None if the end can't be
+    /// reached.
+    pub fn steps_to_end(&self, from: &(i32, i32)) -> Option<i32> {
+        let (_route, level, end_reached) = self.search(from);
+        if end_reached {
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    /// A breadth first search from `from` until it reaches the end. Returns
+    /// each square visited with its distance from `from`, the distance to the
+    /// end, and whether the end was reached.
+    pub fn search(&self, from: &(i32, i32)) -> (Vec<((i32, i32), i32)>, i32, bool) {
+        bfs(&self.graph, from, &self.end, &self.grid)
+    }
+
+    /// The squares at the lowest height, a (or S).
+    pub fn lowest_points(&self) -> Vec<(i32, i32)> {
+        let mut a_points = vec![];
+        for (k, v) in &self.grid {
+            if *v == 1 {
+                a_points.push(*k);
+            }
+        }
+        a_points.sort();
+        a_points
+    }
+
+    /// The fewest steps from any square at height a to the end.
+    pub fn fewest_steps_from_lowest(&self) -> Option<i32> {
+        self.fewest_steps_from_lowest_traced(&mut Trace::default())
+    }
+
+    /// [`HeightMap::fewest_steps_from_lowest`], recording how far the end is
+    /// from each square at height a, and the closest, in `trace` (see
+    /// [`narrate`]).
+    pub fn fewest_steps_from_lowest_traced(&self, trace: &mut Trace) -> Option<i32> {
+        let mut fewest: Option<((i32, i32), i32)> = None;
+        for k in self.lowest_points() {
+            let steps = self.steps_to_end(&k);
+            let at = format!("{},{}", k.0, k.1);
+            match steps {
+                Some(steps) => trace.event("from", &[("at", &at), ("steps", &steps)]),
+                None => trace.event("unreachable", &[("at", &at)]),
+            }
+            if let Some(steps) = steps {
+                if fewest.is_none_or(|(_, fewest_steps)| steps < fewest_steps) {
+                    fewest = Some((k, steps));
+                }
+            }
+        }
+        if let Some((k, steps)) = fewest {
+            trace.event("fewest", &[("at", &format!("{},{}", k.0, k.1)), ("steps", &steps)]);
+        }
+        fewest.map(|(_, steps)| steps)
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`HeightMap::fewest_steps_from_lowest_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "from" => format!("From {} the end is {} steps away.", event.get("at"), event.get("steps")),
+        "unreachable" => format!("From {} the end can't be reached.", event.get("at")),
+        "fewest" => format!(
+            "The fewest steps are {}, starting from {}.",
+            event.get("steps"),
+            event.get("at")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// the heightmap is a-z, with one S (start) and one E (end)
+fn lines_to_graph(
+    lines: Vec<String>,
+) -> Result<
+    (
+        HashMap<(i32, i32), i32>,
+        HashMap<(i32, i32), Vec<(i32, i32, i32)>>,
+        (i32, i32),
+        (i32, i32),
+    ),
+    String,
+> {
+    let mut graph: HashMap<(i32, i32), Vec<(i32, i32, i32)>> = HashMap::new();
+    let mut grid: HashMap<(i32, i32), i32> = HashMap::new();
+
+    let mut start: Option<(i32, i32)> = None;
+    let mut end: Option<(i32, i32)> = None;
+
+    for (ey, line) in lines.iter().enumerate() {
+        for (ex, c) in line.chars().enumerate() {
+            let y = ey as i32;
+            let x = ex as i32;
+            let mut n = c as u32;
+            if c == 'S' && start.is_none() {
+                start = Some((x, y));
+                n = 1;
+            } else if c == 'E' && end.is_none() {
+                end = Some((x, y));
+                n = 26;
+            } else if c.is_ascii_lowercase() {
+                n = n - 'a' as u32 + 1;
+            } else {
+                return Err(format!("line {}: {:?} is not a height", ey + 1, c));
+            }
+            grid.insert((x, y), n as i32);
+        }
+    }
+    let start = match start {
+        Some(start) => start,
+        None => return Err("there is no start (S)".to_string()),
+    };
+    let end = match end {
+        Some(end) => end,
+        None => return Err("there is no end (E)".to_string()),
+    };
+
+    // now build the graph
+    let grid_clone = grid.clone();
+    for i in 0..grid.len() {
+        let pos: (i32, i32) = grid.keys().nth(i).unwrap().clone();
+        let height = *grid.get(&pos).unwrap();
+        let x = pos.0;
+        let y = pos.1;
+        let mut edges: Vec<(i32, i32, i32)> = Vec::new();
+        let moves: Vec<(i32, i32)> = vec![(0, 1), (0, -1), (1, 0), (-1, 0)];
+        for m in moves {
+            let neighbour_x: i32 = x + m.0;
+            let neighbour_y: i32 = y + m.1;
+            let neighbour = grid_clone.get(&(neighbour_x, neighbour_y));
+            if neighbour.is_some() {
+                let neighbour_height = neighbour.unwrap();
+                if neighbour_height < &(height + 2) {
+                    // the edge exists and it can be moved to
+                    edges.push((neighbour_x, neighbour_y, *neighbour_height));
+                }
+            }
+        }
+        graph.insert((x, y), edges);
+    }
+
+    Ok((grid, graph, start, end))
+}
+
+/// Checks the heightmap the way [`HeightMap::parse`] reads it, but finds
+/// every problem rather than the first. It also reports rows that aren't as
+/// wide as the first, and a second S or E, which the parser would take as a
+/// square that can't be reached.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut width: Option<usize> = None;
+    let mut start: Option<usize> = None;
+    let mut end: Option<usize> = None;
+    for (i, line) in s.lines().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            let first = match c {
+                'S' => start.get_or_insert(i),
+                'E' => end.get_or_insert(i),
+                'a'..='z' => continue,
+                _ => {
+                    lint.at(i, format!("{:?} in column {} is not a height", c, column + 1));
+                    continue;
+                }
+            };
+            if *first != i || line.chars().take(column).any(|x| x == c) {
+                lint.at(i, format!("a second {} (the first is on line {})", c, *first + 1));
+            }
+        }
+        let squares = line.chars().count();
+        match width {
+            None => width = Some(squares),
+            Some(width) if squares != width => {
+                lint.at(i, format!("has {} squares, the first row has {}", squares, width))
+            }
+            Some(_) => (),
+        }
+    }
+    if start.is_none() {
+        lint.whole("there is no start (S)");
+    }
+    if end.is_none() {
+        lint.whole("there is no end (E)");
+    }
+    lint.problems()
+}
+
+/*
+Declare a queue and insert the starting vertex.
+Initialize a visited array and mark the starting vertex as visited.
+Follow the below process till the queue becomes empty:
+Remove the first vertex of the queue.
+Mark that vertex as visited.
+Insert all the unvisited neighbours of the vertex into the queue.
+
+--> Additionally if you set the *level* for each node, then 
+you only need to do the run through once, and you can get the
+shortest path to the end node.
+--> level is the number of steps taken to get to the node
+*/
+
+// a breadth first search from start until it reaches end (see HeightMap::search)
+fn bfs(
+    graph: &HashMap<(i32, i32), Vec<(i32, i32, i32)>>,
+    start: &(i32, i32),
+    end: &(i32, i32),
+    grid: &HashMap<(i32, i32), i32>,
+) -> (Vec<((i32, i32), i32)>, i32, bool) {
+    let mut q: VecDeque<((i32, i32), i32)> = VecDeque::new();
+    let mut v: Vec<((i32, i32), i32)> = vec![];
+    // make a quick visited list
+    let mut visited: HashMap<String,bool> = HashMap::new();
+    for (k,_) in grid {
+        visited.insert(format!("{},{}",k.0,k.1), false);
+    }
+
+    let mut l: i32 = 0;
+
+    q.push_back(((start.0, start.1), l));
+
+    let mut end_l = 0;
+    let mut end_reached = false;
+
+    while !q.is_empty() {
+        let (p, level) = q.pop_front().unwrap();
+        l = level;
+        // move the position to the next in the queue
+        // check if visited again...
+        let pos = format!("{},{}",p.0, p.1);
+        let mut is_visited = visited.get(&pos).unwrap();
+        if *is_visited {
+            continue;
+        }
+        v.push(((p.0, p.1), l));
+        // set the point as visited
+        let point = visited.get_mut(&pos).unwrap();
+        *point = true; // set the pointer to true
+        if p.0 == end.0 && p.1 == end.1 {
+            end_l = l;
+            end_reached = true;
+            break;
+        }
+        l += 1;
+        let edges = graph.get(&p).unwrap();
+        for e in edges {
+            // if not visited, this will be false
+            is_visited = visited.get(&format!("{},{}",e.0, e.1)).unwrap();
+            // println!("{} {} {} {}", e.0, e.1, e.2, is_visited);
+            // thread::sleep(std::time::Duration::from_millis(50));
+            if !*is_visited {
+                q.push_back(((e.0, e.1), l));
+            }
+        }
+    }
+    (v, end_l, end_reached)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi\n";
+
+    #[test]
+    fn test_example() {
+        let map = HeightMap::parse(EXAMPLE).unwrap();
+        assert_eq!((map.start, map.end), ((0, 0), (5, 2)));
+        assert_eq!(map.grid.len(), 40);
+        assert_eq!(map.steps_to_end(&map.start), Some(31));
+        assert_eq!(map.lowest_points().len(), 6);
+        assert_eq!(map.fewest_steps_from_lowest(), Some(29));
+    }
+
+    #[test]
+    fn test_explain_the_fewest_steps() {
+        let map = HeightMap::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(map.fewest_steps_from_lowest_traced(&mut trace), Some(29));
+        assert_eq!(
+            trace.render(narrate),
+            "From 0,0 the end is 31 steps away.\n\
+             From 0,1 the end is 30 steps away.\n\
+             From 0,2 the end is 31 steps away.\n\
+             From 0,3 the end is 30 steps away.\n\
+             From 0,4 the end is 29 steps away.\n\
+             From 1,0 the end is 30 steps away.\n\
+             The fewest steps are 29, starting from 0,4.\n"
+        );
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        // the end is more than one higher than everything next to it
+        let map = HeightMap::parse("Sbw\nabE\n").unwrap();
+        assert_eq!(map.steps_to_end(&map.start), None);
+        assert_eq!(map.fewest_steps_from_lowest(), None);
+        assert_eq!(HeightMap::parse("abc\n").err(), Some("there is no start (S)".to_string()));
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use y2022_day12::shared;
+use y2022_day12::HeightMap;
+use y2022_day12::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+struct AOC {
+    data: Vec<String>,
+}
+
+impl AOC {
+    fn new() -> AOC {
+        AOC { data: Vec::new() }
+    }
+
+    // a buffered reader is returned from here
+    fn read_lines<P>(&self, filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let file: File = File::open(filename)?;
+        Ok(io::BufReader::new(file).lines())
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        if let Ok(lines) = self.read_lines(filename) {
+            for l in lines {
+                if let Ok(line) = l {
+                    self.data.push(line);
+                }
+            }
+        }
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+    let mut aoc = AOC::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day12", &filename, |s| HeightMap::parse(s).map(|_| ()));
+        return;
+    }
+
+    aoc.load_all_data(&filename);
+
+    // use breadth-first-search
+    // just have to build the tree
+    // https://programming-idioms.org/idiom/128/breadth-first-traversing-of-a-tree/2490/rust
+
+    let map = match HeightMap::parse(&aoc.data.join("\n")) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(map) => map,
+    };
+
+    // build a graph from the grid
+    // https://www.geeksforgeeks.org/breadth-first-search-or-bfs-for-a-graph/
+    // traversing a graph is different to traversing a tree
+
+
+    let mut min_distance = 500;
+    let a_points = map.lowest_points();
+
+    let mut data = vec![];
+    for k in a_points {
+        println!("{},{}", k.0, k.1);
+        let (route, level, end_reached) = map.search(&k);
+        data.push((format!("{},{}", k.0,k.1), level, route));
+        println!("{},{}: {} ({:?})", k.0, k.1, level, end_reached);
+        if level < min_distance && end_reached {
+            min_distance = level;
+        }
+    }
+
+    println!("{:?}", data);
+
+    println!("Min distance: {}", min_distance);
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        map.fewest_steps_from_lowest_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day12::narrate));
+    }
+
+}
@@ -0,0 +1,702 @@
+//! Day 13: Distress Signal.
+//!
+//! The input is pairs of packets, which are lists of integers and other lists.
+//! [`compare_packets`] says which of two packets comes first. Part 1 is the
+//! sum of the indexes of the pairs already in the right order, and part 2 is
+//! the decoder key from sorting all the packets with two divider packets.
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 13;
+
+use shared::checked;
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+
+// https://rust-unofficial.github.io/too-many-lists/second-option.html
+
+#[derive(Debug, Clone)]
+struct DataItem {
+    s: String,            // the original string
+    index: usize,         // the index of this item in the data store
+    parent: DParent,      // if root, then no parent
+    children: Vec<usize>, // if empty, then no children
+    int: DInt,            // if it's just a value, then this is the value
+}
+
+// yay type aliases!
+type DParent = Option<Box<usize>>;
+type DInt = Option<Box<i32>>;
+// type DList = Option<Box<Vec<usize>>>;
+
+#[derive(Debug)]
+struct DataParseError {
+    reason: String,
+}
+
+impl DataParseError {
+    fn new(reason: String) -> DataParseError {
+        DataParseError { reason: reason }
+    }
+}
+
+// packets nested deeper than this are rejected rather than overflowing the stack
+const MAX_DEPTH: usize = 100;
+
+type DataParseResult<T> = Result<T, DataParseError>;
+
+enum DataCompare {
+    RightOrder,
+    WrongOrder,
+    ContinueComparing,
+}
+
+type DataComparisonResult = DataCompare;
+
+impl DataItem {
+    fn new(s: String) -> DataItem {
+        DataItem {
+            s: s,
+            index: 0,
+            parent: None,
+            children: vec![],
+            int: None,
+        }
+    }
+
+    fn get_index(&self) -> usize {
+        self.index.clone()
+    }
+
+    fn get_integer(&self) -> Option<i32> {
+        if self.int.is_some() {
+            return Some(*self.int.as_ref().unwrap().clone());
+        }
+        None
+    }
+
+    fn get_parent(&self) -> Option<usize> {
+        if self.parent.is_some() {
+            return Some(*self.parent.as_ref().unwrap().clone());
+        }
+        None
+    }
+
+    fn set_parent(&mut self, p: usize) {
+        self.parent = Some(Box::new(p));
+    }
+
+    fn has_children(&self) -> bool {
+        !self.is_integer()
+        //self.children.len() > 0
+    }
+
+    fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    fn is_integer(&self) -> bool {
+        self.int.is_some()
+    }
+
+    fn set_int(&mut self, i: i32) {
+        self.int = Some(Box::new(i));
+    }
+}
+
+#[derive(Debug)]
+struct DataStore {
+    left_data: Vec<DataItem>,  // all data items for the left side
+    right_data: Vec<DataItem>, // all data items for the right side
+    original_string_left: String,
+    original_string_right: String,
+    trace: Trace, // the decisions made comparing, for --explain
+}
+
+impl DataStore {
+    fn new(l: String, r: String) -> DataStore {
+        DataStore {
+            left_data: Vec::new(),
+            right_data: Vec::new(),
+            original_string_left: l.clone(), // make sure we clone these
+            original_string_right: r.clone(), // make sure we clone these
+            trace: Trace::default(),
+        }
+    }
+
+    // fn is_in_right_order(&mut self) -> bool {
+    //     let right_order = self.compare(0, 0, 0);
+    //     match right_order {
+    //         Some(DataCompare::RightOrder) => {
+    //             println!("Right order");
+    //             return true;
+    //         }
+    //         Some(DataCompare::WrongOrder) => {
+    //             println!("Wrong order");
+    //             return false;
+    //         }
+    //         Some(DataCompare::ContinueComparing) => {
+    //             println!("Continue comparing");
+    //             return false;
+    //         }
+    //         None => {
+    //             println!("None");
+    //             return false;
+    //         }
+    //     }
+    // }
+
+    fn compare(&mut self, l: usize, r: usize, depth: i32) -> Option<DataComparisonResult> {
+        // this is recursive
+        // start at l=0 and r=0 and work through the data
+        let l_item = &self.left_data.get(l).unwrap();
+        let r_item = &self.right_data.get(r).unwrap();
+
+        if l_item.is_integer() && r_item.is_integer() {
+            let left_int = l_item.get_integer().unwrap();
+            let right_int = r_item.get_integer().unwrap();
+            return self.compare_integers(&left_int, &right_int, depth + 1);
+        }
+
+        if l_item.has_children() && r_item.has_children() {
+            // get items...
+            return self.compare_lists(l, r, depth + 1);
+        }
+
+        // println!("We get here...");
+        // println!("l_item: {:?}", l_item);
+        // println!("r_item: {:?}", r_item);
+        if l_item.has_children() && r_item.is_integer() {
+            // create a new data item so that the r_item as a child and the new item has a list of 1
+            let num = r_item.get_integer().unwrap();
+            let mut new_data_item = DataItem::new(format!("[{}]", num.to_owned()));
+            self.trace
+                .event("mixed", &[("side", &"right"), ("value", &new_data_item.s)]);
+            new_data_item.set_int(num.clone());
+            let parent = r_item.get_parent();
+            if parent.is_some() {
+                new_data_item.set_parent(parent.unwrap());
+            }
+            new_data_item.index = self.right_data.len();
+            new_data_item.children.push(r_item.get_index());
+            // push the data item to the right data
+            self.right_data.push(new_data_item);
+            // now do a new compare with this new item as it is a list that can be used for a comparison
+            return self.compare_lists(l, self.right_data.len() - 1, depth + 1);
+        } else if r_item.has_children() && l_item.is_integer() {
+            // create a new data item so that the l_item as a child and the new item has a list of 1
+            let num = l_item.get_integer().unwrap();
+            let mut new_data_item = DataItem::new(format!("[{}]", num.to_owned()));
+            self.trace
+                .event("mixed", &[("side", &"left"), ("value", &new_data_item.s)]);
+            new_data_item.set_int(num.clone());
+            let parent = l_item.get_parent();
+            if parent.is_some() {
+                new_data_item.set_parent(parent.unwrap());
+            }
+            new_data_item.index = self.left_data.len();
+            new_data_item.children.push(l_item.get_index());
+            // push the data item to the right data
+            self.left_data.push(new_data_item);
+            // now do a new compare with this new item as it is a list that can be used for a comparison
+            return self.compare_lists(self.left_data.len() - 1, r, depth + 1);
+        }
+
+        Some(DataCompare::ContinueComparing)
+    }
+
+    fn compare_lists(
+        &mut self,
+        left_list: usize,
+        right_list: usize,
+        depth: i32,
+    ) -> Option<DataComparisonResult> {
+        let l_item = self.left_data.get(left_list).unwrap().clone();
+        let r_item = self.right_data.get(right_list).unwrap().clone();
+
+        self.trace
+            .event("compare", &[("left", &l_item.s), ("right", &r_item.s)]);
+        self.trace.enter();
+        let result = self.compare_list_items(&l_item, &r_item, depth);
+        self.trace.leave();
+        result
+    }
+
+    fn compare_list_items(
+        &mut self,
+        l_item: &DataItem,
+        r_item: &DataItem,
+        depth: i32,
+    ) -> Option<DataComparisonResult> {
+        let left_children_indexes = &l_item.children;
+        let right_children_indexes = &r_item.children;
+
+        if left_children_indexes.len() == 0 && right_children_indexes.len() > 0 {
+            self.trace.event("ran out", &[("side", &"left")]);
+            return Some(DataCompare::RightOrder);
+        }
+
+        let mut i = left_children_indexes.len();
+        if right_children_indexes.len() > i {
+            i = r_item.child_count();
+        }
+        // loop up to max child count
+        for j in 0..i {
+            let l = l_item.clone();
+            let r = r_item.clone();
+            let l_child_index = l.children.get(j).clone();
+            let r_child_index = r.children.get(j).clone();
+            if l_child_index.is_none() && r_child_index.is_some() {
+                self.trace.event("ran out", &[("side", &"left")]);
+                // If the left list runs out of items first, the inputs are in the right order
+                return Some(DataCompare::RightOrder);
+            } else if r_child_index.is_none() && l_child_index.clone().is_some() {
+                self.trace.event("ran out", &[("side", &"right")]);
+                // If the right list runs out of items first, the inputs are not in the right order.
+                return Some(DataCompare::WrongOrder);
+            } else if l_child_index.is_some() && r_child_index.is_some() {
+                let lci = l_child_index.unwrap();
+                let rci = r_child_index.unwrap();
+                match self.compare(*lci, *rci, depth + 1) {
+                    Some(DataCompare::ContinueComparing) => {
+                        // continue comparing
+                    }
+                    Some(DataCompare::RightOrder) => {
+                        // right order
+                        return Some(DataCompare::RightOrder);
+                    }
+                    Some(DataCompare::WrongOrder) => {
+                        // wrong order
+                        return Some(DataCompare::WrongOrder);
+                    }
+                    None => {
+                        // continue comparing
+                    }
+                }
+            }
+        }
+
+        Some(DataCompare::ContinueComparing)
+    }
+
+    fn compare_integers(
+        &mut self,
+        left_int: &i32,
+        right_int: &i32,
+        _depth: i32,
+    ) -> Option<DataComparisonResult> {
+        self.trace
+            .event("compare", &[("left", left_int), ("right", right_int)]);
+        if left_int == right_int {
+            return Some(DataCompare::ContinueComparing);
+        } else if left_int < right_int {
+            self.trace.enter();
+            self.trace.event("smaller", &[("side", &"left")]);
+            self.trace.leave();
+            return Some(DataCompare::RightOrder);
+        } else {
+            self.trace.enter();
+            self.trace.event("smaller", &[("side", &"right")]);
+            self.trace.leave();
+            return Some(DataCompare::WrongOrder);
+        }
+    }
+
+    fn parse(&mut self) -> DataParseResult<()> {
+        // println!(
+        //     "\n\nParsing {} and {}",
+        //     self.original_string_left, self.original_string_right
+        // );
+        // take left and right and parse them
+        if !DataStore::is_list(&self.original_string_left) {
+            return Err(DataParseError::new("left packet is not a list".to_string()));
+        }
+        let l = self.parse_string(true, &mut self.original_string_left.clone(), None, 0);
+        if let Err(why) = l {
+            return Err(DataParseError::new(format!("left packet: {}", why.reason)));
+        }
+
+        if !DataStore::is_list(&self.original_string_right) {
+            return Err(DataParseError::new(
+                "right packet is not a list".to_string(),
+            ));
+        }
+        let r = self.parse_string(false, &mut self.original_string_right.clone(), None, 0);
+        if let Err(why) = r {
+            return Err(DataParseError::new(format!("right packet: {}", why.reason)));
+        }
+        Ok(())
+    }
+
+    // return a list of Strings for the specific items
+    fn parse_string(
+        &mut self,
+        is_left: bool,
+        s: &mut String,
+        parent: Option<usize>,
+        depth: usize,
+    ) -> DataParseResult<usize> {
+        // println!("parse_string: {:?}", s);
+        if depth > MAX_DEPTH {
+            return Err(DataParseError::new(format!(
+                "lists are nested more than {} deep",
+                MAX_DEPTH
+            )));
+        }
+        // create a data item
+        let mut data_item = DataItem::new(s.clone());
+        if parent.is_some() {
+            data_item.parent = Some(Box::new(parent.unwrap()));
+        }
+        let item_index;
+        if is_left {
+            item_index = self.left_data.len();
+            data_item.index = item_index.clone();
+            self.left_data.push(data_item.clone());
+        } else {
+            item_index = self.right_data.len();
+            data_item.index = item_index.clone();
+            self.right_data.push(data_item.clone());
+        }
+
+        // now we have a data_item... we can add it to the
+        if DataStore::is_list(&s) {
+            let mut items: Vec<String> = Vec::new();
+            // now parse the list
+            // get each of the parts as a string
+            // get a DataItem for each
+            let mut data_string: &mut String = s;
+            DataStore::strip_list_brackets(&mut data_string);
+            let mut bracket_count = 0;
+            let mut item_string = String::new();
+
+            for _c in data_string.chars() {
+                if _c == '[' {
+                    //     // start of a list
+                    bracket_count += 1;
+                    //     brackets_found = true;
+                    item_string.push(_c);
+                } else if _c == ']' {
+                    //     // end of a list
+                    if bracket_count == 0 {
+                        return Err(DataParseError::new(format!("unbalanced brackets in {}", s)));
+                    }
+                    bracket_count -= 1;
+                    item_string.push(_c);
+                } else if _c == ',' {
+                    if bracket_count == 0 {
+                        if item_string.len() == 0 {
+                            return Err(DataParseError::new(format!("empty item in {}", s)));
+                        }
+                        items.push(item_string.clone());
+                        item_string.clear();
+                    } else {
+                        item_string.push(_c);
+                    }
+                } else {
+                    item_string.push(_c);
+                }
+            }
+            if bracket_count != 0 {
+                return Err(DataParseError::new(format!("unbalanced brackets in {}", s)));
+            }
+            // catch the case where there is zero string length
+            if item_string.len() > 0 {
+                // data_items.add(DataStore::parse_item(&item_string));
+                items.push(item_string.clone());
+                item_string.clear();
+            }
+
+            // now we have items!!!!
+            for item in items.iter() {
+                let ii = Some(item_index).as_ref().cloned();
+                if item.len() == 0 {
+                    return Err(DataParseError::new(format!("empty item in {}", s)));
+                }
+                let _new_item_index = self.parse_string(is_left, &mut item.clone(), ii, depth + 1);
+                // set children in parent
+                let index = _new_item_index?;
+                if is_left {
+                    self.left_data
+                        .get_mut(ii.unwrap())
+                        .unwrap()
+                        .children
+                        .push(index);
+                } else {
+                    self.right_data
+                        .get_mut(ii.unwrap())
+                        .unwrap()
+                        .children
+                        .push(index);
+                }
+            }
+        } else {
+            let num_result = s.parse::<i32>();
+            if num_result.is_err() {
+                return Err(DataParseError::new(format!("{:?} is not an integer", s)));
+            }
+            let num = num_result.unwrap();
+            if is_left {
+                self.left_data[item_index].set_int(num);
+            } else {
+                self.right_data[item_index].set_int(num);
+            }
+        }
+
+        Ok(item_index)
+    }
+
+    fn strip_list_brackets(data: &mut String) {
+        if DataStore::is_list(data) {
+            data.remove(0);
+            data.pop();
+        }
+    }
+
+    fn is_list(data: &String) -> bool {
+        data.starts_with("[") && data.ends_with("]")
+    }
+}
+
+use std::cmp::*;
+
+/// Compares two packets, for sorting. Panics if either isn't a packet, so
+/// the packets should have been checked when they were loaded.
+pub fn compare_packets(a: &str, b: &str) -> Ordering {
+    match try_compare_packets(a, b) {
+        Ok(order) => order,
+        Err(why) => panic!("couldn't parse packets: {}", why),
+    }
+}
+
+/// Compares two packets. Less means they are in the right order. Fails if
+/// either isn't a packet.
+pub fn try_compare_packets(a: &str, b: &str) -> Result<Ordering, String> {
+    try_compare_packets_traced(a, b, &mut Trace::default())
+}
+
+/// [`try_compare_packets`], recording each comparison it makes in `trace`
+/// (see [`narrate`]).
+pub fn try_compare_packets_traced(a: &str, b: &str, trace: &mut Trace) -> Result<Ordering, String> {
+    let mut lr = DataStore::new(a.to_string(), b.to_string());
+    if let Err(why) = lr.parse() {
+        return Err(why.reason);
+    }
+    lr.trace = std::mem::take(trace);
+    let order = lr.compare(0, 0, 0);
+    *trace = lr.trace;
+    let (order, result) = match order {
+        Some(DataCompare::RightOrder) => (Ordering::Less, "right order"),
+        Some(DataCompare::ContinueComparing) => (Ordering::Equal, "the same"),
+        Some(DataCompare::WrongOrder) => (Ordering::Greater, "wrong order"),
+        None => return Err("the packets couldn't be compared".to_string()), // shouldn't ever get here
+    };
+    trace.event("result", &[("left", &a), ("right", &b), ("order", &result)]);
+    Ok(order)
+}
+
+/// One line of the puzzle's own explanation for an event recorded by
+/// [`try_compare_packets_traced`] or [`Packets::right_order_index_sum_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "pair" => format!("== Pair {} ==", event.get("index")),
+        "compare" => format!("- Compare {} vs {}", event.get("left"), event.get("right")),
+        "mixed" => format!(
+            "- Mixed types; convert {} to {} and retry comparison",
+            event.get("side"),
+            event.get("value")
+        ),
+        "smaller" if event.get("side") == "left" => {
+            "- Left side is smaller, so inputs are in the right order".to_string()
+        }
+        "smaller" => "- Right side is smaller, so inputs are not in the right order".to_string(),
+        "ran out" if event.get("side") == "left" => {
+            "- Left side ran out of items, so inputs are in the right order".to_string()
+        }
+        "ran out" => {
+            "- Right side ran out of items, so inputs are not in the right order".to_string()
+        }
+        "result" => format!(
+            "Compare {} vs {} \u{2192} {}",
+            event.get("left"),
+            event.get("right"),
+            event.get("order")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+/// The divider packets added for part 2.
+pub const DIVIDER_PACKETS: [&str; 2] = ["[[2]]", "[[6]]"];
+
+/// The pairs of packets in the input.
+#[derive(Debug, Clone, Default)]
+pub struct Packets {
+    pairs: Vec<(String, String)>,
+}
+
+impl Packets {
+    /// Reads pairs of packets, one packet per line, with blank lines between
+    /// the pairs. Fails if a packet isn't a list of integers and lists, is
+    /// nested more than 100 deep, or has no pair.
+    pub fn parse(s: &str) -> Result<Packets, String> {
+        Ok(Packets {
+            pairs: parse_pairs(s)?,
+        })
+    }
+
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+
+    /// The sum of the 1-based indexes of the pairs in the right order. Part 1.
+    pub fn right_order_index_sum(&self) -> usize {
+        self.right_order_index_sum_traced(&mut Trace::default())
+    }
+
+    /// [`Packets::right_order_index_sum`], recording every comparison in
+    /// `trace`.
+    pub fn right_order_index_sum_traced(&self, trace: &mut Trace) -> usize {
+        let mut sum = 0;
+        for (i, (l, r)) in self.pairs.iter().enumerate() {
+            trace.event("pair", &[("index", &(i + 1))]);
+            let order = match try_compare_packets_traced(l, r, trace) {
+                Ok(order) => order,
+                Err(why) => panic!("couldn't parse packets: {}", why),
+            };
+            if order == Ordering::Less {
+                sum = checked::add("day13 sum of indexes", sum, i + 1);
+            }
+        }
+        sum
+    }
+
+    /// Every packet plus the divider packets, in order.
+    pub fn sorted_with_dividers(&self) -> Vec<String> {
+        let mut packets: Vec<String> = DIVIDER_PACKETS.iter().map(|p| p.to_string()).collect();
+        for (l, r) in &self.pairs {
+            packets.push(l.clone());
+            packets.push(r.clone());
+        }
+        packets.sort_by(|a, b| compare_packets(a, b));
+        packets
+    }
+
+    /// The 1-based indexes of the divider packets once sorted, multiplied
+    /// together. Part 2.
+    pub fn decoder_key(&self) -> usize {
+        let packets = self.sorted_with_dividers();
+        let indexes = DIVIDER_PACKETS
+            .iter()
+            .map(|d| packets.iter().position(|p| p == d).unwrap() + 1);
+        checked::product("day13 decoder key", indexes)
+    }
+}
+
+// pairs of packets, one packet per line, with blank lines between the pairs
+fn parse_pairs(s: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs: Vec<(String, String)> = Vec::new();
+    let mut left_right: Vec<String> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        // println!("Line: {}", line);
+        if line.len() == 0 {
+            continue;
+        }
+        left_right.push(line.to_string());
+        if left_right.len() == 2 {
+            let mut lr = DataStore::new(left_right[0].clone(), left_right[1].clone());
+            if let Err(why) = lr.parse() {
+                return Err(format!("line {}: {}", i + 1, why.reason));
+            }
+            // add the left and right to the data
+            pairs.push((left_right[0].clone(), left_right[1].clone()));
+            // remove all the data from left_right
+            left_right.clear();
+        }
+    }
+    if left_right.len() > 0 {
+        return Err("the last packet has no pair".to_string());
+    }
+    Ok(pairs)
+}
+
+/// Checks the packets the way [`Packets::parse`] reads them, but finds every
+/// problem rather than the first: each packet is read on its own, so a bad
+/// packet is reported on its own line, and each group of lines between the
+/// blank lines has to be a pair.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    // the line each group starts on, and how many packets are in it
+    let mut group: Option<(usize, usize)> = None;
+    for (i, line) in s.lines().chain([""]).enumerate() {
+        if line.is_empty() {
+            if let Some((first, packets)) = group.take() {
+                match packets {
+                    1 => lint.at(first, "the packet has no pair"),
+                    2 => (),
+                    n => lint.at(
+                        first,
+                        format!("{} packets before the blank line, not a pair", n),
+                    ),
+                }
+            }
+            continue;
+        }
+        group = Some(group.map_or((i, 1), |(first, packets)| (first, packets + 1)));
+        // read as the left of a pair with an empty right
+        let mut store = DataStore::new(line.to_string(), "[]".to_string());
+        if let Err(why) = store.parse() {
+            let reason = why.reason.strip_prefix("left packet: ");
+            lint.at(i, reason.unwrap_or("the packet is not a list"));
+        }
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n\
+                           [[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n\
+                           [[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]\n";
+
+    #[test]
+    fn test_example() {
+        let packets = Packets::parse(EXAMPLE).unwrap();
+        assert_eq!(packets.pairs().len(), 8);
+        let right: Vec<bool> = packets
+            .pairs()
+            .iter()
+            .map(|(a, b)| compare_packets(a, b) == Ordering::Less)
+            .collect();
+        assert_eq!(right, [true, true, false, true, false, true, false, false]);
+        assert_eq!(packets.right_order_index_sum(), 13);
+
+        let sorted = packets.sorted_with_dividers();
+        assert_eq!(sorted.len(), 18);
+        assert_eq!(sorted[0], "[]");
+        assert_eq!(sorted[9], "[[2]]");
+        assert_eq!(sorted[13], "[[6]]");
+        assert_eq!(packets.decoder_key(), 140);
+    }
+
+    #[test]
+    fn test_explain_a_pair() {
+        let mut trace = Trace::new(true);
+        let order = try_compare_packets_traced("[[1],[2,3,4]]", "[[1],4]", &mut trace).unwrap();
+        assert_eq!(order, Ordering::Less);
+        let explained = trace.render(narrate);
+        assert!(
+            explained.starts_with("- Compare [[1],[2,3,4]] vs [[1],4]\n"),
+            "{}",
+            explained
+        );
+        assert!(explained.contains("- Mixed types; convert right to [4] and retry comparison\n"));
+        assert!(try_compare_packets("[1,2", "[1]").is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use std::fs;
+
+use y2022_day13::shared;
+use y2022_day13::{compare_packets, Packets};
+use y2022_day13::{DAY, YEAR};
+
+use shared::memory::{self, CountingAllocator};
+use shared::trace::Trace;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct AOC {
+    data: Packets,
+}
+
+impl AOC {
+    fn new() -> AOC {
+        AOC {
+            data: Packets::default(),
+        }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        let data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+        self.data = match Packets::parse(&data) {
+            Err(why) => panic!("couldn't parse {}: {}", filename, why),
+            Ok(packets) => packets,
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+    let mut aoc = AOC::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day13", &filename, |s| Packets::parse(s).map(|_| ()));
+        return;
+    }
+
+    let phase = memory::Phase::start("parse");
+    aoc.load_all_data(&filename);
+    phase.finish();
+
+    println!("Data loaded");
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        aoc.data.right_order_index_sum_traced(&mut trace);
+        println!("{}", trace.render(y2022_day13::narrate));
+    }
+
+    let mut packets: Vec<String> = vec![];
+    packets.push("[[2]]".to_string());
+    packets.push("[[6]]".to_string());
+    for (l, r) in aoc.data.pairs() {
+        packets.push(l.clone());
+        packets.push(r.clone());
+        // println!("{:?}", (l, r));
+    }
+
+    println!("Packets loaded");
+
+    let phase = memory::Phase::start("sort");
+    packets.sort_by(|a, b| compare_packets(a, b));
+    phase.finish();
+
+    let mut d1 = 0;
+    let mut d2 = 0;
+    let mut i = 0;
+    for packet in packets.iter() {
+        i += 1;
+        println!("packet: {:?}", packet);
+        if packet.eq("[[2]]") {
+            d1 = i;
+        } else if packet.eq("[[6]]") {
+            d2 = i;
+        }
+    }
+
+    println!("d1: {:?}", d1);
+    println!("d2: {:?}", d2);
+    println!("d1 * d2: {:?}", d1 * d2);
+}
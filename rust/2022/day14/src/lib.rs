@@ -0,0 +1,636 @@
+//! Day 14: Regolith Reservoir.
+//!
+//! The input is paths of rock in a cave. Sand falls from 500,0 one grain at a
+//! time until it comes to rest. [`Cave::parse`] reads the rock, and the cave
+//! is a [`Simulation`] that drops one grain per step. With a floor two below
+//! the lowest rock, the answer is how many grains fall before the source is
+//! blocked.
+
+use std::collections::HashSet;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checkpoint::{self, Checkpoint};
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+use shared::viewport::{Grid, Viewport};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 14;
+/// The furthest a point of rock can be from 0 in x or y. Every point along
+/// each path is kept, and the puzzle's are all within 1000.
+pub const MAX_COORDINATE: i32 = 10_000;
+
+// each line is a list of points "x,y" separated by "->"
+fn parse_paths(s: &str) -> Result<Vec<Vec<(i32, i32)>>, String> {
+    let mut data: Vec<Vec<(i32, i32)>> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let mut line_data: Vec<(i32, i32)> = Vec::new();
+        for point in line.split("->") {
+            let nums: Vec<Result<i32, _>> =
+                point.trim().split(",").map(|n| n.parse::<i32>()).collect();
+            match nums[..] {
+                [Ok(x), Ok(y)] if x.abs() > MAX_COORDINATE || y.abs() > MAX_COORDINATE => {
+                    return Err(format!(
+                        "line {}: {},{} is further out than {}",
+                        i + 1,
+                        x,
+                        y,
+                        MAX_COORDINATE
+                    ))
+                }
+                [Ok(x), Ok(y)] => line_data.push((x, y)),
+                _ => return Err(format!("line {}: {:?} is not a point", i + 1, point.trim())),
+            }
+        }
+        data.push(line_data);
+    }
+    Ok(data)
+}
+
+/// A path of rock, given by the points where it turns.
+#[derive(Debug, Clone)]
+pub struct Shape {
+    pub outline: Vec<(i32, i32)>,
+    points: Vec<(i32, i32)>,
+}
+
+impl Shape {
+    pub fn new(outline: Vec<(i32, i32)>) -> Shape {
+        Shape {
+            outline: outline,
+            points: Vec::new(),
+        }
+    }
+
+    /// Every point of rock along the path.
+    pub fn get_all_points_in_shape(&self) -> Vec<(i32, i32)> {
+        let mut all_points = Vec::new();
+        let mut points_data = self.outline.clone();
+        points_data.reverse();
+        let mut start = points_data.pop().unwrap(); // get the first
+        while points_data.len() > 0 {
+            let next = points_data.pop().unwrap();
+            // println!("{},{} -> {},{}", start.0, start.1, next.0, next.1);
+            let next_points = Shape::get_all_points_between_two_points(start, next).clone();
+            // println!("{:?}", next_points);
+            for (x, y) in next_points {
+                all_points.push((x, y));
+            }
+            start = (next.0, next.1);
+        }
+        all_points
+    }
+
+    fn get_coords(&self) -> ((i32, i32), (i32, i32)) {
+        let mut min_x: i32 = 5000;
+        let mut max_x: i32 = -5000;
+        let mut min_y: i32 = 5000;
+        let mut max_y: i32 = -5000;
+
+        for (x, y) in &self.outline {
+            if x < &min_x {
+                min_x = *x;
+            }
+            if x > &max_x {
+                max_x = *x;
+            }
+            if y < &min_y {
+                min_y = *y;
+            }
+            if y > &max_y {
+                max_y = *y;
+            }
+        }
+
+        // for drawing the shape
+        let coords = ((min_x, min_y), (max_x, max_y));
+
+        // println!("{:?}", coords);
+
+        coords
+    }
+
+    fn get_all_points_between_two_points(p1: (i32, i32), p2: (i32, i32)) -> Vec<(i32, i32)> {
+        let mut points: Vec<(i32, i32)> = Vec::new();
+
+        if p1.0 == p2.0 {
+            if p2.1 > p1.1 {
+                for y in p1.1..=p2.1 {
+                    points.push((p1.0, y));
+                }
+            } else {
+                for y in p2.1..=p1.1 {
+                    points.push((p1.0, y));
+                }
+            }
+        } else if p1.1 == p2.1 {
+            if p2.0 > p1.0 {
+                for x in p1.0..=p2.0 {
+                    points.push((x, p1.1));
+                }
+            } else {
+                for x in p2.0..=p1.0 {
+                    points.push((x, p1.1));
+                }
+            }
+        }
+
+        points
+    }
+}
+
+/// The rock in the cave and the sand that has come to rest in it. The sand
+/// falls from 500,0.
+pub struct Cave {
+    shapes: Vec<Shape>,
+    coords: ((i32, i32), (i32, i32)),
+    sand: Vec<(i32, i32)>,
+    points: Vec<(i32, i32)>,
+    blocks: HashSet<(i32, i32)>, // the points as a set, for quick lookups
+    sand_blocks: HashSet<(i32, i32)>, // the sand as a set, for quick lookups
+}
+
+impl Cave {
+    pub fn new(shapes: Vec<Shape>) -> Cave {
+        let mut cave = Cave {
+            shapes: shapes.clone(),
+            coords: ((0, 0), (0, 0)),
+            sand: vec![] as Vec<(i32, i32)>,
+            points: vec![] as Vec<(i32, i32)>,
+            blocks: HashSet::new(),
+            sand_blocks: HashSet::new(),
+        };
+
+        cave.update_points();
+
+        cave
+    }
+
+    /// Reads one path of rock per line, as points "x,y" separated by "->".
+    /// The cave has no floor until [`Cave::add_floor`] is called. Fails on a
+    /// point that isn't two numbers, or is further out than
+    /// [`MAX_COORDINATE`].
+    pub fn parse(s: &str) -> Result<Cave, String> {
+        let shapes: Vec<Shape> = parse_paths(s)?.into_iter().map(Shape::new).collect();
+        Ok(Cave::new(shapes))
+    }
+
+    /// Adds the floor two below the lowest rock, wide enough that the sand
+    /// can't fall off the end of it.
+    pub fn add_floor(&mut self) {
+        let floor_shape = Shape::new(vec![
+            (500 - self.coords.1 .1 - 30, self.coords.1 .1 + 2),
+            (500 + self.coords.1 .1 + 30, self.coords.1 .1 + 2),
+        ]);
+        self.shapes.push(floor_shape);
+        self.update_points();
+    }
+
+    /// The smallest and largest x and y of the rock.
+    pub fn coords(&self) -> ((i32, i32), (i32, i32)) {
+        self.coords
+    }
+
+    /// Where each grain of sand came to rest, in order.
+    pub fn sand(&self) -> &[(i32, i32)] {
+        &self.sand
+    }
+
+    /// Drops sand until it blocks the source, and returns how many grains
+    /// came to rest. The cave needs a floor, or the sand falls forever.
+    pub fn fill(&mut self) -> usize {
+        self.fill_traced(&mut Trace::default())
+    }
+
+    /// [`Cave::fill`], recording where each grain comes to rest and what it
+    /// lands on, then how many there are, in `trace` (see [`narrate`]).
+    pub fn fill_traced(&mut self, trace: &mut Trace) -> usize {
+        while !self.is_done() {
+            self.step();
+            if trace.is_enabled() {
+                let (x, y) = self.sand[self.sand.len() - 1];
+                let on = if self.blocks.contains(&(x, y + 1)) { "rock" } else { "sand" };
+                trace.event(
+                    "rest",
+                    &[("grain", &self.sand.len()), ("at", &format!("{},{}", x, y)), ("on", &on)],
+                );
+            }
+        }
+        trace.event("full", &[("grains", &self.sand.len())]);
+        self.sand.len()
+    }
+
+    // call this after changing the shapes
+    fn update_points(&mut self) {
+        self.coords = self.get_total_size_of_all_shapes();
+        self.points = self.get_all_points_in_cave();
+        self.blocks = self.points.iter().cloned().collect();
+    }
+
+    fn add_sand(&mut self, sand_point: (i32, i32)) {
+        self.sand.push(sand_point);
+        self.sand_blocks.insert(sand_point);
+    }
+
+    /// Whether sand has come to rest at the source, so no more can fall.
+    pub fn is_blocked(&self) -> bool {
+        self.sand_blocks.contains(&(500, 0))
+    }
+
+    /// Drops a single grain of sand from the source and lets it come to rest.
+    pub fn drop_grain(&mut self) {
+        let mut grain = Grain { x: 500, y: 0 };
+
+        loop {
+            // if it can move down, move down
+
+            let points_to_check = vec![
+                (grain.x, grain.y + 1),
+                (grain.x - 1, grain.y + 1),
+                (grain.x + 1, grain.y + 1),
+            ];
+
+            let mut moved_down = false;
+
+            for point in points_to_check {
+                if !self.blocks.contains(&(point.0, point.1))
+                    && !self.sand_blocks.contains(&(point.0, point.1))
+                {
+                    grain.move_to(point.0, point.1);
+                    moved_down = true;
+                    // println!("Moved down to {},{}", grain.x, grain.y);
+                    break;
+                }
+            }
+
+            if moved_down {
+                continue;
+            }
+
+            // add to sand
+            self.add_sand((grain.x, grain.y));
+
+            // self.draw_state();
+            break;
+        }
+    }
+
+    fn get_all_points_in_cave(&mut self) -> Vec<(i32, i32)> {
+        let mut all_points: Vec<(i32, i32)> = vec![];
+
+        for shape in &self.shapes {
+            let points = shape.get_all_points_in_shape();
+            for p in points {
+                all_points.push(p);
+            }
+        }
+
+        all_points
+    }
+
+    fn get_cave_bounds(&self) -> ((i32, i32), (i32, i32)) {
+        let min_x: i32 = self.coords.0 .0 - 1;
+        let max_x: i32 = self.coords.1 .0 + 1;
+        let min_y: i32 = 0;
+        let max_y: i32 = self.coords.1 .1 + 1;
+
+        ((min_x, min_y), (max_x, max_y))
+    }
+
+    fn get_total_size_of_all_shapes(&self) -> ((i32, i32), (i32, i32)) {
+        let mut min_x: i32 = 5000;
+        let mut max_x: i32 = -5000;
+        let mut min_y: i32 = 5000;
+        let mut max_y: i32 = -5000;
+
+        for shape in &self.shapes {
+            let coords = shape.get_coords();
+            let min = coords.0;
+            let max = coords.1;
+            if min.0 < min_x {
+                min_x = min.0;
+            }
+            if max.0 > max_x {
+                max_x = max.0;
+            }
+            if min.1 < min_y {
+                min_y = min.1;
+            }
+            if max.1 > max_y {
+                max_y = max.1;
+            }
+        }
+
+        ((min_x, min_y), (max_x, max_y))
+    }
+
+    /// The rock (#) and the sand (o) with the source (+), and the x and y
+    /// down the sides. With the viewport flags, only the window they give
+    /// (see shared/viewport.rs).
+    pub fn render_state(&self) -> String {
+        if let Some(view) = Viewport::from_args() {
+            return view.render(self);
+        }
+        let coords = self.coords;
+
+        let mut blocks: HashSet<(i32, i32)> = HashSet::new();
+        for p in &self.points {
+            blocks.insert(*p);
+        }
+        let mut sand_blocks: HashSet<(i32, i32)> = HashSet::new();
+        for p in &self.sand {
+            sand_blocks.insert(*p);
+        }
+
+        let min_x: i32 = coords.0 .0 - 1;
+        let max_x: i32 = coords.1 .0 + 1;
+        let min_y: i32 = 0;
+        let max_y: i32 = coords.1 .1 + 1;
+
+        let mut s = "".to_string();
+
+        for y in min_y - 3..=max_y {
+            if y < min_y {
+                s.push_str("   ");
+            } else {
+                s.push_str(format!("{:03}", y).as_str());
+            }
+            for x in min_x..=max_x {
+                if x == 500 && y == 0 {
+                    s.push_str("+");
+                    continue;
+                } else if y < min_y {
+                    if y == min_y - 3 {
+                        s.push_str(format!("{}", x.to_string().chars().nth(0).unwrap()).as_str());
+                    } else if y == min_y - 2 {
+                        s.push_str(format!("{}", x.to_string().chars().nth(1).unwrap()).as_str());
+                    } else if y == min_y - 1 {
+                        s.push_str(format!("{}", x.to_string().chars().nth(2).unwrap()).as_str());
+                    }
+                } else {
+                    if blocks.contains(&(x, y)) {
+                        s.push_str("#");
+                        continue;
+                    } else if sand_blocks.contains(&(x, y)) {
+                        s.push_str("o");
+                        continue;
+                    }
+                    s.push_str(".");
+                }
+            }
+            s.push_str("\n");
+        }
+
+        s
+    }
+}
+
+// the cave as the viewport sees it, following the last grain of sand
+impl Grid for Cave {
+    const PRIORITY: &'static str = "+o#.";
+
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        let ((min_x, min_y), (max_x, max_y)) = self.get_cave_bounds();
+        ((min_x as i64, min_y as i64), (max_x as i64, max_y as i64))
+    }
+
+    fn glyph(&self, x: i64, y: i64) -> char {
+        let p = (x as i32, y as i32);
+        if p == (500, 0) {
+            '+'
+        } else if self.blocks.contains(&p) {
+            '#'
+        } else if self.sand_blocks.contains(&p) {
+            'o'
+        } else {
+            '.'
+        }
+    }
+
+    fn focus(&self) -> Option<(i64, i64)> {
+        self.sand.last().map(|(x, y)| (*x as i64, *y as i64))
+    }
+
+    fn marks(&self) -> Vec<((i64, i64), char)> {
+        vec![((500, 0), '+')]
+    }
+}
+
+/// One line of the explanation for an event recorded by [`Cave::fill_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "rest" => format!(
+            "Grain {} comes to rest at {}, on {}.",
+            event.get("grain"),
+            event.get("at"),
+            event.get("on")
+        ),
+        "full" => format!(
+            "The source is blocked once {} grains of sand have come to rest.",
+            event.get("grains")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+// each step is one grain of sand, and it's done when the source is blocked
+impl Simulation for Cave {
+    type State = (usize, Option<(i32, i32)>); // grains of sand, where the last one stopped
+
+    fn step(&mut self) {
+        self.drop_grain();
+    }
+
+    fn is_done(&self) -> bool {
+        self.is_blocked()
+    }
+
+    fn state(&self) -> (usize, Option<(i32, i32)>) {
+        (self.sand.len(), self.sand.last().cloned())
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.sand.len() as u64
+    }
+}
+
+// a checkpoint is the outline of each shape (including the floor) and
+// where every grain of sand has come to rest so far
+impl Checkpoint for Cave {
+    const KIND: &'static str = "cave";
+
+    fn to_checkpoint(&self) -> String {
+        let mut s = String::new();
+        for shape in &self.shapes {
+            s.push_str(&format!("shape {}\n", points_to_string(&shape.outline)));
+        }
+        s.push_str(&format!("sand {}\n", points_to_string(&self.sand)));
+        s
+    }
+
+    fn from_checkpoint(data: &str) -> Result<Cave, String> {
+        let mut shapes: Vec<Shape> = vec![];
+        for outline in checkpoint::values(data, "shape") {
+            shapes.push(Shape::new(points_from_string(outline)?));
+        }
+        let mut cave = Cave::new(shapes);
+        for p in points_from_string(checkpoint::value(data, "sand")?)? {
+            cave.add_sand(p);
+        }
+        Ok(cave)
+    }
+}
+
+// a recording starts with a checkpoint, and each step is where that grain of
+// sand came to rest, "x,y"
+impl Record for Cave {
+    const KIND: &'static str = "cave";
+
+    fn record_start(&self) -> String {
+        self.to_checkpoint()
+    }
+
+    fn record_step(&self) -> String {
+        match self.sand.last() {
+            Some((x, y)) => format!("{},{}", x, y),
+            None => "".to_string(),
+        }
+    }
+
+    fn replay_start(start: &str) -> Result<Cave, String> {
+        Cave::from_checkpoint(start)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        match points_from_string(step)?[..] {
+            [p] => self.add_sand(p),
+            _ => return Err(format!("{:?} is not a grain of sand", step)),
+        }
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        format!("{}Sand: {}", self.render_state(), self.sand.len())
+    }
+}
+
+fn points_to_string(points: &Vec<(i32, i32)>) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn points_from_string(s: &str) -> Result<Vec<(i32, i32)>, String> {
+    let mut points: Vec<(i32, i32)> = vec![];
+    for p in s.split_whitespace() {
+        match p.split_once(',') {
+            Some((x, y)) => points.push((
+                checkpoint::parse(x, "point")?,
+                checkpoint::parse(y, "point")?,
+            )),
+            None => return Err(format!("checkpoint has a bad point: {:?}", p)),
+        }
+    }
+    Ok(points)
+}
+
+#[derive(Debug, Clone)]
+struct GrainOfSand {
+    x: i32,
+    y: i32,
+}
+
+impl GrainOfSand {
+    fn new(x: i32, y: i32) -> GrainOfSand {
+        GrainOfSand { x, y }
+    }
+
+    fn get_coords(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    fn move_down_left(&mut self) {
+        self.y += 1;
+        self.x -= 1;
+    }
+
+    fn move_down_right(&mut self) {
+        self.y += 1;
+        self.x -= 1;
+    }
+}
+
+type Grain = GrainOfSand;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::fuzz::{fuzz_one, FuzzOutcome};
+
+    const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+
+    #[test]
+    fn test_example() {
+        let mut cave = Cave::parse(EXAMPLE).unwrap();
+        assert_eq!(cave.coords(), ((494, 4), (503, 9)));
+        cave.add_floor();
+        assert_eq!(cave.fill(), 93);
+    }
+
+    #[test]
+    fn test_explain_where_the_sand_rests() {
+        let mut cave = Cave::parse(EXAMPLE).unwrap();
+        cave.add_floor();
+        let mut trace = Trace::new(true);
+        assert_eq!(cave.fill_traced(&mut trace), 93);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "Grain 1 comes to rest at 500,8, on rock.\n\
+             Grain 2 comes to rest at 499,8, on rock.\n\
+             Grain 3 comes to rest at 501,8, on rock.\n\
+             Grain 4 comes to rest at 500,7, on sand.\n\
+             Grain 5 comes to rest at 498,8, on rock.\n"
+        ));
+        assert!(explained.ends_with(
+            "Grain 93 comes to rest at 500,0, on sand.\n\
+             The source is blocked once 93 grains of sand have come to rest.\n"
+        ));
+    }
+
+    #[test]
+    fn test_resume_gives_the_same_answer() {
+        let mut cave = Cave::parse(EXAMPLE).unwrap();
+        cave.add_floor();
+        for _ in 0..40 {
+            cave.step();
+        }
+        let mut resumed = Cave::from_checkpoint(&cave.to_checkpoint()).unwrap();
+        assert_eq!(resumed.sand(), cave.sand());
+        assert_eq!(resumed.fill(), 93);
+    }
+
+    #[test]
+    fn test_far_points_are_rejected() {
+        // these used to run out of memory rather than give an error
+        let parse = |s: &str| Cave::parse(s).map(|_| ());
+        for input in ["0,0 -> 0,2147483647\n", "-20000,5 -> 5,5\n"] {
+            match fuzz_one(input.as_bytes(), &parse) {
+                FuzzOutcome::Rejected(why) => assert!(why.contains("further out"), "{}", why),
+                outcome => panic!("{:?} gave {:?}", input, outcome),
+            }
+        }
+        assert_eq!(fuzz_one(b"0,0 -> 0,10000\n", &parse), FuzzOutcome::Parsed);
+    }
+}
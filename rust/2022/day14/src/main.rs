@@ -0,0 +1,117 @@
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use y2022_day14::shared;
+use y2022_day14::Cave;
+use y2022_day14::{DAY, YEAR};
+
+use shared::checkpoint::Checkpointer;
+use shared::replay::Recorder;
+use shared::simulation::{self, CheckpointObserver, ProgressObserver, Runner};
+use shared::trace::Trace;
+
+struct AOC {
+    data: String,
+}
+
+impl AOC {
+    fn new() -> AOC {
+        AOC {
+            data: String::new(),
+        }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        self.data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+// clears the terminal and draws the rock and the sand
+fn draw_state(cave: &Cave) {
+    print!("\x1B[2J\x1B[1;1H");
+    println!("{:?}", cave.coords());
+    println!("{}", cave.render_state());
+    thread::sleep(Duration::from_millis(50));
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, Some(1)));
+    let mut aoc = AOC::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day14", &filename, |s| Cave::parse(s).map(|_| ()));
+        return;
+    }
+
+    let mut checkpointer = Checkpointer::from_args("./day14.checkpoint");
+
+    let mut cave = match checkpointer.resume::<Cave>() {
+        Some(Ok(cave)) => {
+            println!(
+                "Resuming from {} with {} grains of sand\n",
+                checkpointer.path(),
+                cave.sand().len()
+            );
+            cave
+        }
+        Some(Err(why)) => panic!("couldn't resume: {}", why),
+        None => {
+            aoc.load_all_data(&filename);
+
+            let mut cave = match Cave::parse(&aoc.data) {
+                Err(why) => panic!("couldn't parse {}: {}", filename, why),
+                Ok(cave) => cave,
+            };
+
+            println!("Cave: {:?}", cave.coords());
+
+            cave.add_floor();
+            cave
+        }
+    };
+
+    draw_state(&cave);
+
+    let runner = Runner::new()
+        .observe(ProgressObserver::new("Sand"))
+        .observe(CheckpointObserver::new(&mut checkpointer))
+        .observe(Recorder::from_args());
+    simulation::run_from_args(&mut cave, runner);
+
+    println!("Total: {}", cave.sand().len());
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        // the simulation above has already run, so fill the cave again
+        aoc.load_all_data(&filename);
+        if let Ok(mut cave) = Cave::parse(&aoc.data) {
+            cave.add_floor();
+            cave.fill_traced(&mut trace);
+            println!("\n{}", trace.render(y2022_day14::narrate));
+        }
+    }
+}
@@ -0,0 +1,655 @@
+//! Day 15: Beacon Exclusion Zone.
+//!
+//! The input is where each sensor is and the closest beacon to it. A sensor
+//! covers every point no further away than its beacon, and the distress
+//! beacon is the one point in the search area that no sensor covers.
+//! [`Sensors::parse`] reads the sensors and [`Sensors::find_distress_beacon`]
+//! finds it.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+// use std::thread;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::checkpoint::{self, Checkpoint, Checkpointer};
+use shared::progress::Progress;
+use shared::trace::{Event, Trace};
+use shared::viewport::Grid;
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 15;
+
+// example: Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+fn parse_sensor(line: &str) -> Result<Sensor, String> {
+    let (sensor_str, beacon_str) = match line
+        .strip_prefix("Sensor at ")
+        .and_then(|l| l.split_once(": closest beacon is at "))
+    {
+        Some(parts) => parts,
+        None => return Err(format!("{:?} is not a sensor", line)),
+    };
+    Ok(Sensor {
+        location: parse_point(sensor_str)?,
+        beacon: parse_point(beacon_str)?,
+    })
+}
+
+// example: x=2, y=18
+fn parse_point(s: &str) -> Result<Point, String> {
+    let (x_str, y_str) = match s.split_once(", ") {
+        Some(parts) => parts,
+        None => return Err(format!("{:?} is not a point", s)),
+    };
+    let x = x_str.strip_prefix("x=").and_then(|x| x.trim().parse::<i64>().ok());
+    let y = y_str.strip_prefix("y=").and_then(|y| y.trim().parse::<i64>().ok());
+    match (x, y) {
+        (Some(x), Some(y)) => Ok(Point { x: x, y: y }),
+        _ => Err(format!("{:?} is not a point", s)),
+    }
+}
+
+/// The sensors in the input, in order.
+#[derive(Debug, Clone)]
+pub struct Sensors {
+    sensors: Vec<Sensor>,
+}
+
+impl Sensors {
+    /// Reads one sensor per line, like
+    /// `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`. Fails on a line
+    /// that isn't a sensor.
+    pub fn parse(s: &str) -> Result<Sensors, String> {
+        Ok(Sensors {
+            sensors: parse_sensors(s)?,
+        })
+    }
+
+    pub fn sensors(&self) -> &[Sensor] {
+        &self.sensors
+    }
+
+    /// How far each sensor can see, by where it is.
+    pub fn sensor_data(&self) -> HashMap<Point, i64> {
+        self.sensors
+            .iter()
+            .map(|s| (s.location, s.distance()))
+            .collect()
+    }
+
+    /// The first point, by row and then column, with x and y between 0 and
+    /// `max` that none of the sensors cover, or None if they cover them all.
+    pub fn find_distress_beacon(&self, max: i64) -> Option<Point> {
+        self.find_distress_beacon_traced(max, &mut Trace::default())
+    }
+
+    /// [`Sensors::find_distress_beacon`], recording how far each sensor sees,
+    /// then where the beacon is and its tuning frequency, in `trace` (see
+    /// [`narrate`]).
+    pub fn find_distress_beacon_traced(&self, max: i64, trace: &mut Trace) -> Option<Point> {
+        for s in &self.sensors {
+            trace.event(
+                "sensor",
+                &[
+                    ("at", &format!("{},{}", s.location.x, s.location.y)),
+                    ("beacon", &format!("{},{}", s.beacon.x, s.beacon.y)),
+                    ("distance", &s.distance()),
+                ],
+            );
+        }
+        let sensor_data = self.sensor_data();
+        for y in 0..=max {
+            if let Some(point) = get_points_not_covered_on_row(&sensor_data, y, 0, max).first() {
+                trace.event(
+                    "found",
+                    &[
+                        ("at", &format!("{},{}", point.x, point.y)),
+                        ("max", &max),
+                        ("frequency", &get_tuning_frequency(point.x, point.y)),
+                    ],
+                );
+                return Some(*point);
+            }
+        }
+        trace.event("covered", &[("max", &max)]);
+        None
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Sensors::find_distress_beacon_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "sensor" => format!(
+            "The sensor at {} has its closest beacon at {}, so there are no others within {}.",
+            event.get("at"),
+            event.get("beacon"),
+            event.get("distance")
+        ),
+        "found" => format!(
+            "The first point from 0 to {} that no sensor covers is {}, \
+             so the tuning frequency is {}.",
+            event.get("max"),
+            event.get("at"),
+            event.get("frequency")
+        ),
+        "covered" => format!("The sensors cover every point from 0 to {}.", event.get("max")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+fn parse_sensors(s: &str) -> Result<Vec<Sensor>, String> {
+    let mut sensors: Vec<Sensor> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        match parse_sensor(line) {
+            Ok(sensor) => sensors.push(sensor),
+            Err(why) => return Err(format!("line {}: {}", i + 1, why)),
+        }
+    }
+    Ok(sensors)
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A sensor and the closest beacon to it.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Sensor {
+    pub location: Point,
+    pub beacon: Point,
+}
+
+impl Sensor {
+    /// The manhattan distance to the beacon. There are no other beacons
+    /// this close to the sensor.
+    pub fn distance(&self) -> i64 {
+        let x = self.location.x - self.beacon.x;
+        let y = self.location.y - self.beacon.y;
+        x.abs() + y.abs()
+    }
+
+    /// Every point no further from the sensor than its beacon. This is slow
+    /// for sensors that can see far.
+    pub fn get_points_within_manhattan_distance(&self) -> HashSet<Point> {
+        let mut points: HashSet<Point> = HashSet::new();
+        let d = self.distance();
+        for y in -d..=d {
+            for x in -d..=d {
+                if x.abs() + y.abs() <= d {
+                    points.insert(Point {
+                        x: self.location.x + x,
+                        y: self.location.y + y,
+                    });
+                }
+            }
+        }
+        points
+    }
+
+    /// The number of points on row y the sensor covers that can't have a
+    /// beacon, and the range of x it covers, if any.
+    pub fn get_num_of_points_on_y(&self, y: i64) -> (i64, Option<(i64, i64)>) {
+        let d = self.distance();
+        let dist_to_y = (self.location.y - y).abs();
+
+        if y >= self.location.y - d && y <= self.location.y + d {
+            // println!("y is in range");
+        } else {
+            // println!("y is not in range");
+            return (0, None);
+        }
+        // this is distance times 2 from the sensor + 1 for the sensor point
+        // then take away 1 for the sensor point
+        // then every 1 further away from the beacon is 2 less
+        let mut total: i64;
+        let mut x_from = self.location.x - d;
+        let mut x_to = self.location.x + d;
+        if y == self.location.y {
+            total = d * 2; // no + 1 here for the sensor point
+        } else {
+            total = d * 2 - (2 * dist_to_y) + 1; // e.g. if d = 5 and dist_to_y = 2, then 5 * 2 - (2 * 2) + 1 = 9
+            x_from += dist_to_y;
+            x_to -= dist_to_y;
+        }
+        if self.beacon.y == y {
+            total -= 1;
+        }
+        // println!("total: {}", total);
+        (total, Some((x_from, x_to)))
+    }
+}
+
+/// How far the scan has got, and what it has found so far. This is what gets
+/// saved to a checkpoint so a long scan can be resumed.
+#[derive(Debug, Clone)]
+pub struct ScanCursor {
+    pub min_y: i64,
+    pub max_y: i64,
+    pub min_x: i64,
+    pub max_x: i64,
+    pub next_y: i64,
+    pub sensor_count: usize, // to check a checkpoint is being resumed with the same input
+    pub found: Vec<Point>,
+}
+
+impl ScanCursor {
+    pub fn new(min_y: i64, max_y: i64, min_x: i64, max_x: i64, sensor_count: usize) -> ScanCursor {
+        ScanCursor {
+            min_y,
+            max_y,
+            min_x,
+            max_x,
+            next_y: min_y,
+            sensor_count,
+            found: vec![],
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_y > self.max_y
+    }
+}
+
+impl Checkpoint for ScanCursor {
+    const KIND: &'static str = "scan";
+
+    fn to_checkpoint(&self) -> String {
+        let found: Vec<String> = self
+            .found
+            .iter()
+            .map(|p| format!("{},{}", p.x, p.y))
+            .collect();
+        format!(
+            "bounds {} {} {} {}\nnext_y {}\nsensors {}\nfound {}\n",
+            self.min_y,
+            self.max_y,
+            self.min_x,
+            self.max_x,
+            self.next_y,
+            self.sensor_count,
+            found.join(" ")
+        )
+    }
+
+    fn from_checkpoint(data: &str) -> Result<ScanCursor, String> {
+        let bounds: Vec<i64> = checkpoint::parse_list(checkpoint::value(data, "bounds")?, "bound")?;
+        if bounds.len() != 4 {
+            return Err(format!("checkpoint has bad bounds: {:?}", bounds));
+        }
+        let mut cursor = ScanCursor::new(
+            bounds[0],
+            bounds[1],
+            bounds[2],
+            bounds[3],
+            checkpoint::parse(checkpoint::value(data, "sensors")?, "sensor count")?,
+        );
+        cursor.next_y = checkpoint::parse(checkpoint::value(data, "next_y")?, "next y")?;
+        for p in checkpoint::value(data, "found")?.split_whitespace() {
+            match p.split_once(',') {
+                Some((x, y)) => cursor.found.push(Point {
+                    x: checkpoint::parse(x, "point")?,
+                    y: checkpoint::parse(y, "point")?,
+                }),
+                None => return Err(format!("checkpoint has a bad point: {:?}", p)),
+            }
+        }
+        Ok(cursor)
+    }
+}
+
+/// The points within the cursor's bounds that none of the sensors cover,
+/// scanning from the cursor's next row to its last. The cursor keeps what has
+/// been found, and the checkpointer saves it as the scan goes.
+pub fn get_points_not_covered_by_sensors(
+    sensors: &Vec<Sensor>,
+    cursor: &mut ScanCursor,
+    checkpointer: &mut Checkpointer,
+) -> HashSet<Point> {
+    let min_y = cursor.min_y;
+    let max_y = cursor.max_y;
+    let min_x_to_find = cursor.min_x;
+    let max_x_to_find = cursor.max_x;
+
+    let mut sensor_data: HashMap<Point, i64> = HashMap::new();
+    let mut beacon_data: HashMap<Point, Point> = HashMap::new();
+
+    // println!("Getting sensor data... ({} sensors)", sensors.len());
+
+    // avoid having to do multiple loops
+    for sensor in sensors {
+        let d = sensor.distance();
+        sensor_data.insert(sensor.location, d);
+        beacon_data.insert(sensor.location, sensor.beacon);
+    }
+
+    let mut progress = Progress::new("Rows", Some((max_y - min_y + 1) as u64));
+
+    while !cursor.is_done() {
+        let y = cursor.next_y;
+        progress.set((y - min_y) as u64);
+        for point in get_points_not_covered_on_row(&sensor_data, y, min_x_to_find, max_x_to_find) {
+            cursor.found.push(point);
+        }
+
+        // given ranges... work out what ranges are still left within min_x and max_x
+
+        cursor.next_y += 1;
+        checkpointer.maybe_save(cursor);
+    }
+    progress.set((max_y - min_y + 1) as u64);
+    progress.finish();
+
+    cursor.found.iter().cloned().collect()
+}
+
+/// The points on row `y`, between `min_x` and `max_x`, that none of the
+/// sensors cover. `sensor_data` is each sensor's location and the distance it
+/// covers, as [`Sensors::sensor_data`] gives it.
+pub fn get_points_not_covered_on_row(
+    sensor_data: &HashMap<Point, i64>,
+    y: i64,
+    min_x: i64,
+    max_x: i64,
+) -> Vec<Point> {
+    let mut ranges: Vec<(i64, i64)> = vec![];
+    for (sensor, d) in sensor_data {
+        // work out the points on y that are covered by this sensor
+        if sensor.y >= y - *d && sensor.y <= y + *d {
+            // println!("\ny({}) is in range", y);
+            // now work out how far away from the sensor we are
+            let dist_to_sensor = (sensor.y - y).abs();
+            let mut x_min = sensor.x - d + dist_to_sensor;
+            let mut x_max = sensor.x + d - dist_to_sensor;
+            // println!("{:?} - {}", sensor, d);
+            // println!("x_min: {}, x_max: {}", x_min, x_max);
+            if x_min < min_x {
+                x_min = min_x;
+            }
+            if x_max > max_x {
+                x_max = max_x;
+            }
+            ranges.push((x_min, x_max));
+        }
+    }
+    let mut points: Vec<Point> = vec![];
+    if let Some(uncovered) = get_uncovered_range(ranges, min_x, max_x) {
+        for r in uncovered {
+            for x in r.0..=r.1 {
+                points.push(Point { x, y });
+            }
+        }
+    }
+    points
+}
+
+// the gaps in the ranges between min_x and max_x, or None if there aren't any
+fn get_uncovered_range(ranges: Vec<(i64, i64)>, min_x: i64, max_x: i64) -> Option<Vec<(i64, i64)>> {
+    let mut gaps = vec![];
+    // the first x that isn't known to be covered yet
+    let mut next_x = min_x;
+    for r in consolidate_number_ranges(ranges, min_x, max_x) {
+        if r.0 > next_x {
+            gaps.push((next_x, r.0 - 1));
+        }
+        next_x = r.1 + 1;
+    }
+    if next_x <= max_x {
+        gaps.push((next_x, max_x));
+    }
+
+    if gaps.len() == 0 {
+        return None;
+    }
+    Some(gaps)
+}
+
+// cuts the ranges down to min_x..max_x and joins any that overlap or touch,
+// giving them back sorted by x
+fn consolidate_number_ranges(ranges: Vec<(i64, i64)>, min_x: i64, max_x: i64) -> Vec<(i64, i64)> {
+    let mut from_to_data: Vec<(i64, i64)> = ranges
+        .into_iter()
+        .map(|r| (r.0.max(min_x), r.1.min(max_x)))
+        .filter(|r| r.0 <= r.1)
+        .collect();
+    // sort by x_min
+    from_to_data.sort();
+
+    let mut ranges_to_return: Vec<(i64, i64)> = vec![];
+    for r in from_to_data {
+        match ranges_to_return.last_mut() {
+            // if they overlap or are 1 apart, join them
+            Some(last) if number_ranges_overlap(last, &r) || r.0 == last.1 + 1 => {
+                *last = join_number_ranges(last, &r);
+            }
+            _ => ranges_to_return.push(r),
+        }
+    }
+    ranges_to_return
+}
+
+fn number_ranges_overlap(r1: &(i64, i64), r2: &(i64, i64)) -> bool {
+    let (r1_min, r1_max) = r1;
+    let (r2_min, r2_max) = r2;
+    if r1_min <= r2_min && r1_max >= r2_min {
+        true
+    } else if r2_min <= r1_min && r2_max >= r1_min {
+        true
+    } else {
+        false
+    }
+}
+
+fn join_number_ranges(r1: &(i64, i64), r2: &(i64, i64)) -> (i64, i64) {
+    let (r1_min, r1_max) = r1;
+    let (r2_min, r2_max) = r2;
+    let min = if r1_min < r2_min { r1_min } else { r2_min };
+    let max = if r1_max > r2_max { r1_max } else { r2_max };
+    (*min, *max)
+}
+
+/// The whole map for drawing through a viewport (see shared/viewport.rs):
+/// the sensors (S), their beacons (B), the points a sensor covers (#) and the
+/// distress beacon (*) once it has been found, which `--follow` keeps in view.
+pub struct SensorMap<'a> {
+    sensors: &'a [Sensor],
+    distress_beacon: Option<Point>,
+}
+
+impl SensorMap<'_> {
+    pub fn new(sensors: &[Sensor], distress_beacon: Option<Point>) -> SensorMap<'_> {
+        SensorMap {
+            sensors,
+            distress_beacon,
+        }
+    }
+}
+
+impl Grid for SensorMap<'_> {
+    const PRIORITY: &'static str = "*SB#.";
+
+    // as far as any sensor can see
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        let reach = |s: &Sensor, dx: i64, dy: i64| {
+            (s.location.x + dx * s.distance(), s.location.y + dy * s.distance())
+        };
+        let min_x = self.sensors.iter().map(|s| reach(s, -1, 0).0).min().unwrap_or(0);
+        let max_x = self.sensors.iter().map(|s| reach(s, 1, 0).0).max().unwrap_or(0);
+        let min_y = self.sensors.iter().map(|s| reach(s, 0, -1).1).min().unwrap_or(0);
+        let max_y = self.sensors.iter().map(|s| reach(s, 0, 1).1).max().unwrap_or(0);
+        ((min_x, min_y), (max_x, max_y))
+    }
+
+    fn glyph(&self, x: i64, y: i64) -> char {
+        let p = Point { x, y };
+        if self.distress_beacon == Some(p) {
+            return '*';
+        }
+        for s in self.sensors {
+            if s.location == p {
+                return 'S';
+            } else if s.beacon == p {
+                return 'B';
+            }
+        }
+        let covered = self
+            .sensors
+            .iter()
+            .any(|s| (s.location.x - x).abs() + (s.location.y - y).abs() <= s.distance());
+        if covered {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    fn focus(&self) -> Option<(i64, i64)> {
+        self.distress_beacon.map(|p| (p.x, p.y))
+    }
+
+    // single points that sampling a zoomed out map would miss
+    fn marks(&self) -> Vec<((i64, i64), char)> {
+        let mut marks: Vec<((i64, i64), char)> = Vec::new();
+        for s in self.sensors {
+            marks.push(((s.location.x, s.location.y), 'S'));
+            marks.push(((s.beacon.x, s.beacon.y), 'B'));
+        }
+        if let Some(p) = self.distress_beacon {
+            marks.push(((p.x, p.y), '*'));
+        }
+        marks
+    }
+}
+
+/// The tuning frequency of the distress beacon at x, y.
+pub fn get_tuning_frequency(x: i64, y: i64) -> i128 {
+    // widened first, x * 4000000 can be too big for an i64 on a large map
+    let what = "day15 tuning frequency";
+    checked::add(what, checked::mul(what, x as i128, 4_000_000), y as i128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::property;
+    use shared::rng::Rng;
+
+    const MAX: i64 = 20;
+
+    fn random_sensors(rng: &mut Rng) -> Vec<Sensor> {
+        let mut sensors = vec![];
+        for _ in 0..rng.range(1, 6) {
+            let location = Point {
+                x: rng.range(0, MAX),
+                y: rng.range(0, MAX),
+            };
+            // there is only ever one sensor in a place
+            if sensors.iter().any(|s: &Sensor| s.location == location) {
+                continue;
+            }
+            let beacon = Point {
+                x: location.x + rng.range(-6, 6),
+                y: location.y + rng.range(-6, 6),
+            };
+            sensors.push(Sensor { location, beacon });
+        }
+        sensors
+    }
+
+    // every point in the area that isn't in any sensor's diamond, found by
+    // listing every point each sensor covers
+    fn not_covered_brute_force(sensors: &Vec<Sensor>) -> Vec<Point> {
+        let mut covered: HashSet<Point> = HashSet::new();
+        for sensor in sensors {
+            covered.extend(sensor.get_points_within_manhattan_distance());
+        }
+        let mut points = vec![];
+        for y in 0..=MAX {
+            for x in 0..=MAX {
+                if !covered.contains(&Point { x, y }) {
+                    points.push(Point { x, y });
+                }
+            }
+        }
+        points
+    }
+
+    const EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn test_example() {
+        let sensors = Sensors::parse(EXAMPLE).unwrap();
+        let beacon = sensors.find_distress_beacon(20).unwrap();
+        assert_eq!(beacon, Point { x: 14, y: 11 });
+        assert_eq!(get_tuning_frequency(beacon.x, beacon.y), 56000011);
+
+        let mut trace = Trace::new(true);
+        sensors.find_distress_beacon_traced(MAX, &mut trace);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "The sensor at 2,18 has its closest beacon at -2,15, \
+             so there are no others within 7.\n"
+        ));
+        assert!(explained.ends_with(
+            "The first point from 0 to 20 that no sensor covers is 14,11, \
+             so the tuning frequency is 56000011.\n"
+        ));
+    }
+
+    #[test]
+    fn test_resume_gives_the_same_answer() {
+        let sensors = Sensors::parse(EXAMPLE).unwrap();
+        let sensor_data = sensors.sensor_data();
+        // scan the rows up to and including the beacon's by hand, then save
+        let mut cursor = ScanCursor::new(0, MAX, 0, MAX, sensors.sensors().len());
+        while cursor.next_y <= 15 {
+            let y = cursor.next_y;
+            cursor.found.extend(get_points_not_covered_on_row(&sensor_data, y, 0, MAX));
+            cursor.next_y += 1;
+        }
+        let mut resumed = ScanCursor::from_checkpoint(&cursor.to_checkpoint()).unwrap();
+        assert_eq!(resumed.next_y, 16);
+        assert_eq!(resumed.sensor_count, 14);
+
+        // nothing is saved without --checkpoint
+        let mut checkpointer = Checkpointer::from_args("./day15-test.checkpoint");
+        let sensors = sensors.sensors().to_vec();
+        let found = get_points_not_covered_by_sensors(&sensors, &mut resumed, &mut checkpointer);
+        assert_eq!(found, HashSet::from([Point { x: 14, y: 11 }]));
+    }
+
+    #[test]
+    fn test_not_covered_agrees_with_brute_force() {
+        property::check("not covered", 500, random_sensors, |sensors| {
+            let sensor_data: HashMap<Point, i64> =
+                sensors.iter().map(|s| (s.location, s.distance())).collect();
+            let mut fast: Vec<Point> = vec![];
+            for y in 0..=MAX {
+                fast.extend(get_points_not_covered_on_row(&sensor_data, y, 0, MAX));
+            }
+            fast.sort_by_key(|p| (p.y, p.x));
+            property::agree(fast, not_covered_brute_force(sensors))
+        });
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+use std::fs;
+
+use y2022_day15::shared;
+use y2022_day15::{get_points_not_covered_by_sensors, get_tuning_frequency};
+use y2022_day15::{Point, ScanCursor, Sensor, SensorMap, Sensors};
+use y2022_day15::{DAY, YEAR};
+
+use shared::checkpoint::Checkpointer;
+use shared::trace::Trace;
+use shared::viewport::Viewport;
+
+struct AOC {
+    data: Vec<Sensor>,
+}
+
+impl AOC {
+    fn new() -> AOC {
+        AOC { data: Vec::new() }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        let data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+        self.data = match Sensors::parse(&data) {
+            Err(why) => panic!("couldn't parse {}: {}", filename, why),
+            Ok(sensors) => sensors.sensors().to_vec(),
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, None));
+    let mut aoc = AOC::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day15", &filename, |s| Sensors::parse(s).map(|_| ()));
+        return;
+    }
+
+    aoc.load_all_data(&filename);
+
+    println!("data rows: {:?}", aoc.data.len());
+
+    let mut sensor_locations: HashSet<Point> = HashSet::new();
+    let mut beacon_locations: HashSet<Point> = HashSet::new();
+    let mut points: HashSet<Point> = HashSet::new();
+
+    for s in &aoc.data {
+        sensor_locations.insert(s.location.clone());
+        beacon_locations.insert(s.beacon.clone());
+        // let no_beacon_points = s.get_points_within_manhattan_distance();
+        // for p in no_beacon_points {
+        //     points.insert(p);
+        // }
+    }
+
+    println!("sensor locations: {:?}", sensor_locations);
+    println!("beacon locations: {:?}", beacon_locations);
+
+    let min_y = 0;
+    let mut max_y = 20;
+    let min_x = 0;
+    let mut max_x = 20;
+
+    if filename.eq("input.txt") {
+        max_y = 4000000;
+        max_x = 4000000;
+    }
+
+    // bigger (made up) inputs can use bigger bounds
+    if let Some(max) = shared::cli::flag_value("--max") {
+        max_y = max.parse::<i64>().expect("--max should be a number");
+        max_x = max_y;
+    }
+
+    let mut checkpointer = Checkpointer::from_args("./day15.checkpoint");
+
+    let mut cursor = match checkpointer.resume::<ScanCursor>() {
+        Some(Ok(cursor)) => {
+            if cursor.sensor_count != aoc.data.len() {
+                panic!(
+                    "checkpoint {} was for {} sensors, but {} has {}",
+                    checkpointer.path(),
+                    cursor.sensor_count,
+                    filename,
+                    aoc.data.len()
+                );
+            }
+            println!("Resuming from {} at y={}", checkpointer.path(), cursor.next_y);
+            cursor
+        }
+        Some(Err(why)) => panic!("couldn't resume: {}", why),
+        None => ScanCursor::new(min_y, max_y, min_x, max_x, aoc.data.len()),
+    };
+
+    println!("Running...");
+    let data = get_points_not_covered_by_sensors(&aoc.data, &mut cursor, &mut checkpointer);
+    for point in &cursor.found {
+        println!("x: {}", point.x);
+    }
+    if checkpointer.is_enabled() {
+        checkpointer.save(&cursor).expect("couldn't save checkpoint");
+    }
+
+    println!("data: {:?}", data);
+
+    let p = data.iter().nth(0).unwrap();
+
+    println!("tuning frequency: {:?}", get_tuning_frequency(p.x, p.y));
+
+    // the map around the distress beacon, with --view, --centre, --follow or --zoom
+    if let Some(view) = Viewport::from_args() {
+        println!("\n{}", view.render(&SensorMap::new(&aoc.data, Some(*p))));
+    }
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        let sensors = fs::read_to_string(&filename)
+            .map_err(|why| why.to_string())
+            .and_then(|data| Sensors::parse(&data));
+        if let Ok(sensors) = sensors {
+            sensors.find_distress_beacon_traced(max_x, &mut trace);
+            println!("\n{}", trace.render(y2022_day15::narrate));
+        }
+    }
+}
+
+// draws the area a sensor covers for 0..=20, marking row y_to_find. nothing
+// calls it, it's for looking at a single sensor by hand
+#[allow(dead_code)]
+fn print_sensor_map(sensor_ref: &Point, beacon_ref: &Point, y_to_find: Option<i64>) {
+    let sensor = Sensor {
+        location: sensor_ref.clone(),
+        beacon: beacon_ref.clone(),
+    };
+
+    let points = sensor.get_points_within_manhattan_distance();
+
+    for y in 0..=20 {
+        print!("{:02} ", y);
+        for x in 0..=20 {
+            let p = Point { x: x, y: y };
+            if sensor.location.x == x && sensor.location.y == y {
+                print!("S");
+            } else if sensor.beacon.x == x && sensor.beacon.y == y {
+                print!("B");
+            } else if points.contains(&p) {
+                print!("#");
+            } else if y_to_find.is_some() && y == y_to_find.unwrap() {
+                print!("o");
+            } else {
+                print!(".");
+            }
+        }
+        println!("");
+    }
+}
@@ -0,0 +1,1215 @@
+//! Day 16: Proboscidea Volcanium.
+//!
+//! The input is the valves in a cave, how much pressure each releases per
+//! minute once it is opened, and the tunnels between them. Moving through a
+//! tunnel or opening a valve takes a minute. [`TunnelSystem::parse`] reads
+//! the valves and [`TunnelSystem::max_pressure`] finds the most pressure that
+//! can be released in 30 minutes starting at AA. In part 2 an elephant helps,
+//! and [`TunnelSystem::max_pressure_with_elephant`] finds the most the two of
+//! you can release in 26 minutes, each opening different valves.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 16;
+
+use shared::checked;
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+
+// what the pressure is called when it overflows in --checked mode
+const PRESSURE: &str = "day16 pressure released";
+
+// type SavedSolution = (Vec<String>, VecDeque<String>, HashMap<String, i16>);
+
+/// The valves and the tunnels between them. Each tunnel takes a minute.
+pub struct TunnelSystem {
+    valves: HashMap<String, Valve>,
+    one_minute_to: HashMap<String, Vec<String>>,
+}
+
+impl TunnelSystem {
+    pub fn new() -> TunnelSystem {
+        TunnelSystem {
+            valves: HashMap::new(),
+            one_minute_to: HashMap::new(),
+        }
+    }
+
+    /// Reads one valve per line, like
+    /// `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB`. Blank
+    /// lines are skipped. Fails on a line that isn't a valve, a tunnel to a
+    /// valve that isn't there, or no valve AA to start at.
+    pub fn parse(s: &str) -> Result<TunnelSystem, String> {
+        let lines: Vec<String> = s
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| l.len() > 0)
+            .collect();
+        let mut ts = TunnelSystem::new();
+        ts.build_tunnel_system(&lines)?;
+        for (valve, tunnels) in &ts.one_minute_to {
+            for t in tunnels {
+                if !ts.valves.contains_key(t) {
+                    return Err(format!("valve {} has a tunnel to missing valve {}", valve, t));
+                }
+            }
+        }
+        if !ts.valves.contains_key("AA") {
+            return Err("there is no valve AA to start at".to_string());
+        }
+        Ok(ts)
+    }
+
+    // returns None if the line isn't in the form of
+    // "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB"
+    pub fn parse_line(line: &String) -> Option<(String, i16, Vec<String>)> {
+        let (valve_data, tunnel_data) = line.split_once(";")?;
+        let (valve, flow_rate) = valve_data
+            .strip_prefix("Valve ")?
+            .split_once(" has flow rate=")?;
+        let flow_rate = flow_rate.parse::<i16>().ok()?;
+        if valve.len() == 0 || valve.contains(' ') || flow_rate < 0 {
+            return None;
+        }
+        // note: it's "valve" not "valves" when there is only a single tunnel
+        let tunnel_data = tunnel_data.trim();
+        let tunnels_string = tunnel_data
+            .strip_prefix("tunnels lead to valves ")
+            .or(tunnel_data.strip_prefix("tunnel leads to valve "))?;
+        let mut tunnels: Vec<String> = Vec::new();
+        for t in tunnels_string.split(",") {
+            let s = t.trim();
+            if s.len() == 0 || s.contains(' ') {
+                return None;
+            }
+            tunnels.push(s.to_string());
+        }
+        Some((valve.to_string(), flow_rate, tunnels))
+    }
+
+    /// The valves, by name.
+    pub fn valves(&self) -> &HashMap<String, Valve> {
+        &self.valves
+    }
+
+    /// The most pressure that can be released in `minutes` starting at
+    /// `start`, and the valves to open to do it (starting with the start).
+    /// Fails if there is no valve `start`.
+    pub fn max_pressure(&self, start: &String, minutes: i32) -> Result<(i32, Vec<String>), String> {
+        self.check_start(start)?;
+        let mut valves_path_data = self.get_non_zero_valve_names()?;
+        valves_path_data.insert(start.clone(), 0);
+        let routes = self.get_shortest_routes_between_valves(&valves_path_data)?;
+        Ok(self.get_max_pressure(&routes, start, minutes))
+    }
+
+    /// The most pressure you and the elephant can release together in
+    /// `minutes`, both starting at `start`, and the valves each of you opens
+    /// (starting with the start). Fails if there is no valve `start`, or
+    /// more than 64 valves with a flow rate can be reached from it.
+    pub fn max_pressure_with_elephant(
+        &self,
+        start: &String,
+        minutes: i32,
+    ) -> Result<(i32, Vec<String>, Vec<String>), String> {
+        self.check_start(start)?;
+        let mut valves_path_data = self.get_non_zero_valve_names()?;
+        valves_path_data.insert(start.clone(), 0);
+        let routes = self.get_shortest_routes_between_valves(&valves_path_data)?;
+        self.get_max_pressure_for_two(&routes, start, minutes)
+    }
+
+    fn check_start(&self, start: &String) -> Result<(), String> {
+        if !self.valves.contains_key(start) {
+            return Err(format!("there is no valve {} to start at", start));
+        }
+        Ok(())
+    }
+
+    pub fn build_tunnel_system(&mut self, lines: &Vec<String>) -> Result<(), String> {
+        for line in lines {
+            let data = TunnelSystem::parse_line(&line);
+            if data.is_none() {
+                return Err(format!("couldn't parse line {:?}", line));
+            } else {
+                let (valve, flow_rate, tunnels) = data.unwrap();
+                let v = Valve::new(valve.clone(), flow_rate);
+                // save the data
+                self.valves.insert(valve.clone(), v);
+                self.one_minute_to.insert(valve.clone(), tunnels.clone());
+                // println!(
+                //     "V {} - fr={}; to V(s) {}",
+                //     valve,
+                //     flow_rate,
+                //     tunnels.join(", ")
+                // );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The flow rate of each valve that has one, by name.
+    pub fn get_non_zero_valve_names(&self) -> Result<HashMap<String, i16>, String> {
+        let mut non_zero_valves: HashMap<String, i16> = HashMap::new();
+        // println!("{:?}", self.valves);
+        for (valve_name, valve) in &self.valves {
+            // println!("{}: {}", valve_name, valve.flow_rate);
+            if valve.flow_rate > 0 {
+                non_zero_valves.insert(valve_name.clone(), valve.flow_rate);
+            }
+        }
+        Ok(non_zero_valves)
+    }
+
+    /// The valves on the shortest route from v1 to v2, including both. Fails
+    /// if v2 can't be reached from v1.
+    pub fn get_shortest_route_between_valves(
+        &self,
+        v1: &String,
+        v2: &String,
+    ) -> Result<Vec<String>, String> {
+        let mut route = Vec::new();
+        // queue stores (current, parents) valve names where parents is a comma delimited list of parents
+        let mut queue: VecDeque<(String, String)> = VecDeque::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        queue.push_back((v1.clone(), "".to_string())); // first one has no parent so use empty string
+        visited.insert(v1.clone());
+        let mut found: Option<(String, String)> = None;
+        while !queue.is_empty() {
+            let (current, parent) = queue.pop_front().unwrap();
+            if current.eq(v2) {
+                found = Some((current, parent));
+                break;
+            }
+            // a valve that isn't there has no tunnels
+            for next in self.one_minute_to.get(&current).into_iter().flatten() {
+                if !visited.contains(next) {
+                    visited.insert(next.clone());
+                    let new_parent = format!("{},{}", parent, current);
+                    queue.push_back((next.clone(), new_parent));
+                }
+            }
+        }
+        let found = match found {
+            Some(found) => found,
+            None => return Err(format!("valve {} can't be reached from {}", v2, v1)),
+        };
+        // now get the parent list (comma delimited list)
+        for v in found.1.split(",") {
+            if v.len() > 0 {
+                route.push(v.to_string());
+            }
+        }
+        // and add the last one on the end
+        route.push(found.0);
+
+        /*
+        // from wikipedia: BFS pseudocode
+         1  procedure BFS(G, root) is
+         2      let Q be a queue
+         3      label root as explored
+         4      Q.enqueue(root)
+         5      while Q is not empty do
+         6          v := Q.dequeue()
+         7          if v is the goal then
+         8              return v
+         9          for all edges from v to w in G.adjacentEdges(v) do
+        10              if w is not labeled as explored then
+        11                  label w as explored
+        12                  w.parent := v
+        13                  Q.enqueue(w)
+                 */
+        Ok(route)
+    }
+
+    /// The shortest route between every pair of the given valves. A valve
+    /// that can't be reached from another has no route to it.
+    pub fn get_shortest_routes_between_valves(
+        &self,
+        valves: &NonZeroValveData,
+    ) -> Result<TunnelRoutes, String> {
+        let mut routes: TunnelRoutes = HashMap::new();
+        let valve_names = valves.keys().cloned().collect::<Vec<String>>();
+        let num = valve_names.len();
+
+        for i in 0..num {
+            for j in 0..num {
+                if i != j {
+                    let v1 = valve_names[i].clone();
+                    let v2 = valve_names[j].clone();
+                    let route_result = self.get_shortest_route_between_valves(&v1, &v2);
+                    if route_result.is_ok() {
+                        let route = route_result.unwrap();
+                        let current_valve_routes = routes.get(&v1);
+                        if current_valve_routes.is_none() {
+                            let mut route_map: HashMap<String, Vec<String>> = HashMap::new();
+                            route_map.insert(v2.clone(), route.clone());
+                            routes.insert(v1.clone(), route_map);
+                        } else {
+                            let mut route_map = current_valve_routes.unwrap().clone();
+                            route_map.insert(v2.clone(), route.clone());
+                            routes.insert(v1.clone(), route_map);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(routes)
+    }
+
+    // every route (the order the valves are opened in) that can be started within
+    // the time, which is a lot of routes
+    #[cfg(test)]
+    fn get_all_possible_routes(
+        &self,
+        routes: &TunnelRoutes,
+        start: &String,
+        minutes: i32,
+    ) -> Result<AllRoutes, String> {
+        let mut all_routes: Vec<Vec<(String, i16)>> = Vec::new();
+        // need to store how many steps (minutes) it is between each pair of valves
+        let mut distances: HashMap<(String, String), i16> = HashMap::new();
+        for (k, v) in routes {
+            for (k2, v2) in v {
+                // the steps are stored as ["v1", ... , "v2"] so the length is the number of steps
+                // because at the end the last one will need to be waited at for 1 minute
+                // to open the valve, so appending will mean there will be two entries for the
+                // valve ["v1", ... , "v2"] + ["v2", ... , "v3"] because v2 is the start of the next
+                // entry and v1 becomes the parent of v2
+                distances.insert((k.clone(), k2.clone()), v2.len() as i16);
+            }
+        }
+        // queue stores (current, parents) valve names where parents is a comma delimited list of parents
+        let mut queue: VecDeque<(String, Vec<(String, i16)>)> = VecDeque::new();
+
+        queue.push_back((start.clone(), vec![])); // first one has no parent so use empty string
+        while !queue.is_empty() {
+            let (current, parents) = queue.pop_front().unwrap();
+            // the end state is when the time runs out
+            // this is a "distance" calculation
+            let distance_to_here: i16 = parents.clone().into_iter().map(|n| n.1).sum();
+            // get last point in parents
+            let mut distance_for_this_step: i16 = 0;
+            if parents.len() > 0 {
+                let last_parent = parents.last().unwrap();
+                let distance_result = distances.get(&(current.clone(), last_parent.0.to_string()));
+                distance_for_this_step += distance_result.unwrap();
+            }
+
+            let next = (current.clone(), distance_for_this_step);
+
+            // if it's more than the time, don't add any more, just add it to the possible routes
+            if (distance_to_here + distance_for_this_step) as i32 > minutes {
+                let mut new_route = parents.clone();
+                new_route.push(next);
+                all_routes.push(new_route);
+            } else {
+                let mut new_parents = parents.clone();
+                new_parents.push(next);
+                // we can just do this each time for each route
+                // and because each loop carries it's own parents with it
+                // there is no need to have a separate visited list
+                let visited: HashSet<String> =
+                    new_parents.clone().into_iter().map(|n| n.0).collect();
+                let mut is_last = true;
+                if let Some(next_valves) = routes.get(&current) {
+                    for next_valve in next_valves.keys() {
+                        if !visited.contains(next_valve) {
+                            queue.push_back((next_valve.clone(), new_parents.clone()));
+                            is_last = false;
+                        }
+                    }
+                }
+                // every valve is open with time to spare, so this is a possible route too
+                if is_last {
+                    all_routes.push(new_parents);
+                }
+            }
+        }
+        Ok(all_routes)
+    }
+
+    // the solution is where we are at each minute, staying at a valve for a
+    // minute opens it
+    #[cfg(test)]
+    fn calculate_pressure_released(&self, solution: &Vec<String>, minutes: i32) -> i32 {
+        self.calculate_pressure_released_traced(solution, minutes, &mut Trace::default())
+    }
+
+    /// The pressure released in `minutes` following a minute by minute route
+    /// from AA (see [`TunnelSystem::get_route_for_waypoints`]), recording what
+    /// happens each minute in `trace` (see [`narrate`]).
+    pub fn calculate_pressure_released_traced(
+        &self,
+        solution: &Vec<String>,
+        minutes: i32,
+        trace: &mut Trace,
+    ) -> i32 {
+        let mut pressure_released: i32 = 0;
+        let mut flow_rate: i32 = 0;
+        let mut current_valve = "AA";
+        let mut opened: HashSet<&String> = HashSet::new();
+        let mut i: i32 = 1;
+        for valve in solution {
+            trace_minute(trace, i, &opened, flow_rate);
+            i += 1;
+            pressure_released = checked::add(PRESSURE, pressure_released, flow_rate);
+            if valve.eq(current_valve) && opened.insert(valve) {
+                let valve_flow_rate: i32 = self.valves.get(valve).unwrap().flow_rate as i32;
+                trace.event("open", &[("valve", valve), ("flow rate", &valve_flow_rate)]);
+                flow_rate += valve_flow_rate;
+            } else {
+                trace.event("move", &[("valve", valve)]);
+            }
+            current_valve = valve;
+        }
+        // once the route is done, wait with the valves open until the time runs out
+        while i <= minutes {
+            trace_minute(trace, i, &opened, flow_rate);
+            i += 1;
+            pressure_released = checked::add(PRESSURE, pressure_released, flow_rate);
+        }
+        pressure_released
+    }
+
+    /// The pressure released in `minutes` by you and the elephant following
+    /// your minute by minute routes from AA side by side, recording what each
+    /// of you does each minute in `trace` (see [`narrate`]).
+    pub fn calculate_pressure_released_by_two_traced(
+        &self,
+        you: &Vec<String>,
+        elephant: &Vec<String>,
+        minutes: i32,
+        trace: &mut Trace,
+    ) -> i32 {
+        let mut pressure_released: i32 = 0;
+        let mut flow_rate: i32 = 0;
+        let mut current_valves = ["AA", "AA"];
+        let mut opened: HashSet<&String> = HashSet::new();
+        for minute in 1..=minutes {
+            trace_minute(trace, minute, &opened, flow_rate);
+            pressure_released = checked::add(PRESSURE, pressure_released, flow_rate);
+            let agents = [("you", you), ("the elephant", elephant)];
+            for (i, (who, route)) in agents.iter().enumerate() {
+                // the one with the shorter route waits once it is done
+                let valve = match route.get(minute as usize - 1) {
+                    Some(valve) => valve,
+                    None => continue,
+                };
+                if valve.eq(current_valves[i]) && opened.insert(valve) {
+                    let valve_flow_rate: i32 = self.valves.get(valve).unwrap().flow_rate as i32;
+                    trace.event(
+                        "open",
+                        &[("valve", valve), ("flow rate", &valve_flow_rate), ("who", who)],
+                    );
+                    flow_rate += valve_flow_rate;
+                } else {
+                    trace.event("move", &[("valve", valve), ("who", who)]);
+                }
+                current_valves[i] = valve;
+            }
+        }
+        pressure_released
+    }
+
+    // the minute by minute route for a list of waypoints (valves to open), with
+    // the start dropped and an extra minute at each waypoint to open its valve
+    pub fn get_route_for_waypoints(
+        &self,
+        routes: &TunnelRoutes,
+        waypoints: &Vec<String>,
+        minutes: i32,
+    ) -> Vec<String> {
+        let mut route_vec: Vec<String> = vec![];
+        for i in 1..waypoints.len() {
+            let from = waypoints.get(i - 1).unwrap();
+            let to = waypoints.get(i).unwrap();
+            // now get the route from the routes map
+            let route = routes.get(from).unwrap().get(to).unwrap();
+            // the start of each route is the end of the one before, which is
+            // the minute spent opening that valve
+            route_vec.append(&mut route.clone());
+        }
+        if route_vec.len() > 0 {
+            // remove initial start valve from the route
+            route_vec.remove(0);
+            // and open the last valve
+            route_vec.push(waypoints.last().unwrap().clone());
+        }
+        // now get just the time we have
+        route_vec.truncate(minutes as usize);
+        route_vec
+    }
+
+    // the slow way to find the most pressure: work out the pressure for every
+    // possible route. the tests check get_max_pressure against this
+    #[cfg(test)]
+    fn get_max_pressure_by_enumeration(
+        &self,
+        routes: &TunnelRoutes,
+        start: &String,
+        minutes: i32,
+    ) -> (i32, Vec<String>) {
+        let mut max_pressure = 0;
+        let mut max_route = vec![start.clone()];
+        for r in self.get_all_possible_routes(routes, start, minutes).unwrap() {
+            let waypoints: Vec<String> = r.into_iter().map(|n| n.0).collect();
+            let route_vec = self.get_route_for_waypoints(routes, &waypoints, minutes);
+            let pressure_released = self.calculate_pressure_released(&route_vec, minutes);
+            if pressure_released > max_pressure {
+                max_pressure = pressure_released;
+                max_route = waypoints;
+            }
+        }
+        (max_pressure, max_route)
+    }
+
+    // depth first search over the order the valves are opened in, always taking
+    // the shortest route to the next closed valve. gives the most pressure that
+    // can be released and the waypoints (starting with the start) to do it
+    pub fn get_max_pressure(
+        &self,
+        routes: &TunnelRoutes,
+        start: &String,
+        minutes: i32,
+    ) -> (i32, Vec<String>) {
+        let mut best: (i32, Vec<String>) = (0, vec![start.clone()]);
+        let mut waypoints: Vec<String> = vec![start.clone()];
+        self.search_for_max_pressure(routes, &mut waypoints, minutes, 0, &mut best);
+        best
+    }
+
+    fn search_for_max_pressure(
+        &self,
+        routes: &TunnelRoutes,
+        waypoints: &mut Vec<String>,
+        minutes_left: i32,
+        released: i32,
+        best: &mut (i32, Vec<String>),
+    ) {
+        if released > best.0 {
+            *best = (released, waypoints.clone());
+        }
+        let current = waypoints.last().unwrap().clone();
+        let next_valves = match routes.get(&current) {
+            Some(next_valves) => next_valves,
+            None => return,
+        };
+        let closed: Vec<(&String, i32, i32)> = next_valves
+            .iter()
+            .filter(|(valve, _)| !waypoints.contains(valve))
+            .map(|(valve, route)| {
+                let flow_rate = self.valves.get(valve).unwrap().flow_rate as i32;
+                // a minute for each step (the route includes where we are now)
+                // and one more to open the valve
+                let minutes_after = minutes_left - route.len() as i32;
+                (valve, flow_rate, minutes_after)
+            })
+            .filter(|(_, flow_rate, minutes_after)| *flow_rate > 0 && *minutes_after > 0)
+            .collect();
+
+        // give up on this route if even opening every closed valve as soon as
+        // possible (a step and a minute to open it) couldn't beat the best so far
+        let most_possible: i32 = closed
+            .iter()
+            .map(|(_, flow_rate, _)| flow_rate * (minutes_left - 2))
+            .sum();
+        if released + most_possible <= best.0 {
+            return;
+        }
+
+        for (valve, flow_rate, minutes_after) in closed {
+            let released_by_valve = checked::mul(PRESSURE, flow_rate, minutes_after);
+            waypoints.push(valve.clone());
+            self.search_for_max_pressure(
+                routes,
+                waypoints,
+                minutes_after,
+                checked::add(PRESSURE, released, released_by_valve),
+                best,
+            );
+            waypoints.pop();
+        }
+    }
+
+    // the two of you open different valves, so this finds the most pressure
+    // each set of valves can release on its own, and then the best pair of
+    // sets with no valve in common. gives the pressure and the waypoints for
+    // you and for the elephant, or an error if there are more than 64 valves
+    // to share out
+    pub fn get_max_pressure_for_two(
+        &self,
+        routes: &TunnelRoutes,
+        start: &String,
+        minutes: i32,
+    ) -> Result<(i32, Vec<String>, Vec<String>), String> {
+        // a bit for each valve that can be opened
+        let mut valves: Vec<&String> = routes.keys().filter(|v| *v != start).collect();
+        valves.sort();
+        if valves.len() > 64 {
+            return Err(format!(
+                "{} valves have a flow rate, at most 64 can be shared out",
+                valves.len()
+            ));
+        }
+        let bits: HashMap<&String, u64> = valves
+            .iter()
+            .enumerate()
+            .map(|(i, valve)| (*valve, 1 << i))
+            .collect();
+
+        let mut best_by_set: HashMap<u64, (i32, Vec<String>)> = HashMap::new();
+        let mut waypoints: Vec<String> = vec![start.clone()];
+        self.search_every_set(
+            routes,
+            &bits,
+            &mut waypoints,
+            0,
+            minutes,
+            0,
+            &mut best_by_set,
+        );
+
+        // the most pressure first, so the search can stop once no pair with
+        // the set it is on can beat the best so far
+        let mut sets: Vec<(u64, (i32, Vec<String>))> = best_by_set.into_iter().collect();
+        sets.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+        let mut best: (i32, usize, usize) = (-1, 0, 0);
+        for i in 0..sets.len() {
+            if sets[i].1 .0 * 2 <= best.0 {
+                break;
+            }
+            for j in i..sets.len() {
+                let pressure = checked::add(PRESSURE, sets[i].1 .0, sets[j].1 .0);
+                if pressure <= best.0 {
+                    break;
+                }
+                if sets[i].0 & sets[j].0 == 0 {
+                    best = (pressure, i, j);
+                    break;
+                }
+            }
+        }
+        let (pressure, you, elephant) = best;
+        Ok((
+            pressure,
+            sets[you].1 .1.clone(),
+            sets[elephant].1 .1.clone(),
+        ))
+    }
+
+    // like search_for_max_pressure, but without giving up on a route, as
+    // every set of valves that can be opened in time is wanted. `opened` has
+    // the bits of the valves opened so far
+    fn search_every_set(
+        &self,
+        routes: &TunnelRoutes,
+        bits: &HashMap<&String, u64>,
+        waypoints: &mut Vec<String>,
+        opened: u64,
+        minutes_left: i32,
+        released: i32,
+        best_by_set: &mut HashMap<u64, (i32, Vec<String>)>,
+    ) {
+        match best_by_set.get(&opened) {
+            Some((best, _)) if *best >= released => {}
+            _ => {
+                best_by_set.insert(opened, (released, waypoints.clone()));
+            }
+        }
+        let current = waypoints.last().unwrap().clone();
+        let next_valves = match routes.get(&current) {
+            Some(next_valves) => next_valves,
+            None => return,
+        };
+        for (valve, route) in next_valves {
+            let bit = match bits.get(valve) {
+                Some(bit) if opened & bit == 0 => *bit,
+                _ => continue,
+            };
+            let flow_rate = self.valves.get(valve).unwrap().flow_rate as i32;
+            let minutes_after = minutes_left - route.len() as i32;
+            if flow_rate == 0 || minutes_after <= 0 {
+                continue;
+            }
+            let released_by_valve = checked::mul(PRESSURE, flow_rate, minutes_after);
+            waypoints.push(valve.clone());
+            self.search_every_set(
+                routes,
+                bits,
+                waypoints,
+                opened | bit,
+                minutes_after,
+                checked::add(PRESSURE, released, released_by_valve),
+                best_by_set,
+            );
+            waypoints.pop();
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Valve {
+    pub valve_name: String,
+    pub flow_rate: i16,
+}
+
+impl Valve {
+    pub fn new(valve_name: String, flow_rate: i16) -> Valve {
+        Valve {
+            valve_name,
+            flow_rate,
+        }
+    }
+}
+
+/// Checks the valves the way [`TunnelSystem::parse`] reads them, but finds
+/// every problem rather than the first, each on its line: a line that isn't
+/// a valve, a valve that is there twice, and a tunnel to a valve that isn't
+/// there. It also checks there is a valve AA to start at.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut valves: Vec<(usize, String, Vec<String>)> = Vec::new();
+    let mut lines_by_valve: HashMap<String, usize> = HashMap::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match TunnelSystem::parse_line(&line.to_string()) {
+            Some((valve, _flow_rate, tunnels)) => {
+                if let Some(first) = lines_by_valve.get(&valve) {
+                    lint.at(i, format!("valve {} is already on line {}", valve, first + 1));
+                } else {
+                    lines_by_valve.insert(valve.clone(), i);
+                }
+                valves.push((i, valve, tunnels));
+            }
+            None => lint.at(i, format!("{:?} is not a valve", line)),
+        }
+    }
+    for (i, valve, tunnels) in &valves {
+        for t in tunnels {
+            if !lines_by_valve.contains_key(t) {
+                lint.at(*i, format!("valve {} has a tunnel to missing valve {}", valve, t));
+            }
+        }
+    }
+    if !lines_by_valve.contains_key("AA") {
+        lint.whole("there is no valve AA to start at");
+    }
+    lint.problems()
+}
+
+/// The flow rate of every valve that releases any pressure, by name.
+pub type NonZeroValveData = HashMap<String, i16>;
+/// The shortest route from each valve to each of the others, as the valves
+/// on it including both ends.
+pub type TunnelRoutes = HashMap<String, HashMap<String, Vec<String>>>;
+#[cfg(test)]
+type AllRoutes = Vec<Vec<(String, i16)>>;
+
+// the start of a minute: which valves are open and how much they release
+fn trace_minute(trace: &mut Trace, minute: i32, opened: &HashSet<&String>, flow_rate: i32) {
+    if !trace.is_enabled() {
+        return;
+    }
+    let mut open: Vec<&str> = opened.iter().map(|v| v.as_str()).collect();
+    open.sort();
+    trace.event("minute", &[("minute", &minute)]);
+    trace.event("release", &[("open", &open.join(", ")), ("pressure", &flow_rate)]);
+}
+
+/// One line of the puzzle's own walkthrough for an event recorded by
+/// [`TunnelSystem::calculate_pressure_released_traced`] or
+/// [`TunnelSystem::calculate_pressure_released_by_two_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "minute" => format!("\n== Minute {} ==", event.get("minute")),
+        "release" => {
+            let open: Vec<&str> = event.get("open").split(", ").filter(|v| !v.is_empty()).collect();
+            match open.len() {
+                0 => "No valves are open.".to_string(),
+                1 => format!(
+                    "Valve {} is open, releasing {} pressure.",
+                    open[0],
+                    event.get("pressure")
+                ),
+                n => format!(
+                    "Valves {}{} {} are open, releasing {} pressure.",
+                    open[..n - 1].join(", "),
+                    if n > 2 { ", and" } else { " and" },
+                    open[n - 1],
+                    event.get("pressure")
+                ),
+            }
+        }
+        "open" if event.get("who") == "the elephant" => {
+            format!("The elephant opens valve {}.", event.get("valve"))
+        }
+        "open" => format!("You open valve {}.", event.get("valve")),
+        "move" if event.get("who") == "the elephant" => {
+            format!("The elephant moves to valve {}.", event.get("valve"))
+        }
+        "move" => format!("You move to valve {}.", event.get("valve")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::property;
+    use shared::rng::Rng;
+
+    #[test]
+    fn test_parse_lines() {
+        let line = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB".to_string();
+        let data = TunnelSystem::parse_line(&line);
+        assert!(data.is_some());
+        if data.is_some() {
+            let (valve, flow_rate, tunnels) = data.unwrap();
+            assert_eq!(valve, "AA");
+            assert_eq!(flow_rate, 0);
+            assert_eq!(tunnels.len(), 3);
+            assert_eq!(tunnels[0], "DD");
+            assert_eq!(tunnels[1], "II");
+            assert_eq!(tunnels[2], "BB");
+        }
+        // note: valve not valves, flow rate is not zero, and only has a single tunnel
+        let line2 = "Valve HH has flow rate=22; tunnel leads to valve GG".to_string();
+        let data2 = TunnelSystem::parse_line(&line2);
+        assert!(data2.is_some());
+        if data2.is_some() {
+            let (valve2, flow_rate2, tunnels2) = data2.unwrap();
+            assert_eq!(valve2, "HH");
+            assert_eq!(flow_rate2, 22);
+            assert_eq!(tunnels2.len(), 1);
+            assert_eq!(tunnels2[0], "GG");
+        }
+    }
+
+    #[test]
+    fn test_parse_bad_lines() {
+        let lines = vec![
+            "",
+            "Valve AA",
+            "Valve AA has flow rate=; tunnels lead to valves DD",
+            "Valve AA has flow rate=x; tunnels lead to valves DD",
+            "Valve AA has flow rate=0 tunnels lead to valves DD",
+            "Valve AA has flow rate=0; tunnels lead to valves ",
+            "Valve AA has flow rate=0; tunnels lead to valves DD,, BB",
+        ];
+        for line in lines {
+            assert!(TunnelSystem::parse_line(&line.to_string()).is_none(), "{:?}", line);
+        }
+
+        let mut ts = TunnelSystem::new();
+        let result = ts.build_tunnel_system(&vec!["Valve AA".to_string()]);
+        assert!(result.is_err());
+
+        let result = TunnelSystem::parse("Valve BB has flow rate=3; tunnel leads to valve BB\n");
+        assert_eq!(result.err(), Some("there is no valve AA to start at".to_string()));
+    }
+
+    #[test]
+    fn test_lint_finds_every_problem() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=13; tunnel leads to valve ZZ\n\
+                     Valve BB has flow=2\n\
+                     Valve BB has flow rate=2; tunnel leads to valve AA\n";
+        let problems: Vec<String> = lint(input).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "line 1: valve AA has a tunnel to missing valve CC",
+                "line 2: valve BB has a tunnel to missing valve ZZ",
+                "line 3: \"Valve BB has flow=2\" is not a valve",
+                "line 4: valve BB is already on line 2",
+            ]
+        );
+        assert_eq!(lint("Valve BB has flow rate=0; tunnel leads to valve BB\n").len(), 1);
+    }
+
+    #[test]
+    fn test_new_valve() {
+        let valve_name: String = "AA".to_string();
+        let flow_rate: i16 = 20;
+        let valve = Valve::new(valve_name, flow_rate);
+
+        assert_eq!(valve.valve_name, "AA");
+        assert_eq!(valve.flow_rate, 20);
+    }
+
+    #[test]
+    fn test_build_tunnel_system() {
+        let lines = vec![
+            "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB".to_string(),
+            "Valve HH has flow rate=22; tunnel leads to valve GG".to_string(),
+        ];
+        let mut ts = TunnelSystem::new();
+        let result = ts.build_tunnel_system(&lines);
+        assert!(result.is_ok());
+        assert_eq!(ts.valves.len(), 2);
+        assert_eq!(ts.one_minute_to.len(), 2);
+        assert_eq!(ts.valves.contains_key(&"AA".to_string()), true);
+        assert_eq!(ts.valves.contains_key(&"DD".to_string()), false);
+        assert_eq!(ts.one_minute_to.contains_key(&"HH".to_string()), true);
+        assert_eq!(ts.one_minute_to.contains_key(&"II".to_string()), false);
+        assert_eq!(
+            ts.one_minute_to
+                .get(&"HH".to_string())
+                .unwrap()
+                .contains(&"GG".to_string()),
+            true
+        );
+    }
+
+    #[test]
+    fn test_get_valve_names_with_flow_rate_greater_than_zero() {
+        let lines = vec![
+            "Valve AA has flow rate=0; tunnels lead to valves BB, CC, DD".to_string(),
+            "Valve BB has flow rate=1; tunnel leads to valve AA".to_string(),
+            "Valve CC has flow rate=0; tunnel leads to valve DD".to_string(),
+            "Valve DD has flow rate=20; tunnel leads to valve EE, FF".to_string(),
+            "Valve EE has flow rate=0; tunnel leads to valve DD, FF".to_string(),
+            "Valve FF has flow rate=15; tunnel leads to valve CC".to_string(),
+        ];
+        let mut ts = TunnelSystem::new();
+        let result = ts.build_tunnel_system(&lines);
+        // now the tunnel system is built
+        assert!(result.is_ok());
+        let non_zero_valves_result: Result<HashMap<String, i16>, String> =
+            ts.get_non_zero_valve_names();
+        assert!(non_zero_valves_result.is_ok());
+        let non_zero_valves = non_zero_valves_result.unwrap();
+        assert_eq!(non_zero_valves.len(), 3);
+        assert_eq!(non_zero_valves.contains_key(&"BB".to_string()), true);
+        assert_eq!(non_zero_valves.contains_key(&"DD".to_string()), true);
+        assert_eq!(non_zero_valves.contains_key(&"FF".to_string()), true);
+        assert_eq!(non_zero_valves.get(&"BB".to_string()).unwrap(), &1);
+        assert_eq!(non_zero_valves.get(&"DD".to_string()).unwrap(), &20);
+        assert_eq!(non_zero_valves.get(&"FF".to_string()).unwrap(), &15);
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let lines = vec![
+            "Valve AA has flow rate=0; tunnels lead to valves BB, CC, DD".to_string(),
+            "Valve BB has flow rate=1; tunnel leads to valve AA".to_string(),
+            "Valve CC has flow rate=0; tunnel leads to valve DD".to_string(),
+            "Valve DD has flow rate=20; tunnel leads to valve EE, FF".to_string(),
+            "Valve EE has flow rate=0; tunnel leads to valve DD, FF".to_string(),
+            "Valve FF has flow rate=15; tunnel leads to valve CC".to_string(),
+        ];
+        let mut ts = TunnelSystem::new();
+        let result = ts.build_tunnel_system(&lines);
+        // now the tunnel system is built
+        assert!(result.is_ok());
+
+        if result.is_ok() {
+            // paths will be from AA (start) to all the valves with non-zero flow rates
+            let non_zero_valves_result: Result<HashMap<String, i16>, String> =
+                ts.get_non_zero_valve_names();
+            assert!(non_zero_valves_result.is_ok());
+            let mut non_zero_valves = non_zero_valves_result.unwrap();
+
+            non_zero_valves.insert("AA".to_string(), 0);
+
+            let mut shortest_path_result =
+                ts.get_shortest_route_between_valves(&"AA".to_string(), &"BB".to_string());
+            assert!(shortest_path_result.is_ok());
+            assert_eq!(shortest_path_result.unwrap().len(), 2); // AA -> BB
+
+            shortest_path_result =
+                ts.get_shortest_route_between_valves(&"AA".to_string(), &"DD".to_string());
+            assert!(shortest_path_result.is_ok());
+            assert_eq!(shortest_path_result.unwrap().len(), 2); // AA -> DD
+
+            shortest_path_result =
+                ts.get_shortest_route_between_valves(&"AA".to_string(), &"FF".to_string());
+            assert!(shortest_path_result.is_ok());
+            assert_eq!(shortest_path_result.unwrap().len(), 3); // AA -> DD -> FF
+        }
+    }
+
+    fn get_routes(ts: &TunnelSystem) -> TunnelRoutes {
+        let mut non_zero_valves = ts.get_non_zero_valve_names().unwrap();
+        non_zero_valves.insert("AA".to_string(), 0);
+        ts.get_shortest_routes_between_valves(&non_zero_valves)
+            .unwrap()
+    }
+
+    const EXAMPLE: [&str; 10] = [
+        "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB",
+        "Valve BB has flow rate=13; tunnels lead to valves CC, AA",
+        "Valve CC has flow rate=2; tunnels lead to valves DD, BB",
+        "Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE",
+        "Valve EE has flow rate=3; tunnels lead to valves FF, DD",
+        "Valve FF has flow rate=0; tunnels lead to valves EE, GG",
+        "Valve GG has flow rate=0; tunnels lead to valves FF, HH",
+        "Valve HH has flow rate=22; tunnel leads to valve GG",
+        "Valve II has flow rate=0; tunnels lead to valves AA, JJ",
+        "Valve JJ has flow rate=21; tunnel leads to valve II",
+    ];
+
+    #[test]
+    fn test_max_pressure_example() {
+        let lines: Vec<String> = EXAMPLE.iter().map(|l| l.to_string()).collect();
+        let mut ts = TunnelSystem::new();
+        ts.build_tunnel_system(&lines).unwrap();
+        let routes = get_routes(&ts);
+
+        let (max_pressure, max_route) = ts.get_max_pressure(&routes, &"AA".to_string(), 30);
+        assert_eq!(max_pressure, 1651);
+        // the route gives the same pressure when it is walked minute by minute
+        let full_route = ts.get_route_for_waypoints(&routes, &max_route, 30);
+        assert_eq!(ts.calculate_pressure_released(&full_route, 30), 1651);
+
+        let (enumerated_pressure, _) =
+            ts.get_max_pressure_by_enumeration(&routes, &"AA".to_string(), 30);
+        assert_eq!(enumerated_pressure, 1651);
+    }
+
+    #[test]
+    fn test_explain_route() {
+        let lines: Vec<String> = EXAMPLE.iter().map(|l| l.to_string()).collect();
+        let mut ts = TunnelSystem::new();
+        ts.build_tunnel_system(&lines).unwrap();
+        let routes = get_routes(&ts);
+        let (_, max_route) = ts.get_max_pressure(&routes, &"AA".to_string(), 30);
+        let full_route = ts.get_route_for_waypoints(&routes, &max_route, 30);
+
+        let mut trace = Trace::new(true);
+        let pressure = ts.calculate_pressure_released_traced(&full_route, 30, &mut trace);
+        assert_eq!(pressure, 1651);
+        // the same walkthrough as the puzzle's
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with(
+            "\n== Minute 1 ==\nNo valves are open.\nYou move to valve DD.\n"
+        ));
+        assert!(explained.contains("== Minute 2 ==\nNo valves are open.\nYou open valve DD.\n"));
+        assert!(explained.contains(
+            "== Minute 10 ==\nValves BB, DD, and JJ are open, releasing 54 pressure.\n"
+        ));
+        assert_eq!(trace.events().iter().filter(|e| e.kind == "minute").count(), 30);
+
+        // nothing is recorded unless the trace is on
+        let mut trace = Trace::default();
+        ts.calculate_pressure_released_traced(&full_route, 30, &mut trace);
+        assert!(trace.events().is_empty());
+    }
+
+    // a connected tunnel system of a few valves, as the lines of a puzzle input
+    fn random_tunnel_system(rng: &mut Rng) -> (Vec<String>, i32) {
+        let count = rng.range(2, 7) as usize;
+        let names: Vec<String> = (0..count)
+            .map(|i| {
+                let c = (b'A' + i as u8) as char;
+                format!("{}{}", c, c)
+            })
+            .collect();
+        let mut tunnels: Vec<Vec<usize>> = vec![vec![]; count];
+        let mut connect = |a: usize, b: usize| {
+            if a != b && !tunnels[a].contains(&b) {
+                tunnels[a].push(b);
+                tunnels[b].push(a);
+            }
+        };
+        // join each valve to one before it so they're all connected, then add a few more
+        for i in 1..count {
+            connect(i, rng.below(i as u64) as usize);
+        }
+        for _ in 0..rng.below(count as u64) {
+            connect(rng.below(count as u64) as usize, rng.below(count as u64) as usize);
+        }
+        let lines = (0..count)
+            .map(|i| {
+                // AA is always 0, the same as the puzzle
+                let flow_rate = if i == 0 || rng.chance(3) {
+                    0
+                } else {
+                    rng.range(1, 25)
+                };
+                let to: Vec<String> = tunnels[i].iter().map(|t| names[*t].clone()).collect();
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    names[i],
+                    flow_rate,
+                    to.join(", ")
+                )
+            })
+            .collect();
+        (lines, rng.range(1, 30) as i32)
+    }
+
+    #[test]
+    fn test_max_pressure_agrees_with_enumeration() {
+        property::check(
+            "max pressure",
+            300,
+            random_tunnel_system,
+            |(lines, minutes)| {
+                let mut ts = TunnelSystem::new();
+                ts.build_tunnel_system(lines)?;
+                let routes = get_routes(&ts);
+                let start = "AA".to_string();
+                let (fast, fast_route) = ts.get_max_pressure(&routes, &start, *minutes);
+                let (slow, _) = ts.get_max_pressure_by_enumeration(&routes, &start, *minutes);
+                let full_route = ts.get_route_for_waypoints(&routes, &fast_route, *minutes);
+                property::agree(fast, slow)?;
+                property::agree(ts.calculate_pressure_released(&full_route, *minutes), slow)
+            },
+        );
+    }
+
+    // what the two of you release walking your routes minute by minute, and
+    // whether you open any valve the elephant does
+    fn walk_both(
+        ts: &TunnelSystem,
+        routes: &TunnelRoutes,
+        you: &Vec<String>,
+        elephant: &Vec<String>,
+        minutes: i32,
+    ) -> (i32, bool) {
+        let full_route = |waypoints| ts.get_route_for_waypoints(routes, waypoints, minutes);
+        let pressure = ts.calculate_pressure_released(&full_route(you), minutes)
+            + ts.calculate_pressure_released(&full_route(elephant), minutes);
+        let shared = you[1..].iter().any(|valve| elephant[1..].contains(valve));
+        (pressure, shared)
+    }
+
+    #[test]
+    fn test_max_pressure_with_elephant_example() {
+        let lines: Vec<String> = EXAMPLE.iter().map(|l| l.to_string()).collect();
+        let mut ts = TunnelSystem::new();
+        ts.build_tunnel_system(&lines).unwrap();
+        let routes = get_routes(&ts);
+
+        let (max_pressure, you, elephant) = ts
+            .get_max_pressure_for_two(&routes, &"AA".to_string(), 26)
+            .unwrap();
+        assert_eq!(max_pressure, 1707);
+        assert_eq!(walk_both(&ts, &routes, &you, &elephant, 26), (1707, false));
+        // the puzzle's routes, though either of you could take either one
+        let mut both = vec![you, elephant];
+        both.sort();
+        assert_eq!(both, [["AA", "DD", "HH", "EE"], ["AA", "JJ", "BB", "CC"]]);
+    }
+
+    #[test]
+    fn test_explain_routes_for_two() {
+        let lines: Vec<String> = EXAMPLE.iter().map(|l| l.to_string()).collect();
+        let mut ts = TunnelSystem::new();
+        ts.build_tunnel_system(&lines).unwrap();
+        let routes = get_routes(&ts);
+        let full_route = |waypoints: [&str; 4]| {
+            let waypoints: Vec<String> = waypoints.iter().map(|v| v.to_string()).collect();
+            ts.get_route_for_waypoints(&routes, &waypoints, 26)
+        };
+        let you = full_route(["AA", "JJ", "BB", "CC"]);
+        let elephant = full_route(["AA", "DD", "HH", "EE"]);
+
+        let mut trace = Trace::new(true);
+        let pressure =
+            ts.calculate_pressure_released_by_two_traced(&you, &elephant, 26, &mut trace);
+        assert_eq!(pressure, 1707);
+        let explained = trace.render(narrate);
+        // the puzzle's own walkthrough
+        assert!(explained.starts_with(
+            "\n== Minute 1 ==\nNo valves are open.\nYou move to valve II.\n\
+             The elephant moves to valve DD.\n\n== Minute 2 ==\nNo valves are open.\n\
+             You move to valve JJ.\nThe elephant opens valve DD.\n"
+        ));
+        assert!(explained.ends_with(
+            "== Minute 26 ==\nValves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.\n"
+        ));
+    }
+
+    #[test]
+    fn test_unreachable_valves_are_left_out() {
+        let mut input = EXAMPLE.join("\n");
+        input.push_str("\nValve ZZ has flow rate=50; tunnel leads to valve YY");
+        input.push_str("\nValve YY has flow rate=0; tunnel leads to valve ZZ");
+        let ts = TunnelSystem::parse(&input).unwrap();
+        let start = "AA".to_string();
+
+        assert!(ts.get_shortest_route_between_valves(&start, &"ZZ".to_string()).is_err());
+        assert!(!get_routes(&ts).contains_key("ZZ"));
+        assert_eq!(ts.max_pressure(&start, 30).unwrap().0, 1651);
+        assert_eq!(ts.max_pressure_with_elephant(&start, 26).unwrap().0, 1707);
+        assert!(ts.max_pressure(&"QQ".to_string(), 30).is_err());
+    }
+
+    // the slow way for two: every way of sharing out the valves, with the
+    // best each of you can do with your share
+    fn get_max_pressure_for_two_by_enumeration(
+        ts: &TunnelSystem,
+        routes: &TunnelRoutes,
+        minutes: i32,
+    ) -> i32 {
+        let start = "AA".to_string();
+        let valves: Vec<&String> = routes.keys().filter(|v| **v != start).collect();
+        let only = |keep: &dyn Fn(usize) -> bool| -> TunnelRoutes {
+            let kept = |valve: &String| {
+                *valve == start || valves.iter().position(|v| *v == valve).map_or(false, keep)
+            };
+            routes
+                .iter()
+                .filter(|(valve, _)| kept(valve))
+                .map(|(valve, to)| {
+                    let to = to
+                        .iter()
+                        .filter(|(v, _)| kept(v))
+                        .map(|(v, r)| (v.clone(), r.clone()))
+                        .collect();
+                    (valve.clone(), to)
+                })
+                .collect()
+        };
+        let mut max_pressure = 0;
+        for share in 0..1u32 << valves.len() {
+            let yours = only(&|i| share & 1 << i != 0);
+            let elephants = only(&|i| share & 1 << i == 0);
+            let pressure = ts
+                .get_max_pressure_by_enumeration(&yours, &start, minutes)
+                .0
+                + ts.get_max_pressure_by_enumeration(&elephants, &start, minutes)
+                    .0;
+            max_pressure = max_pressure.max(pressure);
+        }
+        max_pressure
+    }
+
+    #[test]
+    fn test_max_pressure_with_elephant_agrees_with_enumeration() {
+        property::check(
+            "max pressure with elephant",
+            100,
+            random_tunnel_system,
+            |(lines, minutes)| {
+                let mut ts = TunnelSystem::new();
+                ts.build_tunnel_system(lines)?;
+                let routes = get_routes(&ts);
+                let (fast, you, elephant) =
+                    ts.get_max_pressure_for_two(&routes, &"AA".to_string(), *minutes)?;
+                let slow = get_max_pressure_for_two_by_enumeration(&ts, &routes, *minutes);
+                property::agree(fast, slow)?;
+                property::agree(
+                    walk_both(&ts, &routes, &you, &elephant, *minutes),
+                    (slow, false),
+                )
+            },
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use y2022_day16::shared;
+use y2022_day16::{NonZeroValveData, TunnelRoutes, TunnelSystem};
+use y2022_day16::{DAY, YEAR};
+
+use shared::memory::{self, CountingAllocator};
+use shared::trace::Trace;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+struct AOC {
+    lines: Vec<String>,
+}
+
+impl AOC {
+    fn new() -> AOC {
+        AOC { lines: Vec::new() }
+    }
+
+    // a buffered reader is returned from here
+    fn read_lines<P>(&self, filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let file: File = File::open(filename)?;
+        Ok(io::BufReader::new(file).lines())
+    }
+
+    // load all the data in one go
+    fn load_all_lines(&mut self, filename: &String) {
+        self.lines = Vec::new();
+        if let Ok(lines) = self.read_lines(filename) {
+            for l in lines {
+                if let Ok(line) = l {
+                    let line_data = line.trim();
+                    if line_data.len() > 0 {
+                        self.lines.push(line_data.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, None));
+    let mut aoc = AOC::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day16", &filename, |s| TunnelSystem::parse(s).map(|_| ()));
+        return;
+    }
+
+    let phase = memory::Phase::start("parse");
+    aoc.load_all_lines(&filename);
+
+    println!("Building tunnel system...");
+    let built = TunnelSystem::parse(&aoc.lines.join("\n"));
+    phase.finish();
+
+    // ts.print_tunnels();
+
+    if let Err(why) = &built {
+        println!("Couldn't build the tunnel system: {}", why);
+    }
+    if let Ok(ts) = built {
+        // get all the non-zero valves
+        println!("Finding shortest routes between valves with non-zero flow rate...");
+        let phase = memory::Phase::start("shortest routes");
+        let valves_path_data_result: Result<NonZeroValveData, String> =
+            ts.get_non_zero_valve_names();
+
+        let mut routes: TunnelRoutes = HashMap::new();
+        if valves_path_data_result.is_ok() {
+            let mut valves_path_data: NonZeroValveData = valves_path_data_result.unwrap();
+            valves_path_data.insert("AA".to_string(), 0);
+            let routes_result: Result<TunnelRoutes, String> =
+                ts.get_shortest_routes_between_valves(&valves_path_data);
+            if routes_result.is_ok() {
+                routes = routes_result.unwrap();
+            }
+        }
+
+        for (k, v) in &routes {
+            let valve = ts.valves().get(k).unwrap();
+            println!("{} -> flow rate: {}", k, valve.flow_rate);
+            for (k2, v2) in v {
+                println!("\t{} -> {:?}", k2, v2);
+            }
+        }
+
+        phase.finish();
+
+        println!("Finding the route that releases the most pressure in 30 minutes...");
+        let phase = memory::Phase::start("search");
+        // starting at AA, try the orders the valves can be opened in within 30 minutes
+        let (max_pressure, max_route) = ts.get_max_pressure(&routes, &"AA".to_string(), 30);
+        let max_full_route = ts.get_route_for_waypoints(&routes, &max_route, 30);
+        phase.finish();
+
+        println!("Max pressure released: {}", max_pressure);
+        println!("Max route: {:?}", max_route);
+        println!("Max full route: {:?}", max_full_route);
+
+        let mut trace = Trace::from_args();
+        if trace.is_enabled() {
+            ts.calculate_pressure_released_traced(&max_full_route, 30, &mut trace);
+            println!("{}", trace.render(y2022_day16::narrate));
+        }
+
+        println!("\nFinding the routes for you and the elephant in 26 minutes...");
+        let phase = memory::Phase::start("search with the elephant");
+        // the best each set of valves can do, and then the best two sets with no valve in common
+        let (max_pressure, your_route, elephant_route) =
+            match ts.get_max_pressure_for_two(&routes, &"AA".to_string(), 26) {
+                Ok(best) => best,
+                Err(why) => {
+                    println!("Couldn't share out the valves: {}", why);
+                    return;
+                }
+            };
+        let your_full_route = ts.get_route_for_waypoints(&routes, &your_route, 26);
+        let elephant_full_route = ts.get_route_for_waypoints(&routes, &elephant_route, 26);
+        phase.finish();
+
+        println!("Max pressure released with the elephant: {}", max_pressure);
+        println!("Your route: {:?}", your_route);
+        println!("Your full route: {:?}", your_full_route);
+        println!("Elephant route: {:?}", elephant_route);
+        println!("Elephant full route: {:?}", elephant_full_route);
+
+        let mut trace = Trace::from_args();
+        if trace.is_enabled() {
+            ts.calculate_pressure_released_by_two_traced(
+                &your_full_route,
+                &elephant_full_route,
+                26,
+                &mut trace,
+            );
+            println!("{}", trace.render(y2022_day16::narrate));
+        }
+    }
+}
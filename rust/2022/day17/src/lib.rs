@@ -0,0 +1,625 @@
+//! Day 17: Pyroclastic Flow.
+//!
+//! The input is the jets of hot gas that push falling rocks left (<) and
+//! right (>) in a chamber seven wide. Five shapes of rock fall in turn, and
+//! each is pushed by the next jet and then falls one, until it can't fall any
+//! further. [`Chamber::parse`] reads the jets, and the chamber is a
+//! [`Simulation`] that drops one rock per step. [`tower_height`] finds how tall
+//! the tower is after any number of rocks, a trillion included, by spotting
+//! when the rocks start to fall the same way over and over.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::checkpoint::{self, Checkpoint};
+use shared::lint::{Lint, Problem};
+use shared::replay::Record;
+use shared::simulation::Simulation;
+use shared::trace::{Event, Trace};
+use shared::viewport::{Grid, Viewport};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 17;
+
+/// How wide the chamber is.
+pub const WIDTH: i32 = 7;
+
+// the shapes in the order they fall, as their points of rock from the bottom
+// left corner, with y going up
+const ROCKS: [&[(i32, i32)]; 5] = [
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+// what the height is called when it overflows in --checked mode
+const HEIGHT: &str = "day17 tower height";
+
+// how far down from the top the tower is compared when looking for a repeat.
+// the rocks in the puzzle's inputs never get this far down into the tower, so
+// what is below it doesn't change where they land
+const SKYLINE_DEPTH: i32 = 64;
+
+// how many rows from the top of the tower are drawn
+const ROWS_SHOWN: i32 = 30;
+
+/// Reads the jets, -1 for a push left (<) and 1 for a push right (>), from a
+/// single line. Fails on anything else, or no jets at all.
+pub fn parse_jets(s: &str) -> Result<Vec<i32>, String> {
+    let mut jets: Vec<i32> = Vec::new();
+    for (i, c) in s.trim().chars().enumerate() {
+        match c {
+            '<' => jets.push(-1),
+            '>' => jets.push(1),
+            _ => return Err(format!("{:?} at {} is not a jet", c, i + 1)),
+        }
+    }
+    if jets.is_empty() {
+        return Err("there are no jets".to_string());
+    }
+    Ok(jets)
+}
+
+/// The chamber, with the rock that has come to rest in it and the jets that
+/// push the next one. Like day 14's cave, the rock is kept as a set of the
+/// points that are blocked, and a falling rock can move anywhere none of its
+/// points would be blocked, or outside the walls, or below the floor.
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    jets: Vec<i32>,
+    jet: usize, // the next jet to push
+    rocks: u64, // how many have come to rest
+    rocks_to_drop: u64,
+    height: i32,
+    blocks: HashSet<(i32, i32)>, // the rock as a set, for quick lookups
+    last_rock: Vec<(i32, i32)>,  // where the last rock came to rest
+}
+
+impl Chamber {
+    pub fn new(jets: Vec<i32>) -> Chamber {
+        Chamber {
+            jets,
+            jet: 0,
+            rocks: 0,
+            rocks_to_drop: 2022,
+            height: 0,
+            blocks: HashSet::new(),
+            last_rock: Vec::new(),
+        }
+    }
+
+    /// Reads the jets (see [`parse_jets`]) into an empty chamber that drops
+    /// 2022 rocks as a simulation.
+    pub fn parse(s: &str) -> Result<Chamber, String> {
+        Ok(Chamber::new(parse_jets(s)?))
+    }
+
+    /// How many rocks the simulation drops before it is done.
+    pub fn set_rocks_to_drop(&mut self, rocks: u64) {
+        self.rocks_to_drop = rocks;
+    }
+
+    /// How tall the tower of rock is.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// How many rocks have come to rest.
+    pub fn rocks(&self) -> u64 {
+        self.rocks
+    }
+
+    fn is_blocked(&self, p: (i32, i32)) -> bool {
+        p.0 < 0 || p.0 >= WIDTH || p.1 < 0 || self.blocks.contains(&p)
+    }
+
+    // whether a rock with its bottom left corner at x,y overlaps anything
+    fn fits(&self, rock: &[(i32, i32)], x: i32, y: i32) -> bool {
+        rock.iter()
+            .all(|(dx, dy)| !self.is_blocked((x + dx, y + dy)))
+    }
+
+    /// Drops the next rock from two in from the left wall and three above the
+    /// tower, pushed by the jets, until it comes to rest.
+    pub fn drop_rock(&mut self) {
+        let rock = ROCKS[(self.rocks % ROCKS.len() as u64) as usize];
+        let (mut x, mut y) = (2, self.height + 3);
+
+        loop {
+            let push = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            if self.fits(rock, x + push, y) {
+                x += push;
+            }
+            if !self.fits(rock, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        let points: Vec<(i32, i32)> = rock.iter().map(|(dx, dy)| (x + dx, y + dy)).collect();
+        self.add_rock(points);
+    }
+
+    fn add_rock(&mut self, points: Vec<(i32, i32)>) {
+        for p in &points {
+            self.blocks.insert(*p);
+            self.height = self.height.max(p.1 + 1);
+        }
+        self.last_rock = points;
+        self.rocks += 1;
+    }
+
+    // what decides where the following rocks land: the next shape, the next
+    // jet, and how far below the top the highest rock in each column is (up
+    // to SKYLINE_DEPTH, with the floor as deep as it gets)
+    fn skyline(&self) -> (usize, usize, [i32; WIDTH as usize]) {
+        let mut depths = [SKYLINE_DEPTH; WIDTH as usize];
+        for (x, depth) in depths.iter_mut().enumerate() {
+            for d in 0..SKYLINE_DEPTH {
+                if self.blocks.contains(&(x as i32, self.height - 1 - d)) {
+                    *depth = d;
+                    break;
+                }
+            }
+        }
+        ((self.rocks % ROCKS.len() as u64) as usize, self.jet, depths)
+    }
+
+    /// The top of the tower as the puzzle draws it: the walls (|), the rock
+    /// (#) with the last rock to come to rest (@), and the floor (+-------+)
+    /// once it is in sight. With the viewport flags, only the window they give
+    /// (see shared/viewport.rs).
+    pub fn render_state(&self) -> String {
+        if let Some(view) = Viewport::from_args() {
+            return view.render(self);
+        }
+        let top = self.height.max(3);
+        let bottom = (top - ROWS_SHOWN).max(-1);
+
+        let mut s = "".to_string();
+        for y in (bottom..=top).rev() {
+            for x in -1..=WIDTH {
+                s.push(self.glyph(x as i64, y as i64));
+            }
+            s.push('\n');
+        }
+        if bottom >= 0 {
+            s.push_str(&format!("({} rows below)\n", bottom));
+        }
+        s
+    }
+}
+
+// the chamber as the viewport sees it, walls and floor included, following
+// the last rock to come to rest
+impl Grid for Chamber {
+    const PRIORITY: &'static str = "@#+|-.";
+
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        ((-1, -1), (WIDTH as i64, self.height.max(3) as i64))
+    }
+
+    fn glyph(&self, x: i64, y: i64) -> char {
+        let p = (x as i32, y as i32);
+        let wall = p.0 < 0 || p.0 >= WIDTH;
+        if wall && p.1 < 0 {
+            '+'
+        } else if wall {
+            '|'
+        } else if p.1 < 0 {
+            '-'
+        } else if self.last_rock.contains(&p) {
+            '@'
+        } else if self.blocks.contains(&p) {
+            '#'
+        } else {
+            '.'
+        }
+    }
+
+    fn focus(&self) -> Option<(i64, i64)> {
+        self.last_rock.first().map(|(x, y)| (*x as i64, *y as i64))
+    }
+
+    fn y_up(&self) -> bool {
+        true
+    }
+}
+
+// each step is one rock, and it's done once rocks_to_drop have come to rest
+impl Simulation for Chamber {
+    type State = (u64, i32); // rocks, the height of the tower
+
+    fn step(&mut self) {
+        self.drop_rock();
+    }
+
+    fn is_done(&self) -> bool {
+        self.rocks >= self.rocks_to_drop
+    }
+
+    fn state(&self) -> (u64, i32) {
+        (self.rocks, self.height)
+    }
+
+    fn steps_taken(&self) -> u64 {
+        self.rocks
+    }
+
+    fn total_steps(&self) -> Option<u64> {
+        Some(self.rocks_to_drop)
+    }
+}
+
+// a checkpoint is the jets, which one is next, how many rocks have fallen
+// and are to fall, and every point of rock with the last rock's points last
+impl Checkpoint for Chamber {
+    const KIND: &'static str = "chamber";
+
+    fn to_checkpoint(&self) -> String {
+        let jets: String = self
+            .jets
+            .iter()
+            .map(|j| if *j < 0 { '<' } else { '>' })
+            .collect();
+        let mut earlier: Vec<(i32, i32)> = self
+            .blocks
+            .iter()
+            .filter(|p| !self.last_rock.contains(p))
+            .cloned()
+            .collect();
+        earlier.sort();
+        let mut s = String::new();
+        s.push_str(&format!("jets {}\n", jets));
+        s.push_str(&format!("jet {}\n", self.jet));
+        s.push_str(&format!("rocks {}\n", self.rocks));
+        s.push_str(&format!("drop {}\n", self.rocks_to_drop));
+        s.push_str(&format!("rock {}\n", points_to_string(&earlier)));
+        s.push_str(&format!("last {}\n", points_to_string(&self.last_rock)));
+        s
+    }
+
+    fn from_checkpoint(data: &str) -> Result<Chamber, String> {
+        let mut chamber = Chamber::new(parse_jets(checkpoint::value(data, "jets")?)?);
+        chamber.set_rocks_to_drop(checkpoint::parse(checkpoint::value(data, "drop")?, "drop")?);
+        chamber.add_rock(points_from_string(checkpoint::value(data, "rock")?)?);
+        chamber.add_rock(points_from_string(checkpoint::value(data, "last")?)?);
+        chamber.rocks = checkpoint::parse(checkpoint::value(data, "rocks")?, "rocks")?;
+        chamber.jet = checkpoint::parse(checkpoint::value(data, "jet")?, "jet")?;
+        if chamber.jet >= chamber.jets.len() {
+            return Err(format!("checkpoint has a bad jet: {}", chamber.jet));
+        }
+        Ok(chamber)
+    }
+}
+
+// a recording starts with a checkpoint, and each step is the next jet after
+// the rock came to rest and where its points are, "jet x,y x,y ..."
+impl Record for Chamber {
+    const KIND: &'static str = "chamber";
+
+    fn record_start(&self) -> String {
+        self.to_checkpoint()
+    }
+
+    fn record_step(&self) -> String {
+        format!("{} {}", self.jet, points_to_string(&self.last_rock))
+    }
+
+    fn replay_start(start: &str) -> Result<Chamber, String> {
+        Chamber::from_checkpoint(start)
+    }
+
+    fn replay_step(&mut self, step: &str) -> Result<(), String> {
+        let (jet, points) = match step.split_once(' ') {
+            Some((jet, points)) => (jet, points_from_string(points)?),
+            None => return Err(format!("{:?} is not a rock", step)),
+        };
+        self.jet = checkpoint::parse(jet, "jet")?;
+        self.add_rock(points);
+        Ok(())
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{}Rocks: {} Height: {}",
+            self.render_state(),
+            self.rocks,
+            self.height
+        )
+    }
+}
+
+fn points_to_string(points: &[(i32, i32)]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn points_from_string(s: &str) -> Result<Vec<(i32, i32)>, String> {
+    let mut points: Vec<(i32, i32)> = vec![];
+    for p in s.split_whitespace() {
+        match p.split_once(',') {
+            Some((x, y)) => points.push((
+                checkpoint::parse(x, "point")?,
+                checkpoint::parse(y, "point")?,
+            )),
+            None => return Err(format!("checkpoint has a bad point: {:?}", p)),
+        }
+    }
+    Ok(points)
+}
+
+/// Where the rocks start falling the same way over and over: after `start`
+/// rocks, every `length` rocks add `height` to the tower.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub start: u64,
+    pub length: u64,
+    pub height: u64,
+}
+
+/// The height of the tower after `rocks` rocks have fallen, pushed by `jets`.
+/// Rocks are dropped until the next shape, the next jet and the top of the
+/// tower are the same as after an earlier rock. From then on the same rocks
+/// land the same way, so each cycle adds the same height, and only what is
+/// left over after the last whole cycle has to be looked up. Also gives the
+/// cycle, if one was found before all the rocks had fallen.
+pub fn tower_height(jets: &[i32], rocks: u64) -> (u64, Option<Cycle>) {
+    tower_height_traced(jets, rocks, &mut Trace::default())
+}
+
+/// [`tower_height`], recording the cycle and how the height is made up from
+/// it in `trace` (see [`narrate`]).
+pub fn tower_height_traced(jets: &[i32], rocks: u64, trace: &mut Trace) -> (u64, Option<Cycle>) {
+    let mut chamber = Chamber::new(jets.to_vec());
+    // the height after each number of rocks, from none
+    let mut heights: Vec<u64> = vec![0];
+    let mut seen: HashMap<(usize, usize, [i32; WIDTH as usize]), u64> = HashMap::new();
+
+    while chamber.rocks() < rocks {
+        chamber.drop_rock();
+        heights.push(chamber.height() as u64);
+        let start = match seen.insert(chamber.skyline(), chamber.rocks()) {
+            Some(start) => start,
+            None => continue,
+        };
+        let cycle = Cycle {
+            start,
+            length: chamber.rocks() - start,
+            height: heights[chamber.rocks() as usize] - heights[start as usize],
+        };
+        let cycles = (rocks - start) / cycle.length;
+        let left_over = (rocks - start) % cycle.length;
+        let height = checked::add(
+            HEIGHT,
+            heights[(start + left_over) as usize],
+            checked::mul(HEIGHT, cycles, cycle.height),
+        );
+        trace.event(
+            "cycle",
+            &[
+                ("rocks", &chamber.rocks()),
+                ("start", &start),
+                ("length", &cycle.length),
+                ("height", &cycle.height),
+            ],
+        );
+        trace.event(
+            "height",
+            &[
+                ("rocks", &rocks),
+                ("start", &start),
+                ("cycles", &cycles),
+                ("left_over", &left_over),
+                ("height", &height),
+            ],
+        );
+        return (height, Some(cycle));
+    }
+    trace.event(
+        "dropped",
+        &[("rocks", &rocks), ("height", &chamber.height())],
+    );
+    (chamber.height() as u64, None)
+}
+
+/// One line of the explanation for an event recorded by
+/// [`tower_height_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "cycle" => format!(
+            "After {} rocks the next shape, the next jet and the top of the tower are the same \
+             as after {}, so from then on every {} rocks add {} to the height.",
+            event.get("rocks"),
+            event.get("start"),
+            event.get("length"),
+            event.get("height")
+        ),
+        "height" => format!(
+            "{} rocks are the first {}, {} cycles and {} more, so the tower is {} units tall.",
+            event.get("rocks"),
+            event.get("start"),
+            event.get("cycles"),
+            event.get("left_over"),
+            event.get("height")
+        ),
+        "dropped" => format!(
+            "All {} rocks fell before they started repeating, and the tower is {} units tall.",
+            event.get("rocks"),
+            event.get("height")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+/// Checks the jets the way [`parse_jets`] reads them, but finds every
+/// character that isn't a jet, with its line and column, and any jets after
+/// the first line.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut first_line: Option<usize> = None;
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match first_line {
+            Some(first) => {
+                lint.at(i, format!("the jets should all be on line {}", first + 1));
+                continue;
+            }
+            None => first_line = Some(i),
+        }
+        for (column, c) in line.chars().enumerate() {
+            if c != '<' && c != '>' {
+                lint.at(i, format!("{:?} in column {} is not a jet", c, column + 1));
+            }
+        }
+    }
+    if first_line.is_none() {
+        lint.whole("there are no jets");
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+
+    #[test]
+    fn test_parse_jets() {
+        assert_eq!(parse_jets("<>>\n"), Ok(vec![-1, 1, 1]));
+        assert_eq!(parse_jets("<x>"), Err("'x' at 2 is not a jet".to_string()));
+        assert!(parse_jets("\n").is_err());
+    }
+
+    #[test]
+    fn test_example() {
+        let mut chamber = Chamber::parse(EXAMPLE).unwrap();
+        while !chamber.is_done() {
+            chamber.step();
+        }
+        assert_eq!(chamber.height(), 3068);
+
+        let jets = parse_jets(EXAMPLE).unwrap();
+        assert_eq!(tower_height(&jets, 2022).0, 3068);
+        let (height, cycle) = tower_height(&jets, 1_000_000_000_000);
+        assert_eq!(height, 1514285714288);
+        assert_eq!(cycle.map(|c| c.length), Some(35));
+    }
+
+    #[test]
+    fn test_explain_the_cycle() {
+        let jets = parse_jets(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        tower_height_traced(&jets, 1_000_000_000_000, &mut trace);
+        assert_eq!(
+            trace.render(narrate),
+            "After 63 rocks the next shape, the next jet and the top of the tower are the same \
+             as after 28, so from then on every 35 rocks add 53 to the height.\n\
+             1000000000000 rocks are the first 28, 28571428570 cycles and 22 more, \
+             so the tower is 1514285714288 units tall.\n"
+        );
+
+        let mut trace = Trace::new(true);
+        tower_height_traced(&jets, 10, &mut trace);
+        assert_eq!(
+            trace.render(narrate),
+            "All 10 rocks fell before they started repeating, and the tower is 17 units tall.\n"
+        );
+    }
+
+    #[test]
+    fn test_first_rocks_as_the_puzzle_draws_them() {
+        let mut chamber = Chamber::parse(EXAMPLE).unwrap();
+        for _ in 0..3 {
+            chamber.drop_rock();
+        }
+        assert!(chamber.render_state().ends_with(
+            "|.......|\n\
+             |..@....|\n\
+             |..@....|\n\
+             |@@@#...|\n\
+             |..###..|\n\
+             |...#...|\n\
+             |..####.|\n\
+             +-------+\n"
+        ));
+    }
+
+    #[test]
+    fn test_tower_height_agrees_with_dropping_every_rock() {
+        let jets = parse_jets(EXAMPLE).unwrap();
+        let mut chamber = Chamber::new(jets.clone());
+        for rocks in 1..=1000 {
+            chamber.drop_rock();
+            assert_eq!(
+                tower_height(&jets, rocks).0,
+                chamber.height() as u64,
+                "{} rocks",
+                rocks
+            );
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let mut chamber = Chamber::parse(EXAMPLE).unwrap();
+        for _ in 0..100 {
+            chamber.drop_rock();
+        }
+        let mut resumed = Chamber::from_checkpoint(&chamber.to_checkpoint()).unwrap();
+        assert_eq!(resumed.state(), chamber.state());
+        for _ in 0..100 {
+            chamber.drop_rock();
+            resumed.drop_rock();
+        }
+        assert_eq!(resumed.state(), chamber.state());
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut chamber = Chamber::parse(EXAMPLE).unwrap();
+        chamber.set_rocks_to_drop(50);
+        let mut recording = format!("# aoc recording chamber\n{}---\n", chamber.record_start());
+        while !chamber.is_done() {
+            chamber.step();
+            recording.push_str(&chamber.record_step());
+            recording.push('\n');
+        }
+
+        let replayed: Chamber = shared::replay::replay_to(&recording, usize::MAX).unwrap();
+        assert_eq!(replayed.state(), (50, chamber.height()));
+        assert_eq!(replayed.render_state(), chamber.render_state());
+        let halfway: Chamber = shared::replay::replay_to(&recording, 25).unwrap();
+        assert_eq!(halfway.rocks(), 25);
+    }
+
+    #[test]
+    fn test_lint() {
+        let problems: Vec<String> = lint("<<x>\n\n>>\n").iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "line 1: 'x' in column 3 is not a jet",
+                "line 3: the jets should all be on line 1",
+            ]
+        );
+        assert_eq!(lint(EXAMPLE), []);
+        assert_eq!(lint("\n")[0].to_string(), "there are no jets");
+    }
+}
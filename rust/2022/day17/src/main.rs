@@ -0,0 +1,112 @@
+use std::fs;
+
+use y2022_day17::shared;
+use y2022_day17::{parse_jets, tower_height, tower_height_traced, Chamber};
+use y2022_day17::{DAY, YEAR};
+
+use shared::checkpoint::Checkpointer;
+use shared::replay::Recorder;
+use shared::simulation::{self, CheckpointObserver, ProgressObserver, Runner};
+use shared::trace::Trace;
+
+struct Aoc {
+    data: String,
+}
+
+impl Aoc {
+    fn new() -> Aoc {
+        Aoc {
+            data: String::new(),
+        }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        self.data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, None));
+    let mut aoc = Aoc::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day17", &filename, |s| parse_jets(s).map(|_| ()));
+        return;
+    }
+
+    aoc.load_all_data(&filename);
+    let jets = match parse_jets(&aoc.data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(jets) => jets,
+    };
+    println!("Jets: {}", jets.len());
+
+    let mut checkpointer = Checkpointer::from_args("./day17.checkpoint");
+
+    let mut chamber = match checkpointer.resume::<Chamber>() {
+        Some(Ok(chamber)) => {
+            println!(
+                "Resuming from {} with {} rocks\n",
+                checkpointer.path(),
+                chamber.rocks()
+            );
+            chamber
+        }
+        Some(Err(why)) => panic!("couldn't resume: {}", why),
+        None => Chamber::new(jets.clone()),
+    };
+
+    // part 1 drops every rock
+    let runner = Runner::new()
+        .observe(ProgressObserver::new("Rocks"))
+        .observe(CheckpointObserver::new(&mut checkpointer))
+        .observe(Recorder::from_args());
+    simulation::run_from_args(&mut chamber, runner);
+
+    println!("{}", chamber.render_state());
+    println!(
+        "Height after {} rocks: {}",
+        chamber.rocks(),
+        chamber.height()
+    );
+
+    // part 2 has far too many to drop, so it finds where they start repeating
+    let rocks: u64 = 1_000_000_000_000;
+    let (height, cycle) = tower_height(&jets, rocks);
+    if let Some(cycle) = cycle {
+        println!(
+            "After {} rocks every {} rocks add {} to the height",
+            cycle.start, cycle.length, cycle.height
+        );
+    }
+    println!("Height after {} rocks: {}", rocks, height);
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        tower_height_traced(&jets, rocks, &mut trace);
+        println!("\n{}", trace.render(y2022_day17::narrate));
+    }
+}
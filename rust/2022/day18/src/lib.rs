@@ -0,0 +1,325 @@
+//! Day 18: Boiling Boulders.
+//!
+//! The input is the 1x1x1 cubes of a lava droplet, one `x,y,z` per line.
+//! [`Droplet::parse`] reads them, [`Droplet::surface_area`] counts the faces
+//! of the cubes that don't touch another cube, and
+//! [`Droplet::exterior_surface_area`] counts only the faces the steam outside
+//! can reach, leaving out the air pockets trapped inside.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::graph;
+use shared::lint::{Lint, Problem};
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 18;
+
+/// A cube, or the air where a cube could be.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Point3 {
+        Point3 { x, y, z }
+    }
+
+    /// The six points that share a face with this one.
+    pub fn neighbours(&self) -> [Point3; 6] {
+        let Point3 { x, y, z } = *self;
+        [
+            Point3::new(x - 1, y, z),
+            Point3::new(x + 1, y, z),
+            Point3::new(x, y - 1, z),
+            Point3::new(x, y + 1, z),
+            Point3::new(x, y, z - 1),
+            Point3::new(x, y, z + 1),
+        ]
+    }
+
+    // reads "x,y,z"
+    fn parse(s: &str) -> Option<Point3> {
+        let nums: Vec<Result<i32, _>> = s.trim().split(',').map(|n| n.trim().parse()).collect();
+        match nums[..] {
+            [Ok(x), Ok(y), Ok(z)] => Some(Point3::new(x, y, z)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// The cubes of a lava droplet.
+pub struct Droplet {
+    cubes: HashSet<Point3>,
+}
+
+impl Droplet {
+    pub fn new(cubes: HashSet<Point3>) -> Droplet {
+        Droplet { cubes }
+    }
+
+    /// Reads one cube per line as `x,y,z`. Blank lines are skipped. Fails on
+    /// a line that isn't three numbers.
+    pub fn parse(s: &str) -> Result<Droplet, String> {
+        let mut cubes: HashSet<Point3> = HashSet::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match Point3::parse(line) {
+                Some(cube) => cubes.insert(cube),
+                None => return Err(format!("line {}: {:?} is not a cube", i + 1, line.trim())),
+            };
+        }
+        Ok(Droplet::new(cubes))
+    }
+
+    pub fn cubes(&self) -> &HashSet<Point3> {
+        &self.cubes
+    }
+
+    /// Every face of every cube that isn't against another cube, the air
+    /// pockets inside the droplet included.
+    pub fn surface_area(&self) -> usize {
+        self.cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours())
+            .filter(|p| !self.cubes.contains(p))
+            .count()
+    }
+
+    /// The faces of the cubes that the outside air touches, found by filling
+    /// the air around the droplet from a corner of a box one bigger than it
+    /// on every side (see [`flood_fill`]).
+    pub fn exterior_surface_area(&self) -> usize {
+        self.exterior_surface_area_traced(&mut Trace::default())
+    }
+
+    /// [`Droplet::exterior_surface_area`], recording the box the air is
+    /// filled in, each pocket of air the fill can't reach and the faces
+    /// counted in `trace` (see [`narrate`]).
+    pub fn exterior_surface_area_traced(&self, trace: &mut Trace) -> usize {
+        let outside = self.outside();
+        let (min, max) = self.bounds();
+        trace.event(
+            "fill",
+            &[("from", &min), ("to", &max), ("reached", &outside.len())],
+        );
+        if trace.is_enabled() {
+            let mut trapped: Vec<Point3> = vec![];
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        let p = Point3::new(x, y, z);
+                        if !self.cubes.contains(&p) && !outside.contains_key(&p) {
+                            trapped.push(p);
+                        }
+                    }
+                }
+            }
+            for p in trapped {
+                trace.event("trapped", &[("at", &p)]);
+            }
+        }
+        let exterior = self
+            .cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours())
+            .filter(|p| outside.contains_key(p))
+            .count();
+        trace.event(
+            "exterior",
+            &[("surface", &self.surface_area()), ("exterior", &exterior)],
+        );
+        exterior
+    }
+
+    // the smallest and largest point of a box around every cube, with a gap
+    // of one all round so the air can get round the outside of the droplet
+    fn bounds(&self) -> (Point3, Point3) {
+        let min = |f: fn(&Point3) -> i32| self.cubes.iter().map(f).min().unwrap_or(0) - 1;
+        let max = |f: fn(&Point3) -> i32| self.cubes.iter().map(f).max().unwrap_or(0) + 1;
+        (
+            Point3::new(min(|p| p.x), min(|p| p.y), min(|p| p.z)),
+            Point3::new(max(|p| p.x), max(|p| p.y), max(|p| p.z)),
+        )
+    }
+
+    // the air inside the box that can be reached from its corner, with how
+    // far away each point is
+    fn outside(&self) -> HashMap<Point3, usize> {
+        let (min, max) = self.bounds();
+        let in_box = |p: &Point3| {
+            (min.x..=max.x).contains(&p.x)
+                && (min.y..=max.y).contains(&p.y)
+                && (min.z..=max.z).contains(&p.z)
+        };
+        flood_fill(min, |p| in_box(p) && !self.cubes.contains(p))
+    }
+}
+
+/// One line of the explanation for an event recorded by
+/// [`Droplet::exterior_surface_area_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "fill" => format!(
+            "The steam fills the box from {} to {} around the droplet, reaching {} places.",
+            event.get("from"),
+            event.get("to"),
+            event.get("reached")
+        ),
+        "trapped" => format!(
+            "The air at {} is trapped inside the droplet.",
+            event.get("at")
+        ),
+        "exterior" => format!(
+            "Of the {} faces that aren't against another cube, {} are reached by the steam.",
+            event.get("surface"),
+            event.get("exterior")
+        ),
+        _ => shared::trace::describe(event),
+    }
+}
+
+/// A breadth first search from `start` through the six faces of a cube,
+/// moving to any point `open` allows. Returns every point reached with its
+/// distance from the start.
+pub fn flood_fill(start: Point3, open: impl Fn(&Point3) -> bool) -> HashMap<Point3, usize> {
+    if !open(&start) {
+        return HashMap::new();
+    }
+    graph::distances(start, |p| {
+        p.neighbours().into_iter().filter(|next| open(next))
+    })
+}
+
+/// Checks the cubes the way [`Droplet::parse`] reads them, but finds every
+/// problem rather than the first, each on its line: a line that isn't a cube,
+/// and a cube that is there twice.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut lines_by_cube: HashMap<Point3, usize> = HashMap::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match Point3::parse(line) {
+            Some(cube) => match lines_by_cube.get(&cube) {
+                Some(first) => {
+                    lint.at(i, format!("cube {} is already on line {}", cube, first + 1))
+                }
+                None => {
+                    lines_by_cube.insert(cube, i);
+                }
+            },
+            None => lint.at(i, format!("{:?} is not a cube", line)),
+        }
+    }
+    if lines_by_cube.is_empty() {
+        lint.whole("there are no cubes");
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n\
+                           1,2,5\n3,2,5\n2,1,5\n2,3,5\n";
+
+    #[test]
+    fn test_example() {
+        let droplet = Droplet::parse(EXAMPLE).unwrap();
+        assert_eq!(droplet.cubes().len(), 13);
+        assert_eq!(droplet.surface_area(), 64);
+        assert_eq!(droplet.exterior_surface_area(), 58);
+
+        let two = Droplet::parse("1,1,1\n2,1,1\n").unwrap();
+        assert_eq!(two.surface_area(), 10);
+        assert_eq!(two.exterior_surface_area(), 10);
+    }
+
+    #[test]
+    fn test_explain_the_exterior() {
+        let droplet = Droplet::parse(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(droplet.exterior_surface_area_traced(&mut trace), 58);
+        // the box is 5x5x8, less the 13 cubes and the pocket
+        assert_eq!(
+            trace.render(narrate),
+            "The steam fills the box from 0,0,0 to 4,4,7 around the droplet, reaching 186 places.\n\
+             The air at 2,2,5 is trapped inside the droplet.\n\
+             Of the 64 faces that aren't against another cube, 58 are reached by the steam.\n"
+        );
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        // a 3x3x3 cube with the middle taken out, so the inside faces of the
+        // hole only count without the flood fill
+        let mut cubes: HashSet<Point3> = HashSet::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                for z in 0..3 {
+                    cubes.insert(Point3::new(x, y, z));
+                }
+            }
+        }
+        cubes.remove(&Point3::new(1, 1, 1));
+        let droplet = Droplet::new(cubes);
+        assert_eq!(droplet.surface_area(), 54 + 6);
+        assert_eq!(droplet.exterior_surface_area(), 54);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        // a 3x3x3 box of air from its corner
+        let reached = flood_fill(Point3::new(0, 0, 0), |p| {
+            [p.x, p.y, p.z].iter().all(|n| (0..3).contains(n))
+        });
+        assert_eq!(reached.len(), 27);
+        assert_eq!(reached.get(&Point3::new(2, 2, 2)), Some(&6));
+        assert!(flood_fill(Point3::new(0, 0, 0), |_| false).is_empty());
+    }
+
+    #[test]
+    fn test_parse_and_lint() {
+        assert!(Droplet::parse("1,2,3\n\n4,5,6\n").is_ok());
+        assert_eq!(
+            Droplet::parse("1,2,3\n1,2\n").err(),
+            Some("line 2: \"1,2\" is not a cube".to_string())
+        );
+        let problems: Vec<String> = lint("1,2,3\n1,2\n4,5,x\n1,2,3\n")
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                "line 2: \"1,2\" is not a cube",
+                "line 3: \"4,5,x\" is not a cube",
+                "line 4: cube 1,2,3 is already on line 1",
+            ]
+        );
+        assert_eq!(lint(EXAMPLE), []);
+        assert_eq!(lint("")[0].to_string(), "there are no cubes");
+    }
+}
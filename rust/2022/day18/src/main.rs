@@ -0,0 +1,72 @@
+use std::fs;
+
+use y2022_day18::shared;
+use y2022_day18::Droplet;
+use y2022_day18::{DAY, YEAR};
+
+use shared::trace::Trace;
+
+struct Aoc {
+    data: String,
+}
+
+impl Aoc {
+    fn new() -> Aoc {
+        Aoc {
+            data: String::new(),
+        }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        self.data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, None));
+    let mut aoc = Aoc::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day18", &filename, |s| Droplet::parse(s).map(|_| ()));
+        return;
+    }
+
+    aoc.load_all_data(&filename);
+    let droplet = match Droplet::parse(&aoc.data) {
+        Err(why) => panic!("couldn't parse {}: {}", filename, why),
+        Ok(droplet) => droplet,
+    };
+
+    println!("Cubes: {}", droplet.cubes().len());
+    println!("Surface area: {}", droplet.surface_area());
+    println!("Exterior surface area: {}", droplet.exterior_surface_area());
+
+    let mut trace = Trace::from_args();
+    if trace.is_enabled() {
+        droplet.exterior_surface_area_traced(&mut trace);
+        println!("\n{}", trace.render(y2022_day18::narrate));
+    }
+}
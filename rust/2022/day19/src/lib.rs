@@ -0,0 +1,501 @@
+//! Day 19: Not Enough Minerals.
+//!
+//! The input is blueprints for a robot factory, saying how much ore, clay and
+//! obsidian each kind of robot costs. Each robot collects one of its kind
+//! a minute, the factory builds one robot a minute, and the aim is to open as
+//! many geodes as possible in the time. [`parse_blueprints`] reads them, and
+//! [`Blueprint::max_geodes`] searches the build schedules with branch and
+//! bound (see shared/search.rs). [`quality_level_sum`] and
+//! [`first_three_product`] are the answers, with every blueprint searched on
+//! its own thread.
+
+use std::fmt;
+use std::thread;
+
+#[path = "../../../shared/mod.rs"]
+pub mod shared;
+
+use shared::checked;
+use shared::lint::{Lint, Problem};
+use shared::search::{self, Best};
+use shared::trace::{Event, Trace};
+
+/// The Advent of Code event this puzzle is from.
+pub const YEAR: u32 = 2022;
+/// The puzzle's day in the event.
+pub const DAY: u32 = 19;
+
+// what the answers are called when they overflow in --checked mode
+const QUALITY: &str = "day19 quality level sum";
+const PRODUCT: &str = "day19 product of geodes";
+
+/// The four things a robot can collect, and so the four kinds of robot.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Robot {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Robot {
+    const ALL: [Robot; 4] = [Robot::Ore, Robot::Clay, Robot::Obsidian, Robot::Geode];
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for Robot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Robot::Ore => "ore",
+            Robot::Clay => "clay",
+            Robot::Obsidian => "obsidian",
+            Robot::Geode => "geode",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What each robot costs, as ore, clay and obsidian (geodes are never spent).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blueprint {
+    pub id: u32,
+    pub costs: [[u32; 3]; 4],
+}
+
+// the words of a blueprint, with {} where the numbers go
+const TEMPLATE: &str = "Blueprint {}: Each ore robot costs {} ore. \
+                        Each clay robot costs {} ore. \
+                        Each obsidian robot costs {} ore and {} clay. \
+                        Each geode robot costs {} ore and {} obsidian.";
+
+impl Blueprint {
+    /// Reads a blueprint like the puzzle's, which can be split over lines.
+    /// Returns None if the words aren't the puzzle's.
+    pub fn parse(s: &str) -> Option<Blueprint> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let template: Vec<&str> = TEMPLATE.split_whitespace().collect();
+        if words.len() != template.len() {
+            return None;
+        }
+        let mut numbers: Vec<u32> = Vec::new();
+        for (word, expected) in words.iter().zip(template) {
+            match expected.split_once("{}") {
+                Some((before, after)) => {
+                    let number = word.strip_prefix(before)?.strip_suffix(after)?;
+                    numbers.push(number.parse().ok()?);
+                }
+                None if *word == expected => {}
+                None => return None,
+            }
+        }
+        Some(Blueprint {
+            id: numbers[0],
+            costs: [
+                [numbers[1], 0, 0],
+                [numbers[2], 0, 0],
+                [numbers[3], numbers[4], 0],
+                [numbers[5], 0, numbers[6]],
+            ],
+        })
+    }
+
+    /// The most geodes that can be opened in `minutes`, starting with one
+    /// ore robot, with the schedule that does it and how much of the search
+    /// tree was looked at.
+    pub fn max_geodes(&self, minutes: u32) -> Best<Factory> {
+        let start = Factory {
+            minutes_left: minutes,
+            robots: [1, 0, 0, 0],
+            stock: [0, 0, 0, 0],
+        };
+        search::best(&Schedules::new(self), start)
+    }
+}
+
+/// Reads the blueprints, each starting with "Blueprint", whether each is on
+/// one line or split over several. Fails on one that isn't the puzzle's
+/// words, saying which.
+pub fn parse_blueprints(s: &str) -> Result<Vec<Blueprint>, String> {
+    let mut blueprints: Vec<Blueprint> = Vec::new();
+    for text in split_blueprints(s) {
+        match Blueprint::parse(&text) {
+            Some(blueprint) => blueprints.push(blueprint),
+            None => return Err(format!("{:?} is not a blueprint", text)),
+        }
+    }
+    Ok(blueprints)
+}
+
+// each blueprint's text with its words joined by single spaces, and the line
+// it starts on
+fn split_blueprints_by_line(s: &str) -> Vec<(usize, String)> {
+    let mut blueprints: Vec<(usize, Vec<&str>)> = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        for word in line.split_whitespace() {
+            if word == "Blueprint" || blueprints.is_empty() {
+                blueprints.push((i, Vec::new()));
+            }
+            blueprints.last_mut().unwrap().1.push(word);
+        }
+    }
+    blueprints
+        .into_iter()
+        .map(|(i, words)| (i, words.join(" ")))
+        .collect()
+}
+
+fn split_blueprints(s: &str) -> Vec<String> {
+    split_blueprints_by_line(s)
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// The state of the factory between building robots: how many minutes are
+/// left, how many robots of each kind there are, and how much of each thing
+/// has been collected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Factory {
+    pub minutes_left: u32,
+    pub robots: [u32; 4],
+    pub stock: [u32; 4],
+}
+
+impl Factory {
+    /// The geodes there will be once the time is up if no more robots are
+    /// built.
+    pub fn geodes(&self) -> u32 {
+        self.stock[Robot::Geode.index()] + self.robots[Robot::Geode.index()] * self.minutes_left
+    }
+
+    // the robot that was built between this and the next state
+    fn built(&self, next: &Factory) -> Option<Robot> {
+        Robot::ALL
+            .into_iter()
+            .find(|r| next.robots[r.index()] > self.robots[r.index()])
+    }
+}
+
+/// Which robot is built at which minute (counting from 1) along a path of
+/// factory states from [`Blueprint::max_geodes`].
+pub fn schedule(path: &[Factory], minutes: u32) -> Vec<(u32, Robot)> {
+    path.windows(2)
+        .filter_map(|pair| {
+            let robot = pair[0].built(&pair[1])?;
+            // it is built in the last minute before the next state
+            Some((minutes - pair[1].minutes_left, robot))
+        })
+        .collect()
+}
+
+// the build schedules for a blueprint as a search tree. each choice is the
+// next robot to build, waiting as long as it takes to afford it
+struct Schedules<'a> {
+    blueprint: &'a Blueprint,
+    // there's no point having more robots collecting something than can be
+    // spent in a minute, as only one robot is built a minute
+    most_needed: [u32; 3],
+}
+
+impl Schedules<'_> {
+    fn new(blueprint: &Blueprint) -> Schedules<'_> {
+        let mut most_needed = [0; 3];
+        for costs in &blueprint.costs {
+            for (most, cost) in most_needed.iter_mut().zip(costs) {
+                *most = (*most).max(*cost);
+            }
+        }
+        Schedules {
+            blueprint,
+            most_needed,
+        }
+    }
+
+    // how many minutes until the factory can afford a robot, None if it never
+    // can with the robots it has
+    fn minutes_to_afford(&self, factory: &Factory, robot: Robot) -> Option<u32> {
+        let mut wait = 0;
+        for (i, cost) in self.blueprint.costs[robot.index()].iter().enumerate() {
+            if factory.stock[i] >= *cost {
+                continue;
+            }
+            if factory.robots[i] == 0 {
+                return None;
+            }
+            let short = cost - factory.stock[i];
+            wait = wait.max(short.div_ceil(factory.robots[i]));
+        }
+        Some(wait)
+    }
+}
+
+impl search::Problem for Schedules<'_> {
+    type Node = Factory;
+
+    fn value(&self, factory: &Factory) -> i64 {
+        factory.geodes() as i64
+    }
+
+    // as if a geode robot could be built every minute from now on
+    fn bound(&self, factory: &Factory) -> i64 {
+        let t = factory.minutes_left as i64;
+        factory.geodes() as i64 + t * (t - 1) / 2
+    }
+
+    // geode robots first, as they are what a good schedule ends up with
+    fn children(&self, factory: &Factory) -> Vec<Factory> {
+        let mut children: Vec<Factory> = Vec::new();
+        for robot in Robot::ALL.into_iter().rev() {
+            let i = robot.index();
+            if robot != Robot::Geode && factory.robots[i] >= self.most_needed[i] {
+                continue;
+            }
+            let wait = match self.minutes_to_afford(factory, robot) {
+                Some(wait) => wait,
+                None => continue,
+            };
+            // a robot built in the last minute collects nothing
+            if wait + 1 >= factory.minutes_left {
+                continue;
+            }
+            let mut next = *factory;
+            next.minutes_left -= wait + 1;
+            for (stock, robots) in next.stock.iter_mut().zip(factory.robots) {
+                *stock += robots * (wait + 1);
+            }
+            for (stock, cost) in next.stock.iter_mut().zip(self.blueprint.costs[i]) {
+                *stock -= cost;
+            }
+            next.robots[i] += 1;
+            children.push(next);
+        }
+        children
+    }
+}
+
+/// The most geodes each blueprint can open in `minutes`, in the same order,
+/// searching each blueprint on its own thread.
+pub fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<Best<Factory>> {
+    thread::scope(|s| {
+        let searches: Vec<_> = blueprints
+            .iter()
+            .map(|blueprint| s.spawn(move || blueprint.max_geodes(minutes)))
+            .collect();
+        searches
+            .into_iter()
+            .map(|search| search.join().expect("a blueprint's search panicked"))
+            .collect()
+    })
+}
+
+/// Part 1: each blueprint's id times the most geodes it can open in 24
+/// minutes, added up.
+pub fn quality_level_sum(blueprints: &[Blueprint]) -> u32 {
+    quality_level_sum_traced(blueprints, &mut Trace::default())
+}
+
+/// [`quality_level_sum`], recording each blueprint's best schedule and its
+/// quality level, and then the sum, in `trace` (see [`narrate`]).
+pub fn quality_level_sum_traced(blueprints: &[Blueprint], trace: &mut Trace) -> u32 {
+    let geodes = max_geodes(blueprints, 24);
+    for (blueprint, best) in blueprints.iter().zip(&geodes) {
+        for (minute, robot) in schedule(&best.path, 24) {
+            trace.event(
+                "build",
+                &[
+                    ("blueprint", &blueprint.id),
+                    ("minute", &minute),
+                    ("robot", &robot),
+                ],
+            );
+        }
+        trace.event(
+            "quality",
+            &[("blueprint", &blueprint.id), ("geodes", &best.value)],
+        );
+    }
+    let sum = checked::sum(
+        QUALITY,
+        blueprints
+            .iter()
+            .zip(geodes)
+            .map(|(blueprint, best)| checked::mul(QUALITY, blueprint.id, best.value as u32)),
+    );
+    trace.event("sum", &[("sum", &sum)]);
+    sum
+}
+
+/// One line of the explanation for an event recorded by
+/// [`quality_level_sum_traced`].
+pub fn narrate(event: &Event) -> String {
+    match event.kind {
+        "build" => {
+            let (a, job) = match event.get("robot") {
+                "ore" | "obsidian" => ("an", "collecting"),
+                "geode" => ("a", "cracking"),
+                _ => ("a", "collecting"),
+            };
+            format!(
+                "Blueprint {} builds {} {}-{} robot in minute {}.",
+                event.get("blueprint"),
+                a,
+                event.get("robot"),
+                job,
+                event.get("minute")
+            )
+        }
+        "quality" => {
+            let id: u32 = event.get("blueprint").parse().unwrap_or(0);
+            let geodes: u32 = event.get("geodes").parse().unwrap_or(0);
+            format!(
+                "Blueprint {} opens {} geodes, a quality level of {} * {} = {}.",
+                id,
+                geodes,
+                id,
+                geodes,
+                id * geodes
+            )
+        }
+        "sum" => format!("The quality levels add up to {}.", event.get("sum")),
+        _ => shared::trace::describe(event),
+    }
+}
+
+/// Part 2: the most geodes each of the first three blueprints (or as many as
+/// there are) can open in 32 minutes, multiplied together.
+pub fn first_three_product(blueprints: &[Blueprint]) -> u64 {
+    let first_three = &blueprints[..blueprints.len().min(3)];
+    checked::product(
+        PRODUCT,
+        max_geodes(first_three, 32)
+            .iter()
+            .map(|best| best.value as u64),
+    )
+}
+
+/// Checks the blueprints the way [`parse_blueprints`] reads them, but finds
+/// every one that isn't the puzzle's words, on the line it starts, and any
+/// blueprint number used twice.
+pub fn lint(s: &str) -> Vec<Problem> {
+    let mut lint = Lint::new();
+    let mut lines_by_id: Vec<(u32, usize)> = Vec::new();
+    for (i, text) in split_blueprints_by_line(s) {
+        match Blueprint::parse(&text) {
+            Some(blueprint) => match lines_by_id.iter().find(|(id, _)| *id == blueprint.id) {
+                Some((_, first)) => lint.at(
+                    i,
+                    format!(
+                        "blueprint {} is already on line {}",
+                        blueprint.id,
+                        first + 1
+                    ),
+                ),
+                None => lines_by_id.push((blueprint.id, i)),
+            },
+            None => lint.at(i, format!("{:?} is not a blueprint", text)),
+        }
+    }
+    if lines_by_id.is_empty() {
+        lint.whole("there are no blueprints");
+    }
+    lint.problems()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // as the puzzle shows it, over several lines
+    const EXAMPLE: &str = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn test_parse_blueprints() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(blueprints[1].id, 2);
+        assert_eq!(
+            blueprints[1].costs,
+            [[2, 0, 0], [3, 0, 0], [3, 8, 0], [3, 0, 12]]
+        );
+        // the same on one line each, as the real inputs are
+        let one_per_line: String = split_blueprints(EXAMPLE).join("\n");
+        assert_eq!(parse_blueprints(&one_per_line), Ok(blueprints));
+
+        assert!(parse_blueprints("Blueprint 1: Each ore robot costs 4 ore.").is_err());
+        assert!(Blueprint::parse(&TEMPLATE.replace("{}", "x")).is_none());
+    }
+
+    #[test]
+    fn test_example() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        let best = blueprints[0].max_geodes(24);
+        assert_eq!(best.value, 9);
+        // the path is a schedule that really gets there
+        assert_eq!(best.path.last().unwrap().geodes(), 9);
+        assert_eq!(
+            schedule(&best.path, 24).first(),
+            Some(&(3, Robot::Clay)),
+            "the puzzle's schedule starts with a clay robot in minute 3"
+        );
+        assert_eq!(blueprints[1].max_geodes(24).value, 12);
+        assert_eq!(quality_level_sum(&blueprints), 33);
+    }
+
+    #[test]
+    fn test_explain_the_quality_levels() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        let mut trace = Trace::new(true);
+        assert_eq!(quality_level_sum_traced(&blueprints, &mut trace), 33);
+        let explained = trace.render(narrate);
+        assert!(explained.starts_with("Blueprint 1 builds a clay-collecting robot in minute 3.\n"));
+        assert!(explained.contains("Blueprint 1 opens 9 geodes, a quality level of 1 * 9 = 9.\n"));
+        assert!(explained.ends_with(
+            "Blueprint 2 opens 12 geodes, a quality level of 2 * 12 = 24.\n\
+             The quality levels add up to 33.\n"
+        ));
+    }
+
+    #[test]
+    fn test_example_32_minutes() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        let geodes: Vec<i64> = max_geodes(&blueprints, 32)
+            .iter()
+            .map(|best| best.value)
+            .collect();
+        assert_eq!(geodes, [56, 62]);
+        assert_eq!(first_three_product(&blueprints), 56 * 62);
+    }
+
+    #[test]
+    fn test_lint() {
+        let lines: Vec<String> = split_blueprints(EXAMPLE).into_iter().collect();
+        let input = format!(
+            "{}\n{}\nBlueprint 3: Each ore robot costs 4 ore.\n{}\n",
+            lines[0], lines[1], lines[0]
+        );
+        let problems: Vec<String> = lint(&input).iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            problems,
+            [
+                "line 3: \"Blueprint 3: Each ore robot costs 4 ore.\" is not a blueprint",
+                "line 4: blueprint 1 is already on line 1",
+            ]
+        );
+        assert_eq!(lint(EXAMPLE), []);
+        assert_eq!(lint("\n")[0].to_string(), "there are no blueprints");
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs;
+
+use y2022_day19::shared;
+use y2022_day19::{
+    first_three_product, max_geodes, parse_blueprints, quality_level_sum_traced, schedule,
+};
+use y2022_day19::{Blueprint, DAY, YEAR};
+
+use shared::trace::Trace;
+
+struct Aoc {
+    data: Vec<Blueprint>,
+}
+
+impl Aoc {
+    fn new() -> Aoc {
+        Aoc { data: Vec::new() }
+    }
+
+    // load all the data in one go
+    fn load_all_data(&mut self, filename: &String) {
+        let data = match fs::read_to_string(filename) {
+            Err(why) => panic!("couldn't read {}: {}", filename, why),
+            Ok(data) => data,
+        };
+        self.data = match parse_blueprints(&data) {
+            Err(why) => panic!("couldn't parse {}: {}", filename, why),
+            Ok(blueprints) => blueprints,
+        };
+    }
+
+    fn get_filename(&self) -> Result<String, String> {
+        let mut filename: String = "./test-data.txt".to_string();
+        match shared::cli::filename() {
+            Some(arg) => {
+                filename = arg;
+            }
+            None => {
+                println!("No filename given, using test data\n");
+            }
+        }
+        Ok(filename)
+    }
+}
+
+fn main() {
+    println!("{}\n", shared::event::banner(YEAR, DAY, None));
+    let mut aoc = Aoc::new();
+    let filename = aoc.get_filename().unwrap();
+
+    if shared::cli::has_flag("--watch") {
+        shared::watch::run_from_args(&filename);
+        return;
+    }
+
+    if shared::cli::has_flag("--fuzz") {
+        shared::fuzz::run_from_args("day19", &filename, |s| parse_blueprints(s).map(|_| ()));
+        return;
+    }
+
+    aoc.load_all_data(&filename);
+    println!("Blueprints: {}", aoc.data.len());
+
+    // each blueprint's best schedule in 24 minutes, and how much searching it took
+    for (blueprint, best) in aoc.data.iter().zip(max_geodes(&aoc.data, 24)) {
+        let built: Vec<String> = schedule(&best.path, 24)
+            .iter()
+            .map(|(minute, robot)| format!("{} {}", minute, robot))
+            .collect();
+        println!(
+            "Blueprint {}: {} geodes (searched {}, cut off {}, repeats {})",
+            blueprint.id, best.value, best.stats.visited, best.stats.cut_off, best.stats.repeats
+        );
+        println!("  built: {}", built.join(", "));
+    }
+
+    let mut trace = Trace::from_args();
+    println!(
+        "Quality level sum: {}",
+        quality_level_sum_traced(&aoc.data, &mut trace)
+    );
+    println!(
+        "Geodes of the first three multiplied in 32 minutes: {}",
+        first_three_product(&aoc.data)
+    );
+
+    if trace.is_enabled() {
+        println!("\n{}", trace.render(y2022_day19::narrate));
+    }
+}
@@ -0,0 +1,304 @@
+// The 2022 solvers, one for each part the days' libraries can answer, and the
+// days' linters and replay players (see years.rs).
+//
+// Some days only have the part the binary was written for: day 2, 3 and 11
+// only have part 2 (part 1 reads the input differently), and day 15 only has
+// the part its binary solves. Day 15 searches 0..=4000000 for the distress
+// beacon unless the params have a max, the example needs a max of 20.
+
+use super::{Linter, Params, Player, Solver};
+use crate::shared::answer::Answer;
+use crate::shared::checked;
+
+// the days' crates, by the names the solvers here use
+use y2022_day01 as day01;
+use y2022_day02 as day02;
+use y2022_day03 as day03;
+use y2022_day04 as day04;
+use y2022_day05 as day05;
+use y2022_day06 as day06;
+use y2022_day07 as day07;
+use y2022_day08 as day08;
+use y2022_day09 as day09;
+use y2022_day10 as day10;
+use y2022_day11 as day11;
+use y2022_day12 as day12;
+use y2022_day13 as day13;
+use y2022_day14 as day14;
+use y2022_day15 as day15;
+use y2022_day16 as day16;
+use y2022_day17 as day17;
+use y2022_day18 as day18;
+use y2022_day19 as day19;
+
+pub const YEAR: u32 = 2022;
+
+fn new(
+    day: u32,
+    part: u32,
+    solve: impl Fn(&str) -> Result<Answer, String> + Send + Sync + 'static,
+) -> Solver {
+    Solver::new(YEAR, day, part, solve)
+}
+
+pub fn solvers(params: &Params) -> Vec<Solver> {
+    let day15_max = params.max.unwrap_or(DAY15_MAX);
+    vec![
+        new(1, 1, |s| {
+            Ok(day01::Expedition::parse(s)?.top_calories(1).into())
+        }),
+        new(1, 2, |s| {
+            Ok(day01::Expedition::parse(s)?.top_calories(3).into())
+        }),
+        new(2, 2, |s| {
+            Ok(day02::Strategy::parse(s)?.total_score().into())
+        }),
+        new(3, 2, |s| {
+            Ok(day03::Rucksacks::parse(s)?.badge_priority_sum()?.into())
+        }),
+        new(4, 1, |s| {
+            Ok(day04::Assignments::parse(s)?
+                .fully_overlapping_count()
+                .into())
+        }),
+        new(4, 2, |s| {
+            Ok(day04::Assignments::parse(s)?.overlapping_count().into())
+        }),
+        new(5, 2, |s| {
+            let mut shipyard = day05::ShipYard::parse(s)?;
+            shipyard.run();
+            Ok(shipyard.top_of_each_stack().into())
+        }),
+        new(6, 1, |s| solve_day06(s, day06::PACKET_MARKER_SIZE)),
+        new(6, 2, |s| solve_day06(s, day06::MESSAGE_MARKER_SIZE)),
+        new(7, 1, |s| {
+            let elffs = day07::ElfFs::parse(s)?;
+            let paths = elffs.dir_paths();
+            let sizes = paths
+                .iter()
+                .map(|path| elffs.get_dir_size(path.to_string()))
+                .filter(|size| *size <= 100_000);
+            Ok(checked::sum("day07 sum of small directories", sizes).into())
+        }),
+        new(7, 2, |s| {
+            day07::ElfFs::parse(s)?
+                .dir_to_delete()
+                .map(|(_, size)| Answer::from(size))
+                .ok_or("no directory is big enough".to_string())
+        }),
+        new(8, 1, |s| {
+            Ok(day08::Forest::parse(s)?.visible_count().into())
+        }),
+        new(8, 2, |s| {
+            Ok(day08::Forest::parse(s)?.max_scenic_score().0.into())
+        }),
+        new(9, 1, |s| solve_day09(s, 2)),
+        new(9, 2, |s| solve_day09(s, 10)),
+        new(10, 1, |s| {
+            let mut cpu = day10::CPU::parse(s)?;
+            cpu.run();
+            Ok(cpu.signal_strength_sum().into())
+        }),
+        new(10, 2, |s| {
+            let mut cpu = day10::CPU::parse(s)?;
+            cpu.run();
+            Ok(Answer::image(cpu.crt_lines()))
+        }),
+        new(11, 2, |s| {
+            let mut monkeys = day11::Monkeys::parse(s)?;
+            monkeys.run();
+            Ok(monkeys.monkey_business().into())
+        }),
+        new(12, 1, |s| {
+            let map = day12::HeightMap::parse(s)?;
+            map.steps_to_end(&map.start)
+                .map(Answer::from)
+                .ok_or("the end can't be reached".to_string())
+        }),
+        new(12, 2, |s| {
+            day12::HeightMap::parse(s)?
+                .fewest_steps_from_lowest()
+                .map(Answer::from)
+                .ok_or("the end can't be reached".to_string())
+        }),
+        new(13, 1, |s| {
+            Ok(day13::Packets::parse(s)?.right_order_index_sum().into())
+        }),
+        new(13, 2, |s| {
+            Ok(day13::Packets::parse(s)?.decoder_key().into())
+        }),
+        new(14, 2, |s| {
+            let mut cave = day14::Cave::parse(s)?;
+            cave.add_floor();
+            Ok(cave.fill().into())
+        }),
+        new(15, 2, move |s| solve_day15(s, day15_max)),
+        new(16, 1, |s| {
+            let ts = day16::TunnelSystem::parse(s)?;
+            Ok(ts.max_pressure(&"AA".to_string(), 30)?.0.into())
+        }),
+        new(16, 2, |s| {
+            let ts = day16::TunnelSystem::parse(s)?;
+            Ok(ts.max_pressure_with_elephant(&"AA".to_string(), 26)?.0.into())
+        }),
+        new(17, 1, |s| {
+            Ok(day17::tower_height(&day17::parse_jets(s)?, 2022).0.into())
+        }),
+        new(17, 2, |s| {
+            Ok(day17::tower_height(&day17::parse_jets(s)?, 1_000_000_000_000).0.into())
+        }),
+        new(18, 1, |s| {
+            Ok(day18::Droplet::parse(s)?.surface_area().into())
+        }),
+        new(18, 2, |s| {
+            Ok(day18::Droplet::parse(s)?.exterior_surface_area().into())
+        }),
+        new(19, 1, |s| {
+            Ok(day19::quality_level_sum(&day19::parse_blueprints(s)?).into())
+        }),
+        new(19, 2, |s| {
+            Ok(day19::first_three_product(&day19::parse_blueprints(s)?).into())
+        }),
+    ]
+}
+
+fn solve_day06(s: &str, queue_size: usize) -> Result<Answer, String> {
+    day06::Signal::parse(s)?
+        .find_marker(queue_size)
+        .map(|i| Answer::from(i + 1))
+        .ok_or("there is no marker".to_string())
+}
+
+fn solve_day09(s: &str, knots: usize) -> Result<Answer, String> {
+    let mut rope = day09::Rope::parse(s, knots)?;
+    rope.run();
+    Ok(rope.tail_visited_count().into())
+}
+
+// how far the real input searches for the day 15 distress beacon
+const DAY15_MAX: i64 = 4_000_000;
+
+fn solve_day15(s: &str, max: i64) -> Result<Answer, String> {
+    let beacon = day15::Sensors::parse(s)?
+        .find_distress_beacon(max)
+        .ok_or("there is no distress beacon".to_string())?;
+    Ok(day15::get_tuning_frequency(beacon.x, beacon.y).into())
+}
+
+// each day's lint, or its parser for the days that don't have one
+pub fn linter(day: u32) -> Option<Linter> {
+    let lint: Linter = match day {
+        1 => |s| parsed(day01::Expedition::parse(s)),
+        2 => |s| parsed(day02::Strategy::parse(s)),
+        3 => |s| parsed(day03::Rucksacks::parse(s)),
+        4 => |s| parsed(day04::Assignments::parse(s)),
+        5 => |s| problems(day05::lint(s)),
+        6 => |s| parsed(day06::Signal::parse(s)),
+        7 => |s| problems(day07::lint(s)),
+        8 => |s| problems(day08::lint(s)),
+        9 => |s| parsed(day09::Rope::parse(s, 2)),
+        10 => |s| parsed(day10::CPU::parse(s)),
+        11 => |s| parsed(day11::Monkeys::parse(s)),
+        12 => |s| problems(day12::lint(s)),
+        13 => |s| problems(day13::lint(s)),
+        14 => |s| parsed(day14::Cave::parse(s)),
+        15 => |s| parsed(day15::Sensors::parse(s)),
+        16 => |s| problems(day16::lint(s)),
+        17 => |s| problems(day17::lint(s)),
+        18 => |s| problems(day18::lint(s)),
+        19 => |s| problems(day19::lint(s)),
+        _ => return None,
+    };
+    Some(lint)
+}
+
+// each kind of recording, and the day that can play it
+pub const PLAYERS: &[(&str, Player)] = &[
+    ("crates", day05::shared::replay::play::<day05::ShipYard>),
+    ("rope", day09::shared::replay::play::<day09::Rope>),
+    ("cpu", day10::shared::replay::play::<day10::CPU>),
+    ("monkeys", day11::shared::replay::play::<day11::Monkeys>),
+    ("cave", day14::shared::replay::play::<day14::Cave>),
+    ("chamber", day17::shared::replay::play::<day17::Chamber>),
+];
+
+// every day's library has its own copy of shared::lint::Problem
+fn problems<P: ToString>(problems: Vec<P>) -> Vec<String> {
+    problems.iter().map(|p| p.to_string()).collect()
+}
+
+fn parsed<T>(parsed: Result<T, String>) -> Vec<String> {
+    parsed.err().into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{self, AssertUnwindSafe};
+
+    const DAY15_EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn test_day15_example_searches_up_to_20() {
+        assert_eq!(solve_day15(DAY15_EXAMPLE, 20), Ok(Answer::Int(56000011)));
+    }
+
+    #[test]
+    fn test_players_match_kinds() {
+        let kinds = [
+            <day05::ShipYard as day05::shared::replay::Record>::KIND,
+            <day09::Rope as day09::shared::replay::Record>::KIND,
+            <day10::CPU as day10::shared::replay::Record>::KIND,
+            <day11::Monkeys as day11::shared::replay::Record>::KIND,
+            <day14::Cave as day14::shared::replay::Record>::KIND,
+            <day17::Chamber as day17::shared::replay::Record>::KIND,
+        ];
+        let players: Vec<&str> = PLAYERS.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(players, kinds);
+    }
+
+    #[test]
+    fn test_solvers_check_invariants() {
+        // each day's library has its own copy of the switch
+        day05::shared::invariant::set_enabled(true);
+        day07::shared::invariant::set_enabled(true);
+        day09::shared::invariant::set_enabled(true);
+        let solver = |day: u32, part: u32| {
+            solvers(&Params::default())
+                .into_iter()
+                .find(|s| s.day == day && s.part == part)
+                .unwrap()
+        };
+        let crates = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                      move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\n\
+                      move 1 from 1 to 2\n";
+        assert_eq!((solver(5, 2).solve)(crates), Ok(Answer::parse("MCD")));
+        let moves = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2\n";
+        assert_eq!((solver(9, 2).solve)(moves), Ok(Answer::Int(1)));
+
+        // x is visited but / never lists it
+        let terminal = "$ cd /\n$ ls\n100 a.txt\n$ cd x\n$ ls\n50 b.txt\n";
+        let solve = solver(7, 2).solve;
+        let panicked = panic::catch_unwind(AssertUnwindSafe(|| solve(terminal)));
+        assert_eq!(
+            panicked.unwrap_err().downcast_ref::<String>().unwrap(),
+            "invariant broken, day07 / holds every file: \
+             / is 100, all the files add up to 150"
+        );
+    }
+}
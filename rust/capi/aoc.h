@@ -0,0 +1,50 @@
+/*
+ * C ABI for the Advent of Code 2022 solvers (see src/lib.rs).
+ *
+ * The solve functions take the input and a buffer for the answer. Like
+ * snprintf they write as much of the answer as fits (it is not NUL
+ * terminated) and return its full length, so a caller can retry with a bigger
+ * buffer. A negative return means the input couldn't be solved, and the
+ * buffer holds an error message -return bytes long instead.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* any part of any day with a built-in solver, e.g. aoc_solve(2022, 1, 2, ...).
+ * An image answer (2022 day 10 part 2) is one line per row */
+int64_t aoc_solve(uint32_t year, uint32_t day, uint32_t part,
+                  const uint8_t *input, size_t input_len,
+                  char *answer, size_t answer_cap);
+
+/* day 10 */
+int64_t aoc_day10_signal_strength_sum(const uint8_t *input, size_t input_len,
+                                      char *answer, size_t answer_cap);
+int64_t aoc_day10_crt(const uint8_t *input, size_t input_len,
+                      char *answer, size_t answer_cap);
+
+/* day 13 */
+int64_t aoc_day13_right_order_index_sum(const uint8_t *input, size_t input_len,
+                                        char *answer, size_t answer_cap);
+int64_t aoc_day13_decoder_key(const uint8_t *input, size_t input_len,
+                              char *answer, size_t answer_cap);
+
+#define AOC_COMPARE_ERROR (-2)
+
+/* -1 if left comes before right, 0 if they are the same, 1 if right comes
+ * first, or AOC_COMPARE_ERROR if either packet can't be parsed */
+int32_t aoc_day13_compare_packets(const uint8_t *left, size_t left_len,
+                                  const uint8_t *right, size_t right_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,319 @@
+// C ABI exports of the puzzle solvers, built as a cdylib so C and C++ test
+// harnesses (and any language with a C FFI) can call them directly. The
+// declarations are in capi/aoc.h.
+//
+//     rustc --edition 2021 --crate-type cdylib --extern y2022_day01=... --extern y2022_day19=... capi/src/lib.rs
+//
+// aoc_solve() solves any part of any day the runner has a built-in solver for,
+// using the runner's own registry (runner/src/solvers.rs), so a day added there
+// can be called from C without a change here. Day 10 and day 13 also have
+// functions of their own for the CPU and the packet comparison.
+//
+// The solve functions all work the same way as the plugin ABI's (see
+// shared/plugin.rs): they take the input bytes and a buffer for the answer,
+// write as much of the answer as fits and return its full length, or return
+// minus the length of an error message written to the buffer instead. They
+// never panic across the boundary.
+
+#[path = "../../shared/mod.rs"]
+mod shared;
+
+// the runner's solvers. only builtin() is used here, the rest is for the runner
+#[path = "../../runner/src"]
+#[allow(dead_code)]
+mod runner {
+    pub mod solvers;
+}
+
+use std::cmp::Ordering;
+
+use shared::plugin::call_solve;
+
+/// Solves part `part` of day `day` of `year`'s puzzle with the runner's
+/// built-in solver. An image (day 10 part 2) is one line per row. It is an
+/// error if there is no built-in solver for that part.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` bytes that can be read, and `answer` to
+/// `answer_cap` bytes that can be written, as for
+/// [`call_solve`](shared::plugin::call_solve).
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64 {
+    call_solve(input, input_len, answer, answer_cap, |s| {
+        // the real input's search sizes
+        let solver = runner::solvers::builtin(&runner::solvers::Params::default())
+            .into_iter()
+            .find(|solver| (solver.year, solver.day, solver.part) == (year, day, part));
+        match solver {
+            Some(solver) => Ok((solver.solve)(s)?.to_string()),
+            None => Err(format!(
+                "there is no solver for {} day {} part {}",
+                year, day, part
+            )),
+        }
+    })
+}
+
+/// Day 10: runs the program and adds up the signal strengths.
+///
+/// # Safety
+///
+/// The same as [`aoc_solve`].
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day10_signal_strength_sum(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64 {
+    call_solve(input, input_len, answer, answer_cap, |s| {
+        let mut cpu = y2022_day10::CPU::parse(s)?;
+        cpu.run();
+        Ok(cpu.signal_strength_sum().to_string())
+    })
+}
+
+/// Day 10: runs the program and returns what the CRT drew, one line per row.
+///
+/// # Safety
+///
+/// The same as [`aoc_solve`].
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day10_crt(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64 {
+    call_solve(input, input_len, answer, answer_cap, |s| {
+        let mut cpu = y2022_day10::CPU::parse(s)?;
+        cpu.run();
+        Ok(cpu.crt_lines().join("\n"))
+    })
+}
+
+/// Day 13 part 1.
+///
+/// # Safety
+///
+/// The same as [`aoc_solve`].
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day13_right_order_index_sum(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64 {
+    call_solve(input, input_len, answer, answer_cap, |s| {
+        Ok(y2022_day13::Packets::parse(s)?
+            .right_order_index_sum()
+            .to_string())
+    })
+}
+
+/// Day 13 part 2.
+///
+/// # Safety
+///
+/// The same as [`aoc_solve`].
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day13_decoder_key(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64 {
+    call_solve(input, input_len, answer, answer_cap, |s| {
+        Ok(y2022_day13::Packets::parse(s)?.decoder_key().to_string())
+    })
+}
+
+pub const AOC_COMPARE_ERROR: i32 = -2;
+
+/// Day 13: compares two packets like strcmp, -1 if left comes first (they are
+/// in the right order), 0 if they are the same and 1 if right comes first.
+/// `AOC_COMPARE_ERROR` if either packet can't be parsed.
+///
+/// # Safety
+///
+/// `left` must point to `left_len` bytes that can be read, and `right` to
+/// `right_len` bytes. Either can be null when its length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_day13_compare_packets(
+    left: *const u8,
+    left_len: usize,
+    right: *const u8,
+    right_len: usize,
+) -> i32 {
+    let (left, right) = match (to_str(left, left_len), to_str(right, right_len)) {
+        (Some(left), Some(right)) => (left, right),
+        _ => return AOC_COMPARE_ERROR,
+    };
+    match std::panic::catch_unwind(|| y2022_day13::try_compare_packets(left, right)) {
+        Ok(Ok(Ordering::Less)) => -1,
+        Ok(Ok(Ordering::Equal)) => 0,
+        Ok(Ok(Ordering::Greater)) => 1,
+        _ => AOC_COMPARE_ERROR,
+    }
+}
+
+// bytes has to point to len bytes that stay put for 'a (or be anything when
+// len is 0). None if they aren't UTF-8
+unsafe fn to_str<'a>(bytes: *const u8, len: usize) -> Option<&'a str> {
+    if len == 0 {
+        return Some("");
+    }
+    if bytes.is_null() {
+        return None;
+    }
+    let bytes = std::slice::from_raw_parts(bytes, len);
+    std::str::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type SolveFn = unsafe extern "C" fn(*const u8, usize, *mut u8, usize) -> i64;
+
+    // calls a solve function the way a C caller would, with a fixed buffer
+    fn call(solve: SolveFn, input: &str, cap: usize) -> (i64, String) {
+        let mut answer: Vec<u8> = vec![0; cap];
+        let result = unsafe { solve(input.as_ptr(), input.len(), answer.as_mut_ptr(), cap) };
+        let written = (result.unsigned_abs() as usize).min(cap);
+        (
+            result,
+            String::from_utf8_lossy(&answer[..written]).to_string(),
+        )
+    }
+
+    // aoc_solve with room for any of the answers
+    fn solve(year: u32, day: u32, part: u32, input: &str) -> Result<String, String> {
+        let mut answer: Vec<u8> = vec![0; 1024];
+        let result = unsafe {
+            aoc_solve(
+                year,
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                answer.as_mut_ptr(),
+                answer.len(),
+            )
+        };
+        let text = String::from_utf8_lossy(&answer[..result.unsigned_abs() as usize]).to_string();
+        if result < 0 {
+            Err(text)
+        } else {
+            Ok(text)
+        }
+    }
+
+    const PACKETS: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n[[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]\n";
+
+    // the puzzle's larger example program
+    const PROGRAM: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\n\
+                           addx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\n\
+                           addx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\n\
+                           addx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\n\
+                           addx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\n\
+                           addx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\n\
+                           addx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\n\
+                           addx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\n\
+                           addx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\n\
+                           addx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\n\
+                           noop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\n\
+                           addx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\n\
+                           noop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\n\
+                           addx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\n\
+                           addx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\n\
+                           noop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\n\
+                           addx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\n\
+                           addx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\n\
+                           addx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop\n";
+
+    const CRT: &str = "##..##..##..##..##..##..##..##..##..##..\n\
+                       ###...###...###...###...###...###...###.\n\
+                       ####....####....####....####....####....\n\
+                       #####.....#####.....#####.....#####.....\n\
+                       ######......######......######......####\n\
+                       #######.......#######.......#######.....";
+
+    fn compare(left: &str, right: &str) -> i32 {
+        unsafe { aoc_day13_compare_packets(left.as_ptr(), left.len(), right.as_ptr(), right.len()) }
+    }
+
+    #[test]
+    fn test_day13_compare_packets() {
+        assert_eq!(compare("[1,1,3,1,1]", "[1,1,5,1,1]"), -1);
+        assert_eq!(compare("[9]", "[[8,7,6]]"), 1);
+        assert_eq!(compare("[[4,4],4,4]", "[[4,4],4,4]"), 0);
+        assert_eq!(compare("[1,2", "[1]"), AOC_COMPARE_ERROR);
+    }
+
+    #[test]
+    fn test_day13_solvers() {
+        assert_eq!(
+            call(aoc_day13_right_order_index_sum, PACKETS, 64),
+            (2, "13".to_string())
+        );
+        assert_eq!(
+            call(aoc_day13_decoder_key, PACKETS, 64),
+            (3, "140".to_string())
+        );
+    }
+
+    #[test]
+    fn test_day10() {
+        assert_eq!(PROGRAM.lines().count(), 146);
+        assert_eq!(
+            call(aoc_day10_signal_strength_sum, PROGRAM, 64),
+            (5, "13140".to_string())
+        );
+        assert_eq!(
+            call(aoc_day10_crt, PROGRAM, 1024),
+            (CRT.len() as i64, CRT.to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve_any_day() {
+        let calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        assert_eq!(solve(2022, 1, 1, calories), Ok("24000".to_string()));
+        assert_eq!(solve(2022, 1, 2, calories), Ok("45000".to_string()));
+        assert_eq!(solve(2022, 10, 1, PROGRAM), Ok("13140".to_string()));
+        assert_eq!(solve(2022, 10, 2, PROGRAM), Ok(CRT.to_string()));
+        assert_eq!(solve(2022, 13, 2, PACKETS), Ok("140".to_string()));
+        assert_eq!(
+            solve(2022, 26, 1, ""),
+            Err("there is no solver for 2022 day 26 part 1".to_string())
+        );
+        assert!(solve(2022, 1, 1, "1000\nlots\n").is_err());
+    }
+
+    #[test]
+    fn test_answer_too_big_for_the_buffer() {
+        // only what fits is written, but the full length is returned
+        let (len, crt) = call(aoc_day10_crt, "noop\n", 10);
+        assert_eq!(crt.len(), 10);
+        assert!(len > 10);
+    }
+
+    #[test]
+    fn test_errors_are_negative() {
+        let (len, why) = call(aoc_day10_signal_strength_sum, "jump 3\n", 256);
+        assert!(len < 0);
+        assert_eq!(why.len(), -len as usize);
+        let (len, _) = call(aoc_day13_decoder_key, "[1,[2\n[3]\n", 256);
+        assert!(len < 0);
+    }
+}
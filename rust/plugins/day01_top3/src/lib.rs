@@ -0,0 +1,35 @@
+// An example solver plugin: day 1 part 2 without sorting the elves.
+//
+// Build it as a cdylib and put it in the runner's plugins directory, e.g.
+//
+//     rustc --edition 2021 --crate-type cdylib -O -o plugins/libday01_top3.so plugins/day01_top3/src/lib.rs
+//     runner --day=1 --part=2 input.txt
+
+#[path = "../../../shared/mod.rs"]
+mod shared;
+
+// keeps the three biggest totals as it goes, rather than collecting every elf
+fn solve(s: &str) -> Result<String, String> {
+    let mut top: [u64; 3] = [0; 3];
+    for (i, elf) in s.split("\n\n").enumerate() {
+        let mut calories: u64 = 0;
+        for line in elf.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            match line.parse::<u64>() {
+                Ok(num) => calories += num,
+                Err(_) => return Err(format!("elf {}: {:?} is not a number", i + 1, line)),
+            }
+        }
+        // top is kept smallest first, so replace the smallest and bubble it up
+        if calories > top[0] {
+            top[0] = calories;
+            for j in 1..top.len() {
+                if top[j - 1] > top[j] {
+                    top.swap(j - 1, j);
+                }
+            }
+        }
+    }
+    Ok(top.iter().sum::<u64>().to_string())
+}
+
+export_plugin!(2022, 1, 2, "day01 top three", solve);
@@ -0,0 +1,236 @@
+// Runs one day against every input in a directory, for checking a solver
+// against many accounts' inputs (and hand-made ones) in one go.
+//
+//     runner batch [--year=2022] --day=N [--threads=T] DIR
+//
+// The inputs are solved in parallel, a thread per core unless --threads is
+// given, and a CSV is written to stdout: the file, the answers to both parts
+// and how long it took in milliseconds, plus whether the answers matched.
+// An input can have a sidecar with the answers it should give, the input's
+// name with a .expected extension (day01/alice.txt and day01/alice.expected)
+// holding the part 1 answer on the first line and part 2 on the second. A
+// blank line isn't checked. Multi-line answers use \n like answers.txt does.
+//
+// The files whose answers differ are listed on stderr, and the exit code is 1
+// if there were any.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use crate::run_solver;
+use crate::shared::answer::Answer;
+use crate::solvers::Solver;
+
+const EXPECTED_EXTENSION: &str = "expected";
+
+struct Row {
+    file: String,
+    answers: [Option<Result<Answer, String>>; 2],
+    elapsed: Duration,
+    // None when nothing was expected, Some(false) if any answer differs
+    matched: Option<bool>,
+}
+
+// the inputs in dir, by name, leaving out the sidecars and hidden files
+fn inputs(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = match fs::read_dir(dir) {
+        Err(why) => return Err(format!("couldn't read {}: {}", dir.display(), why)),
+        Ok(entries) => entries,
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| path.extension().and_then(|e| e.to_str()) != Some(EXPECTED_EXTENSION))
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            !name.starts_with('.')
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+// the answers a sidecar expects for part 1 and part 2
+fn parse_expected(s: &str) -> [Option<Answer>; 2] {
+    let mut lines = s.lines().map(|line| line.trim());
+    let mut next = || match lines.next() {
+        Some(line) if !line.is_empty() => Some(Answer::parse(&line.replace("\\n", "\n"))),
+        _ => None,
+    };
+    let part1 = next();
+    [part1, next()]
+}
+
+fn read_expected(input: &Path) -> Option<[Option<Answer>; 2]> {
+    fs::read_to_string(input.with_extension(EXPECTED_EXTENSION))
+        .ok()
+        .map(|s| parse_expected(&s))
+}
+
+fn solve_file(path: &Path, parts: &[Option<&Solver>; 2]) -> Row {
+    let file = path.display().to_string();
+    let input = match fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(why) => {
+            let error = Some(Err(format!("couldn't read it: {}", why)));
+            return Row {
+                file,
+                answers: [error.clone(), error],
+                elapsed: Duration::ZERO,
+                matched: None,
+            };
+        }
+    };
+    let expected = read_expected(path);
+    let mut row = Row {
+        file,
+        answers: [None, None],
+        elapsed: Duration::ZERO,
+        matched: None,
+    };
+    for (i, solver) in parts.iter().enumerate() {
+        let solver = match solver {
+            Some(solver) => solver,
+            None => continue,
+        };
+        let expected = expected.as_ref().and_then(|e| e[i].as_ref());
+        let run = run_solver(solver, &input, expected);
+        if let Some(correct) = run.correct {
+            row.matched = Some(row.matched.unwrap_or(true) && correct);
+        }
+        row.answers[i] = Some(run.answer);
+        row.elapsed += run.elapsed;
+    }
+    row
+}
+
+// quoted if it has to be, with quotes doubled
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_row(row: &Row) -> String {
+    let answer = |answer: &Option<Result<Answer, String>>| match answer {
+        Some(Ok(answer)) => answer.to_string(),
+        Some(Err(why)) => format!("error: {}", why),
+        None => "".to_string(),
+    };
+    let matched = match row.matched {
+        Some(true) => "ok",
+        Some(false) => "DIFFERS",
+        None => "",
+    };
+    [
+        csv_field(&row.file),
+        csv_field(&answer(&row.answers[0])),
+        csv_field(&answer(&row.answers[1])),
+        format!("{:.3}", row.elapsed.as_secs_f64() * 1000.0),
+        matched.to_string(),
+    ]
+    .join(",")
+}
+
+// solves every input in dir with the first solver for each part of the day,
+// and returns the exit code
+pub fn run(solvers: &[Solver], year: u32, day: u32, dir: &Path, threads: usize) -> i32 {
+    let solver_for = |part: u32| {
+        solvers
+            .iter()
+            .find(|s| s.year == year && s.day == day && s.part == part)
+    };
+    let parts = [solver_for(1), solver_for(2)];
+    if parts.iter().all(|p| p.is_none()) {
+        eprintln!("there are no solvers for {} day {}", year, day);
+        return 1;
+    }
+    let paths = match inputs(dir) {
+        Ok(paths) => paths,
+        Err(why) => {
+            eprintln!("{}", why);
+            return 1;
+        }
+    };
+
+    // each thread takes the next file until there are none left
+    let next = AtomicUsize::new(0);
+    let rows: Mutex<Vec<(usize, Row)>> = Mutex::new(vec![]);
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1).min(paths.len()) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= paths.len() {
+                    break;
+                }
+                let row = solve_file(&paths[i], &parts);
+                rows.lock().unwrap().push((i, row));
+            });
+        }
+    });
+    let mut rows = rows.into_inner().unwrap();
+    rows.sort_by_key(|(i, _)| *i);
+
+    println!("file,part1,part2,ms,expected");
+    let mut differs: Vec<&str> = vec![];
+    for (_, row) in &rows {
+        println!("{}", csv_row(row));
+        if row.matched == Some(false) {
+            differs.push(&row.file);
+        }
+    }
+    if differs.is_empty() {
+        return 0;
+    }
+    eprintln!(
+        "{} of {} inputs gave different answers:",
+        differs.len(),
+        rows.len()
+    );
+    for file in differs {
+        eprintln!("  {}", file);
+    }
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected() {
+        assert_eq!(
+            parse_expected("24000\n45000\n"),
+            [Some(Answer::Int(24000)), Some(Answer::Int(45000))]
+        );
+        // only part 2 is known
+        assert_eq!(parse_expected("\n45000"), [None, Some(Answer::Int(45000))]);
+        assert_eq!(
+            parse_expected("#.\\n.#\n"),
+            [Some(Answer::image(["#.", ".#"])), None]
+        );
+    }
+
+    #[test]
+    fn test_csv_row() {
+        let row = Row {
+            file: "in/a,b.txt".to_string(),
+            answers: [
+                Some(Ok(Answer::image(["##..", "..##"]))),
+                Some(Err("no \"marker\"".to_string())),
+            ],
+            elapsed: Duration::from_micros(1500),
+            matched: Some(false),
+        };
+        assert_eq!(
+            csv_row(&row),
+            "\"in/a,b.txt\",\"##..\n..##\",\"error: no \"\"marker\"\"\",1.500,DIFFERS"
+        );
+    }
+}
@@ -0,0 +1,467 @@
+// A record of every run, so a change to the shared code that changes an
+// answer or slows a day down shows up.
+//
+//     runner history [--year=2022] [--day=16] [--threshold=1.5] [--history=FILE]
+//
+// Each time the runner solves a day, a line is appended to the history file
+// (./history.jsonl unless --history is given) for each solver, with when it
+// ran, the day and part, a hash of the input, the answer and how long it took:
+//
+//     {"time":1671235200,"year":2022,"day":16,"part":1,"solver":"day16",
+//      "input":"9c1185a5c5e9fc54","kind":"int","answer":1651,"ms":812.402}
+//
+// (all on one line). Runs with --checked or --check aren't recorded, as
+// they're slower on purpose, and neither is anything with --no-history.
+//
+// history goes through the runs of each solver on each input, oldest first,
+// and shows how long the last few took. It flags an answer that changed from
+// one run to the next on the same input, and a last run that took more than
+// --threshold times (1.5 unless given) the median of the runs before it. The
+// exit code is 1 if anything was flagged.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::shared::answer::{json_string, Answer};
+use crate::Run;
+
+pub const DEFAULT_PATH: &str = "./history.jsonl";
+pub const DEFAULT_THRESHOLD: f64 = 1.5;
+
+// a run that slowed down by less than this is noise, however many times slower
+const MIN_SLOWDOWN_MS: f64 = 1.0;
+// how many of the latest times are shown
+const SHOWN: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub time: u64,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub solver: String,
+    pub input: String,
+    pub answer: Result<Answer, String>,
+    pub ms: f64,
+}
+
+impl Entry {
+    pub fn new(run: &Run, input: &str) -> Entry {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |t| t.as_secs());
+        Entry {
+            time,
+            year: run.year,
+            day: run.day,
+            part: run.part,
+            solver: run.solver.clone(),
+            input: input_hash(input),
+            answer: run.answer.clone(),
+            ms: run.elapsed.as_secs_f64() * 1000.0,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let answer = match &self.answer {
+            Ok(answer) => format!(
+                "\"kind\":\"{}\",\"answer\":{}",
+                answer.kind(),
+                answer.to_json()
+            ),
+            Err(why) => format!("\"error\":{}", json_string(why)),
+        };
+        format!(
+            "{{\"time\":{},\"year\":{},\"day\":{},\"part\":{},\"solver\":{},\"input\":\"{}\",{},\"ms\":{:.3}}}",
+            self.time,
+            self.year,
+            self.day,
+            self.part,
+            json_string(&self.solver),
+            self.input,
+            answer,
+            self.ms
+        )
+    }
+
+    pub fn parse(line: &str) -> Result<Entry, String> {
+        let fields = parse_object(line)?;
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value)
+        };
+        let number = |name: &str| match field(name) {
+            Some(Value::Number(n)) => n
+                .parse::<f64>()
+                .map_err(|_| format!("{} isn't a number: {}", name, n)),
+            _ => Err(format!("there is no {}", name)),
+        };
+        let string = |name: &str| match field(name) {
+            Some(Value::Str(s)) => Ok(s.clone()),
+            _ => Err(format!("there is no {}", name)),
+        };
+        let answer = match (field("answer"), field("error")) {
+            (Some(Value::Number(n)), _) => Ok(Answer::parse(n)),
+            (Some(Value::Str(s)), _) => Ok(Answer::Text(s.clone())),
+            (Some(Value::Array(rows)), _) => Ok(Answer::image(rows)),
+            (None, Some(Value::Str(why))) => Err(why.clone()),
+            _ => return Err("there is no answer or error".to_string()),
+        };
+        Ok(Entry {
+            time: number("time")? as u64,
+            year: number("year")? as u32,
+            day: number("day")? as u32,
+            part: number("part")? as u32,
+            solver: string("solver")?,
+            input: string("input")?,
+            answer,
+            ms: number("ms")?,
+        })
+    }
+}
+
+// FNV-1a, which unlike the standard library's hasher is the same from one
+// Rust release to the next, so a history outlives a compiler upgrade
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+pub fn append(path: &str, entries: &[Entry]) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|why| format!("couldn't open {}: {}", path, why))?;
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&entry.to_json());
+        lines.push('\n');
+    }
+    file.write_all(lines.as_bytes())
+        .map_err(|why| format!("couldn't write to {}: {}", path, why))
+}
+
+// every entry in the file, oldest first, with a line that can't be read
+// reported by its number
+pub fn load(path: &str) -> Result<Vec<Entry>, String> {
+    let s = fs::read_to_string(path).map_err(|why| format!("couldn't read {}: {}", path, why))?;
+    s.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            Entry::parse(line).map_err(|why| format!("{} line {}: {}", path, i + 1, why))
+        })
+        .collect()
+}
+
+// the runs of one solver on one input, oldest first
+struct Series<'a> {
+    entries: Vec<&'a Entry>,
+}
+
+impl Series<'_> {
+    // each run whose answer isn't the one the run before it gave
+    fn answer_changes(&self) -> Vec<String> {
+        self.entries
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0].answer != pair[1].answer)
+            .map(|(i, pair)| {
+                format!(
+                    "run {} gave {}, the run before gave {}",
+                    i + 2,
+                    describe(&pair[1].answer),
+                    describe(&pair[0].answer)
+                )
+            })
+            .collect()
+    }
+
+    // the last run, if it took more than threshold times the median before it
+    fn slowdown(&self, threshold: f64) -> Option<String> {
+        let (last, before) = self.entries.split_last()?;
+        if before.is_empty() {
+            return None;
+        }
+        let median = median(before.iter().map(|e| e.ms).collect());
+        if last.ms > median * threshold && last.ms - median >= MIN_SLOWDOWN_MS {
+            Some(format!(
+                "the last run took {:.1}ms, {:.1} times the median of the runs before ({:.1}ms)",
+                last.ms,
+                last.ms / median,
+                median
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn median(mut ms: Vec<f64>) -> f64 {
+    ms.sort_by(|a, b| a.total_cmp(b));
+    let middle = ms.len() / 2;
+    if ms.len().is_multiple_of(2) {
+        (ms[middle - 1] + ms[middle]) / 2.0
+    } else {
+        ms[middle]
+    }
+}
+
+fn describe(answer: &Result<Answer, String>) -> String {
+    match answer {
+        Ok(Answer::Image(rows)) => format!(
+            "a {}x{} picture",
+            rows.first().map_or(0, |r| r.len()),
+            rows.len()
+        ),
+        Ok(answer) => answer.to_string(),
+        Err(why) => format!("an error ({})", why),
+    }
+}
+
+// the entries for year and day (every day if None) by solver and input, in
+// the order each first ran
+fn series(entries: &[Entry], year: u32, day: Option<u32>) -> Vec<Series<'_>> {
+    let mut order: Vec<Series> = Vec::new();
+    let mut index: HashMap<(u32, u32, u32, &str, &str), usize> = HashMap::new();
+    let wanted = entries
+        .iter()
+        .filter(|e| e.year == year && day.is_none_or(|day| e.day == day));
+    for entry in wanted {
+        let key = (
+            entry.year,
+            entry.day,
+            entry.part,
+            entry.solver.as_str(),
+            entry.input.as_str(),
+        );
+        let i = *index.entry(key).or_insert_with(|| {
+            order.push(Series {
+                entries: Vec::new(),
+            });
+            order.len() - 1
+        });
+        order[i].entries.push(entry);
+    }
+    order
+}
+
+// prints the trends and what was flagged, and returns the exit code
+pub fn run(path: &str, year: u32, day: Option<u32>, threshold: f64) -> i32 {
+    let entries = match load(path) {
+        Err(why) => {
+            eprintln!("{}", why);
+            return 1;
+        }
+        Ok(entries) => entries,
+    };
+    let all = series(&entries, year, day);
+    if all.is_empty() {
+        println!("there are no runs of {} in {}", year, path);
+        return 0;
+    }
+    let mut flagged = 0;
+    for series in all {
+        let first = series.entries[0];
+        println!(
+            "{} day {:>2} part {}, {}, input {}",
+            first.year, first.day, first.part, first.solver, first.input
+        );
+        let shown = &series.entries[series.entries.len().saturating_sub(SHOWN)..];
+        let times: Vec<String> = shown.iter().map(|e| format!("{:.2}", e.ms)).collect();
+        let runs = match series.entries.len() {
+            1 => "1 run, which took".to_string(),
+            n if n == shown.len() => format!("{} runs, which took", n),
+            n => format!("{} runs, the last {} took", n, shown.len()),
+        };
+        println!("  {} {} ms", runs, times.join(" "));
+        for change in series.answer_changes() {
+            println!("  ANSWER CHANGED: {}", change);
+            flagged += 1;
+        }
+        if let Some(slowdown) = series.slowdown(threshold) {
+            println!("  SLOWER: {}", slowdown);
+            flagged += 1;
+        }
+    }
+    if flagged > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+// just enough JSON for the lines append writes: one object of numbers,
+// strings and arrays of strings
+#[derive(Debug, PartialEq)]
+enum Value {
+    Number(String),
+    Str(String),
+    Array(Vec<String>),
+}
+
+fn parse_object(line: &str) -> Result<Vec<(String, Value)>, String> {
+    let mut chars = line.trim().chars().peekable();
+    let mut fields = Vec::new();
+    expect(&mut chars, '{')?;
+    if chars.peek() == Some(&'}') {
+        return Ok(fields);
+    }
+    loop {
+        let key = parse_string(&mut chars)?;
+        expect(&mut chars, ':')?;
+        let value = match chars.peek() {
+            Some('"') => Value::Str(parse_string(&mut chars)?),
+            Some('[') => {
+                chars.next();
+                let mut items = Vec::new();
+                while chars.peek() != Some(&']') {
+                    items.push(parse_string(&mut chars)?);
+                    if chars.peek() == Some(&',') {
+                        chars.next();
+                    }
+                }
+                chars.next();
+                Value::Array(items)
+            }
+            _ => {
+                let mut n = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || "-+.eE".contains(*c)) {
+                    n.push(c);
+                }
+                if n.is_empty() {
+                    return Err(format!("{} has no value", key));
+                }
+                Value::Number(n)
+            }
+        };
+        fields.push((key, value));
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => return Ok(fields),
+            _ => return Err("the line isn't a whole object".to_string()),
+        }
+    }
+}
+
+fn expect(chars: &mut impl Iterator<Item = char>, c: char) -> Result<(), String> {
+    match chars.next() {
+        Some(next) if next == c => Ok(()),
+        _ => Err(format!("expected {}", c)),
+    }
+}
+
+// a string with the escapes json_string makes
+fn parse_string(chars: &mut impl Iterator<Item = char>) -> Result<String, String> {
+    expect(chars, '"')?;
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => return Err("a string isn't closed".to_string()),
+            Some('"') => return Ok(s),
+            Some('\\') => match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or(format!("\\u{} isn't a character", hex))?;
+                    s.push(c);
+                }
+                Some(c) => s.push(c),
+                None => return Err("a string isn't closed".to_string()),
+            },
+            Some(c) => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(answer: Result<Answer, String>, ms: f64) -> Entry {
+        Entry {
+            time: 1671235200,
+            year: 2022,
+            day: 16,
+            part: 1,
+            solver: "day16".to_string(),
+            input: input_hash("Valve AA"),
+            answer,
+            ms,
+        }
+    }
+
+    #[test]
+    fn test_entries_read_back() {
+        let entries = [
+            entry(Ok(Answer::Int(1651)), 812.402),
+            entry(Ok(Answer::Text("C\"M\\Z".to_string())), 0.5),
+            entry(Ok(Answer::image(["#..#", ".##."])), 1.0),
+            entry(
+                Err("the solver panicked: invariant broken\n".to_string()),
+                2.0,
+            ),
+        ];
+        for entry in entries {
+            assert_eq!(Entry::parse(&entry.to_json()), Ok(entry));
+        }
+        assert!(Entry::parse("{\"time\":1}").is_err());
+        assert!(Entry::parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_ne!(input_hash("1\n2\n"), input_hash("2\n1\n"));
+    }
+
+    #[test]
+    fn test_answer_changes_are_flagged() {
+        let entries = [
+            entry(Ok(Answer::Int(1651)), 10.0),
+            entry(Ok(Answer::Int(1651)), 10.0),
+            entry(Ok(Answer::Int(1650)), 10.0),
+        ];
+        let all = series(&entries, 2022, Some(16));
+        assert_eq!(all.len(), 1);
+        assert_eq!(
+            all[0].answer_changes(),
+            vec!["run 3 gave 1650, the run before gave 1651".to_string()]
+        );
+        // a different input is a different series
+        let mut other = entry(Ok(Answer::Int(1650)), 10.0);
+        other.input = input_hash("Valve BB");
+        let entries = [entry(Ok(Answer::Int(1651)), 10.0), other];
+        let all = series(&entries, 2022, None);
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|s| s.answer_changes().is_empty()));
+    }
+
+    #[test]
+    fn test_slowdowns_are_flagged() {
+        let times = |ms: &[f64]| -> Vec<Entry> {
+            ms.iter().map(|ms| entry(Ok(Answer::Int(1)), *ms)).collect()
+        };
+        let slowdown =
+            |entries: &[Entry]| series(entries, 2022, Some(16))[0].slowdown(DEFAULT_THRESHOLD);
+        assert!(slowdown(&times(&[10.0, 12.0, 11.0, 20.0])).is_some());
+        assert!(slowdown(&times(&[10.0, 12.0, 11.0, 14.0])).is_none());
+        // too little to be more than noise
+        assert!(slowdown(&times(&[0.1, 0.1, 0.5])).is_none());
+        assert!(slowdown(&times(&[10.0])).is_none());
+    }
+}
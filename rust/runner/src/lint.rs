@@ -0,0 +1,96 @@
+// Checks an input file against a day's grammar without solving it, and
+// reports every problem with the line it is on (see shared/lint.rs).
+//
+//     runner lint [--year=2022] --day=16 FILE
+//
+// Days with a lint function report every problem, the others report the first
+// problem their parser finds.
+
+use std::fs;
+
+use crate::solvers::Linter;
+
+// prints the problems with the input in the file, and returns the exit code
+pub fn run(lint: Linter, path: &str) -> i32 {
+    let input = match fs::read_to_string(path) {
+        Err(why) => {
+            eprintln!("couldn't read {}: {}", path, why);
+            return 1;
+        }
+        Ok(input) => input,
+    };
+    let problems = lint(&input);
+    if problems.is_empty() {
+        println!("{}: ok", path);
+        return 0;
+    }
+    for problem in &problems {
+        println!("{}: {}", path, problem);
+    }
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers;
+
+    #[test]
+    fn test_every_day_has_a_linter() {
+        for day in 1..=19 {
+            assert!(solvers::linter(2022, day).is_some(), "day {}", day);
+        }
+        assert!(solvers::linter(2022, 26).is_none());
+        assert!(solvers::linter(1999, 1).is_none());
+    }
+
+    #[test]
+    fn test_lint_reports_lines() {
+        let lint = solvers::linter(2022, 5).unwrap();
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                     move 1 from 2 to 1\nmove 4 from 1 to 3\nmove 2 from 2 to 4\n";
+        assert_eq!(
+            lint(input),
+            [
+                "line 7: moves 4 crates from stack 1, which has 3",
+                "line 8: there is no stack 4",
+            ]
+        );
+
+        let lint = solvers::linter(2022, 7).unwrap();
+        assert_eq!(
+            lint("$ ls\n14848514 b.txt\n$ cd /\n$ cd ..\n"),
+            [
+                "line 1: ls before any cd, so which directory it lists isn't known",
+                "line 4: cd .. out of /",
+            ]
+        );
+
+        let lint = solvers::linter(2022, 12).unwrap();
+        assert_eq!(
+            lint("Sabq\nabcryxxl\naccszEx?\n"),
+            [
+                "line 2: has 8 squares, the first row has 4",
+                "line 3: '?' in column 8 is not a height",
+                "line 3: has 8 squares, the first row has 4",
+            ]
+        );
+        assert_eq!(
+            lint("abc\n"),
+            ["there is no start (S)", "there is no end (E)"]
+        );
+
+        let lint = solvers::linter(2022, 13).unwrap();
+        assert_eq!(
+            lint("[1,1,3]\n[1,[2]\n\n[[1]]\n"),
+            [
+                "line 2: unbalanced brackets in 1,[2",
+                "line 4: the packet has no pair",
+            ]
+        );
+
+        // days without a lint function fall back to their parser
+        let lint = solvers::linter(2022, 1).unwrap();
+        assert_eq!(lint("1000\n2000\n\n3000\n").len(), 0);
+        assert_eq!(lint("1000\nx\n").len(), 1);
+    }
+}
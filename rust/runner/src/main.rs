@@ -0,0 +1,362 @@
+// Runs the solvers for a day against an input file: the built-in ones from each
+// day's library, and any plugins in the plugins directory, so alternative
+// solutions can be raced against the built-in ones.
+//
+//     runner [--year=2022] --day=16 [--part=1] [--plugins=DIR] [input.txt]
+//     runner --list [--plugins=DIR]
+//     runner --checked --day=16 [input.txt]
+//     runner --check --day=16 [input.txt]
+//     runner batch [--year=2022] --day=16 DIR
+//     runner lint [--year=2022] --day=16 FILE
+//     runner scale [--year=2022] [--day=16]
+//     runner history [--year=2022] [--day=16] [--threshold=1.5]
+//     runner replay [--speed=N] [--seek=N] [--stop=N] FILE
+//
+// The year is the latest one with solvers unless --year is given. Without an
+// input file the day's input is read from the inputs directory (./inputs, or
+// --inputs=DIR), laid out by year as described in shared/event.rs, and any
+// known answers in there are checked.
+//
+// Each solver's answer and how long it took are printed, and if the solvers for
+// a part don't agree that is reported too. --json prints one JSON object per
+// solver instead, with the answer's kind and the answer as a number, a string
+// or an array of the rows of a picture (see shared/answer.rs). The plugins
+// directory is ./plugins unless --plugins is given.
+//
+// --max=N is how far the days that search an area go (2022 day 15 searches
+// 0..=4000000 unless it is given, its example needs --max=20).
+//
+// --checked checks the sums and products the solvers build up for overflow
+// (see shared/checked.rs), and --check checks the invariants the days state
+// while they run (see shared/invariant.rs), for batch as well.
+//
+// batch runs a day against every input in a directory (see batch.rs), lint
+// checks an input against the day's grammar without solving it (see lint.rs),
+// scale times a day on bigger and bigger generated inputs (see scale.rs), and
+// replay plays back a recording of a simulation (see replay.rs).
+//
+// Every run is added to a history file (./history.jsonl, or --history=FILE),
+// and history shows how the answers and times have changed (see history.rs).
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[path = "../../shared/mod.rs"]
+mod shared;
+
+mod batch;
+mod history;
+mod lint;
+mod plugins;
+mod replay;
+mod scale;
+mod solvers;
+
+use shared::answer::{json_string, Answer};
+use shared::event;
+use solvers::Solver;
+
+struct Run {
+    year: u32,
+    day: u32,
+    part: u32,
+    solver: String,
+    answer: Result<Answer, String>,
+    elapsed: Duration,
+    // whether the answer matches the known one, if there is one
+    correct: Option<bool>,
+}
+
+fn run_solver(solver: &Solver, input: &str, expected: Option<&Answer>) -> Run {
+    let started = Instant::now();
+    let answer = match panic::catch_unwind(AssertUnwindSafe(|| (solver.solve)(input))) {
+        Ok(answer) => answer,
+        // an overflow in --checked mode or a broken invariant in --check mode
+        // says what went wrong
+        Err(payload) => {
+            let why = match payload.downcast_ref::<String>() {
+                Some(why) => why.as_str(),
+                None => payload
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .unwrap_or("unknown panic"),
+            };
+            Err(format!("the solver panicked: {}", why))
+        }
+    };
+    let correct = expected.map(|expected| answer.as_ref() == Ok(expected));
+    Run {
+        year: solver.year,
+        day: solver.day,
+        part: solver.part,
+        solver: solver.name.clone(),
+        answer,
+        elapsed: started.elapsed(),
+        correct,
+    }
+}
+
+// the built-in solvers followed by the plugins, with any plugin that couldn't
+// be loaded reported on stderr
+fn all_solvers() -> Vec<Solver> {
+    let dir = shared::cli::flag_value("--plugins").unwrap_or("./plugins".to_string());
+    let (plugins, errors) = plugins::discover(Path::new(&dir));
+    for why in errors {
+        eprintln!("skipping plugin: {}", why);
+    }
+    let params = solvers::Params {
+        max: shared::cli::flag_value("--max").map(|m| m.parse().expect("--max should be a number")),
+    };
+    let mut solvers = solvers::builtin(&params);
+    for plugin in plugins {
+        solvers.push(Solver {
+            year: plugin.year,
+            day: plugin.day,
+            part: plugin.part,
+            name: plugin.name.clone(),
+            solve: Box::new(move |s| plugin.solve(s).map(Answer::from)),
+        });
+    }
+    solvers
+}
+
+fn print_run(run: &Run) {
+    let time = format!("({:.1?})", run.elapsed);
+    if shared::cli::is_json() {
+        let answer = match &run.answer {
+            Ok(answer) => format!(
+                "\"kind\":\"{}\",\"answer\":{}",
+                answer.kind(),
+                answer.to_json()
+            ),
+            Err(why) => format!("\"error\":{}", json_string(why)),
+        };
+        let correct = match run.correct {
+            Some(correct) => format!(",\"correct\":{}", correct),
+            None => "".to_string(),
+        };
+        println!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"solver\":{},{}{},\"ms\":{:.3}}}",
+            run.year,
+            run.day,
+            run.part,
+            json_string(&run.solver),
+            answer,
+            correct,
+            run.elapsed.as_secs_f64() * 1000.0
+        );
+        return;
+    }
+    let time = match run.correct {
+        Some(true) => format!("{} correct", time),
+        Some(false) => format!("{} WRONG", time),
+        None => time,
+    };
+    match &run.answer {
+        // pictures (day 10 part 2) go under the name
+        Ok(Answer::Image(rows)) => {
+            println!("  {:<24} {}", run.solver, time);
+            for row in rows {
+                println!("    {}", row);
+            }
+        }
+        Ok(answer) => println!("  {:<24} {:<20} {}", run.solver, answer, time),
+        Err(why) => println!("  {:<24} error: {} {}", run.solver, why, time),
+    }
+}
+
+fn history_path() -> String {
+    shared::cli::flag_value("--history").unwrap_or(history::DEFAULT_PATH.to_string())
+}
+
+fn list(solvers: &Vec<Solver>) {
+    for solver in solvers {
+        println!(
+            "{} day {:>2} part {}: {}",
+            solver.year, solver.day, solver.part, solver.name
+        );
+    }
+}
+
+fn main() {
+    let positionals = shared::cli::positionals();
+    if positionals.first().map(|p| p.as_str()) == Some("replay") {
+        let file = match positionals.get(1) {
+            Some(file) => file,
+            None => {
+                eprintln!("usage: runner replay [--speed=N] [--seek=N] [--stop=N] FILE");
+                process::exit(2);
+            }
+        };
+        let number = |flag: &str| {
+            shared::cli::flag_value(flag).map(|n| {
+                n.parse()
+                    .unwrap_or_else(|_| panic!("{} should be a number", flag))
+            })
+        };
+        let speed = number("--speed").unwrap_or(10) as u32;
+        let seek = number("--seek").unwrap_or(0);
+        process::exit(replay::run(file, speed, seek, number("--stop")));
+    }
+
+    let solvers = all_solvers();
+
+    if shared::cli::has_flag("--list") {
+        list(&solvers);
+        return;
+    }
+
+    let year: u32 = match shared::cli::flag_value("--year") {
+        Some(year) => year.parse().expect("--year should be a number"),
+        None => solvers::latest_year(),
+    };
+    if positionals.first().map(|p| p.as_str()) == Some("scale") {
+        let day =
+            shared::cli::flag_value("--day").map(|d| d.parse().expect("--day should be a number"));
+        process::exit(scale::run(year, day));
+    }
+    if positionals.first().map(|p| p.as_str()) == Some("history") {
+        let day =
+            shared::cli::flag_value("--day").map(|d| d.parse().expect("--day should be a number"));
+        let threshold = match shared::cli::flag_value("--threshold") {
+            Some(threshold) => threshold.parse().expect("--threshold should be a number"),
+            None => history::DEFAULT_THRESHOLD,
+        };
+        process::exit(history::run(&history_path(), year, day, threshold));
+    }
+
+    let day: u32 = match shared::cli::flag_value("--day").map(|d| d.parse()) {
+        Some(Ok(day)) => day,
+        _ => {
+            eprintln!("usage: runner [--year=YYYY] --day=N [--part=P] [--plugins=DIR] [input.txt]");
+            process::exit(2);
+        }
+    };
+
+    if positionals.first().map(|p| p.as_str()) == Some("batch") {
+        let dir = match positionals.get(1) {
+            Some(dir) => dir,
+            None => {
+                eprintln!("usage: runner batch [--year=YYYY] --day=N [--threads=T] DIR");
+                process::exit(2);
+            }
+        };
+        let threads = match shared::cli::flag_value("--threads") {
+            Some(threads) => threads.parse().expect("--threads should be a number"),
+            None => thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+        process::exit(batch::run(&solvers, year, day, Path::new(dir), threads));
+    }
+
+    if positionals.first().map(|p| p.as_str()) == Some("lint") {
+        let (file, linter) = match (positionals.get(1), solvers::linter(year, day)) {
+            (Some(file), Some(linter)) => (file, linter),
+            (None, _) => {
+                eprintln!("usage: runner lint [--year=YYYY] --day=N FILE");
+                process::exit(2);
+            }
+            (_, None) => {
+                eprintln!("there is no lint for {} day {}", year, day);
+                process::exit(1);
+            }
+        };
+        process::exit(lint::run(linter, file));
+    }
+
+    let part: Option<u32> =
+        shared::cli::flag_value("--part").map(|p| p.parse().expect("--part should be a number"));
+    let inputs =
+        PathBuf::from(shared::cli::flag_value("--inputs").unwrap_or("./inputs".to_string()));
+    let filename = match shared::cli::filename() {
+        Some(filename) => filename,
+        None => event::input_path(&inputs, year, day).display().to_string(),
+    };
+    let known = match event::Answers::load(&inputs, year) {
+        Ok(answers) => answers,
+        Err(why) => {
+            eprintln!("ignoring the known answers: {}", why);
+            event::Answers::default()
+        }
+    };
+    let input = match fs::read_to_string(&filename) {
+        Err(why) => panic!("couldn't read {}: {}", filename, why),
+        Ok(input) => input,
+    };
+
+    let mut parts: Vec<u32> = solvers
+        .iter()
+        .filter(|s| s.year == year && s.day == day && part.is_none_or(|p| s.part == p))
+        .map(|s| s.part)
+        .collect();
+    parts.sort();
+    parts.dedup();
+    if parts.is_empty() {
+        eprintln!("there are no solvers for {} day {}", year, day);
+        process::exit(1);
+    }
+
+    let mut entries: Vec<history::Entry> = vec![];
+    for part in parts {
+        if !shared::cli::is_json() {
+            println!("{}", event::banner(year, day, Some(part)));
+        }
+        let mut answers: Vec<Answer> = vec![];
+        let solvers = solvers
+            .iter()
+            .filter(|s| s.year == year && s.day == day && s.part == part);
+        for solver in solvers {
+            let run = run_solver(solver, &input, known.get(day, part));
+            print_run(&run);
+            entries.push(history::Entry::new(&run, &input));
+            if let Ok(answer) = run.answer {
+                answers.push(answer);
+            }
+        }
+        answers.dedup();
+        if answers.len() > 1 && !shared::cli::is_json() {
+            println!("  the answers don't agree");
+        }
+    }
+
+    // checking makes the runs slower on purpose, so their times aren't kept
+    let recorded = ["--checked", "--check", "--no-history"]
+        .iter()
+        .all(|flag| !shared::cli::has_flag(flag));
+    if recorded {
+        if let Err(why) = history::append(&history_path(), &entries) {
+            eprintln!("not recorded in the history: {}", why);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_reports_a_broken_invariant() {
+        shared::invariant::set_enabled(true);
+        // x is visited but / never lists it
+        let solver = Solver::new(2022, 7, 2, |_| {
+            shared::invariant::check(
+                "day07 / holds every file",
+                || false,
+                || "/ is 100, all the files add up to 150".to_string(),
+            );
+            Ok(Answer::Int(100))
+        });
+        let run = run_solver(&solver, "", None);
+        assert_eq!(
+            run.answer,
+            Err(
+                "the solver panicked: invariant broken, day07 / holds every file: \
+                 / is 100, all the files add up to 150"
+                    .to_string()
+            )
+        );
+    }
+}
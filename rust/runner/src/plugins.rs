@@ -0,0 +1,315 @@
+// Loading solver plugins (see shared/plugin.rs for the ABI).
+//
+// Every shared library in the plugins directory is opened and asked for its
+// info. A library that can't be loaded, or was built for a different ABI
+// version, is reported and skipped rather than stopping the run. Libraries are
+// never closed, so their solve functions stay valid until the runner exits.
+
+use std::ffi::CStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::shared::plugin::{PluginInfo, SolveFn, ABI_VERSION};
+
+// a plugin that still wants a bigger buffer after this many calls never gets an answer out
+const SOLVE_TRIES: usize = 3;
+
+pub struct Plugin {
+    path: PathBuf,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub name: String,
+    solve: SolveFn,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> Result<Plugin, String> {
+        let info = unsafe { dl::load_info(path)? };
+        unsafe { Plugin::from_info(path, info) }
+    }
+
+    // info has to point to a PluginInfo that lives as long as the plugin
+    unsafe fn from_info(path: &Path, info: *const PluginInfo) -> Result<Plugin, String> {
+        if info.is_null() {
+            return Err(format!("{} has no plugin info", path.display()));
+        }
+        let info = &*info;
+        if info.abi_version != ABI_VERSION {
+            return Err(format!(
+                "{} is for plugin ABI version {}, the runner is version {}",
+                path.display(),
+                info.abi_version,
+                ABI_VERSION
+            ));
+        }
+        let name = if info.name.is_null() {
+            path.display().to_string()
+        } else {
+            CStr::from_ptr(info.name).to_string_lossy().to_string()
+        };
+        Ok(Plugin {
+            path: path.to_path_buf(),
+            year: info.year,
+            day: info.day,
+            part: info.part,
+            name,
+            solve: info.solve,
+        })
+    }
+
+    pub fn solve(&self, input: &str) -> Result<String, String> {
+        let mut answer: Vec<u8> = vec![0; 4096];
+        for _ in 0..SOLVE_TRIES {
+            // the input is all there to read and the answer all there to write
+            let result = unsafe {
+                (self.solve)(
+                    input.as_ptr(),
+                    input.len(),
+                    answer.as_mut_ptr(),
+                    answer.len(),
+                )
+            };
+            let len = result.unsigned_abs() as usize;
+            if len > answer.len() {
+                // it didn't fit, so try again with room for all of it
+                answer = vec![0; len];
+                continue;
+            }
+            let text = String::from_utf8_lossy(&answer[..len]).to_string();
+            return if result < 0 { Err(text) } else { Ok(text) };
+        }
+        Err(format!(
+            "{} ({}) still wanted a bigger buffer after {} tries",
+            self.name,
+            self.path.display(),
+            SOLVE_TRIES
+        ))
+    }
+}
+
+// the plugins in dir, sorted by file name, and an error for each library that
+// couldn't be loaded. a missing dir just has no plugins
+pub fn discover(dir: &Path) -> (Vec<Plugin>, Vec<String>) {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+        Err(_) => return (vec![], vec![]),
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().and_then(|e| e.to_str()) == Some(std::env::consts::DLL_EXTENSION)
+            })
+            .collect(),
+    };
+    paths.sort();
+
+    let mut plugins: Vec<Plugin> = vec![];
+    let mut errors: Vec<String> = vec![];
+    for path in paths {
+        match Plugin::load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(why) => errors.push(why),
+        }
+    }
+    (plugins, errors)
+}
+
+#[cfg(unix)]
+mod dl {
+    use std::ffi::{CStr, CString};
+    use std::os::raw::{c_char, c_int, c_void};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    use crate::shared::plugin::{InfoFn, PluginInfo, INFO_SYMBOL};
+
+    const RTLD_NOW: c_int = 2;
+
+    #[cfg_attr(target_os = "linux", link(name = "dl"))]
+    extern "C" {
+        fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        fn dlerror() -> *mut c_char;
+    }
+
+    unsafe fn last_error() -> String {
+        let error = dlerror();
+        if error.is_null() {
+            "unknown error".to_string()
+        } else {
+            CStr::from_ptr(error).to_string_lossy().to_string()
+        }
+    }
+
+    pub unsafe fn load_info(path: &Path) -> Result<*const PluginInfo, String> {
+        let filename = match CString::new(path.as_os_str().as_bytes()) {
+            Ok(filename) => filename,
+            Err(_) => return Err(format!("{} is not a valid path", path.display())),
+        };
+        let handle = dlopen(filename.as_ptr(), RTLD_NOW);
+        if handle.is_null() {
+            return Err(format!(
+                "couldn't load {}: {}",
+                path.display(),
+                last_error()
+            ));
+        }
+        let symbol = CString::new(INFO_SYMBOL).unwrap();
+        let info_fn = dlsym(handle, symbol.as_ptr());
+        if info_fn.is_null() {
+            return Err(format!(
+                "{} is not a plugin, it has no {}",
+                path.display(),
+                INFO_SYMBOL
+            ));
+        }
+        let info_fn: InfoFn = std::mem::transmute(info_fn);
+        Ok(info_fn())
+    }
+}
+
+#[cfg(not(unix))]
+mod dl {
+    use std::path::Path;
+
+    use crate::shared::plugin::PluginInfo;
+
+    pub unsafe fn load_info(path: &Path) -> Result<*const PluginInfo, String> {
+        Err(format!(
+            "couldn't load {}: plugins are only supported on unix",
+            path.display()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::plugin::call_solve;
+    use std::os::raw::c_char;
+
+    fn repeat(s: &str) -> Result<String, String> {
+        match s.trim().parse::<usize>() {
+            Ok(n) => Ok("x".repeat(n)),
+            Err(_) => Err(format!("{:?} is not a number", s)),
+        }
+    }
+
+    unsafe extern "C" fn solve_repeat(
+        input: *const u8,
+        len: usize,
+        answer: *mut u8,
+        cap: usize,
+    ) -> i64 {
+        unsafe { call_solve(input, len, answer, cap, repeat) }
+    }
+
+    fn panics(_: &str) -> Result<String, String> {
+        panic!("oops");
+    }
+
+    unsafe extern "C" fn solve_panics(
+        input: *const u8,
+        len: usize,
+        answer: *mut u8,
+        cap: usize,
+    ) -> i64 {
+        unsafe { call_solve(input, len, answer, cap, panics) }
+    }
+
+    // always says the answer is one byte longer than the buffer it was given
+    unsafe extern "C" fn solve_greedy(_: *const u8, _: usize, _: *mut u8, cap: usize) -> i64 {
+        cap as i64 + 1
+    }
+
+    fn info(abi_version: u32, solve: SolveFn) -> PluginInfo {
+        PluginInfo {
+            abi_version,
+            year: 2022,
+            day: 1,
+            part: 2,
+            name: "repeat\0".as_ptr() as *const c_char,
+            solve,
+        }
+    }
+
+    #[test]
+    fn test_solve_through_the_abi() {
+        let info = info(ABI_VERSION, solve_repeat);
+        let plugin = unsafe { Plugin::from_info(Path::new("test"), &info) }.unwrap();
+        assert_eq!((plugin.year, plugin.day, plugin.part), (2022, 1, 2));
+        assert_eq!(plugin.name, "repeat");
+        assert_eq!(plugin.solve("3"), Ok("xxx".to_string()));
+        assert_eq!(plugin.solve("").unwrap_err(), "\"\" is not a number");
+        // bigger than the first buffer, so it has to ask again
+        assert_eq!(plugin.solve("10000").unwrap().len(), 10000);
+    }
+
+    #[test]
+    fn test_panics_are_errors() {
+        let info = info(ABI_VERSION, solve_panics);
+        let plugin = unsafe { Plugin::from_info(Path::new("test"), &info) }.unwrap();
+        assert_eq!(plugin.solve("1"), Err("the solver panicked".to_string()));
+    }
+
+    #[test]
+    fn test_asking_for_more_room_gives_up() {
+        let info = info(ABI_VERSION, solve_greedy);
+        let plugin = unsafe { Plugin::from_info(Path::new("test"), &info) }.unwrap();
+        assert_eq!(
+            plugin.solve("1").unwrap_err(),
+            "repeat (test) still wanted a bigger buffer after 3 tries"
+        );
+    }
+
+    // builds the example plugin with rustc and loads it the way the runner does
+    #[test]
+    fn test_load_the_example_plugin() {
+        let source = Path::new("plugins/day01_top3/src/lib.rs");
+        let root = std::env::current_dir()
+            .unwrap()
+            .ancestors()
+            .find(|dir| dir.join(source).exists())
+            .expect("the example plugin's source")
+            .to_path_buf();
+        let dir = std::env::temp_dir().join(format!("aoc-plugins-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let library = dir.join(format!(
+            "{}day01_top3.{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_EXTENSION
+        ));
+        let rustc = std::env::var("RUSTC").unwrap_or("rustc".to_string());
+        let built = std::process::Command::new(rustc)
+            .args([
+                "--edition",
+                "2021",
+                "--crate-type",
+                "cdylib",
+                "-A",
+                "warnings",
+                "-o",
+            ])
+            .arg(&library)
+            .arg(root.join(source))
+            .status()
+            .unwrap();
+        assert!(built.success());
+
+        let (plugins, errors) = discover(&dir);
+        assert_eq!(errors, Vec::<String>::new());
+        assert_eq!(plugins.len(), 1);
+        let plugin = &plugins[0];
+        assert_eq!((plugin.year, plugin.day, plugin.part), (2022, 1, 2));
+        assert_eq!(plugin.name, "day01 top three");
+        let example = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+        assert_eq!(plugin.solve(example), Ok("45000".to_string()));
+        assert!(plugin.solve("1\nx\n").is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_wrong_abi_version() {
+        let info = info(ABI_VERSION + 1, solve_repeat);
+        assert!(unsafe { Plugin::from_info(Path::new("test"), &info) }.is_err());
+    }
+}
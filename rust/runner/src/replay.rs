@@ -0,0 +1,44 @@
+// Plays back a recording made by one of the simulation days with --record=FILE
+// (see shared/replay.rs), without needing the input or solving it again.
+//
+//     runner replay [--speed=N] [--seek=N] [--stop=N] FILE
+//
+// The header of the recording says what kind of model it is, and so which
+// day's library draws it (each year lists its players, see years.rs).
+
+use std::fs;
+
+use crate::shared;
+use crate::solvers;
+
+// plays the recording in the file, and returns the exit code
+pub fn run(path: &str, speed: u32, seek: usize, stop: Option<usize>) -> i32 {
+    let data = match fs::read_to_string(path) {
+        Err(why) => {
+            eprintln!("couldn't read {}: {}", path, why);
+            return 1;
+        }
+        Ok(data) => data,
+    };
+    let kind = match shared::replay::kind(&data) {
+        Err(why) => {
+            eprintln!("{}: {}", path, why);
+            return 1;
+        }
+        Ok(kind) => kind,
+    };
+    let play = match solvers::player(kind) {
+        Some(play) => play,
+        None => {
+            eprintln!("{}: there is no day that plays {} recordings", path, kind);
+            return 1;
+        }
+    };
+    match play(&data, speed, seek, stop) {
+        Err(why) => {
+            eprintln!("{}: {}", path, why);
+            1
+        }
+        Ok(()) => 0,
+    }
+}
@@ -0,0 +1,661 @@
+// Times a day's solvers on generated inputs of growing size, and fits how the
+// time grows with the size of the input, to find the days that need a better
+// algorithm rather than a faster machine.
+//
+//     runner scale [--year=2022] [--day=N] [--steps=N] [--budget=MS] [--seed=N]
+//
+// Each day has a generator that makes a valid input from a size, which the
+// length of the input grows in proportion to (a number of lines, the squares
+// in a grid, the items the monkeys hold, ...). The size starts small and
+// doubles for --steps steps (6 unless given), and each part is timed at each
+// size, the best of a few runs. A part stops growing once a run takes longer than
+// --budget milliseconds (1000 unless given), so a day that blows up doesn't
+// take all day.
+//
+// The growth is fitted against the size, so the days can be compared: time ~
+// n^1.0 is linear in the length of the input, ^2.0 quadratic. Without --day
+// every day with a generator is scaled, and the parts are listed at the end
+// from the fastest growing down.
+
+use std::collections::HashSet;
+
+use crate::run_solver;
+use crate::shared::cli;
+use crate::shared::rng::Rng;
+use crate::solvers::{self, Solver};
+
+// makes an input of a size
+type Generate = fn(&mut Rng, usize) -> String;
+
+// each day's generator and the size it starts at, by year and day
+const GENERATORS: &[(u32, u32, Generate, usize)] = &[
+    (2022, 1, calories, 64),
+    (2022, 2, strategy, 256),
+    (2022, 3, rucksacks, 64),
+    (2022, 4, assignments, 32),
+    (2022, 5, crates, 64),
+    (2022, 6, signal, 1024),
+    (2022, 7, terminal, 32),
+    (2022, 8, forest, 256),
+    (2022, 9, motions, 64),
+    (2022, 10, program, 64),
+    (2022, 11, monkeys, 16),
+    (2022, 12, heightmap, 256),
+    (2022, 13, packets, 16),
+    (2022, 14, rocks, 4),
+    (2022, 15, sensors, 8),
+    (2022, 16, valves, 4),
+    (2022, 17, jets, 64),
+    (2022, 18, droplet, 256),
+    (2022, 19, blueprints, 1),
+];
+
+const DEFAULT_STEPS: u32 = 6;
+const DEFAULT_BUDGET_MS: f64 = 1000.0;
+// a run is repeated (keeping the best) until it has run this often, or for this long
+const REPEATS: usize = 3;
+const REPEAT_MS: f64 = 100.0;
+// times below this are mostly noise, so they are left out of the fit when there are enough others
+const NOISE_MS: f64 = 0.05;
+
+struct Point {
+    size: usize,
+    bytes: usize,
+    ms: f64,
+    error: Option<String>,
+}
+
+struct Growth {
+    year: u32,
+    day: u32,
+    part: u32,
+    exponent: Option<f64>,
+}
+
+// times one part at each size, up to the budget
+fn measure(
+    solver: &Solver,
+    generate: Generate,
+    start: usize,
+    steps: u32,
+    budget: f64,
+) -> Vec<Point> {
+    let seed =
+        cli::flag_value("--seed").map_or(1, |s| s.parse().expect("--seed should be a number"));
+    let mut points: Vec<Point> = Vec::new();
+    for step in 0..steps {
+        let size = start << step;
+        let input = generate(&mut Rng::new(seed), size);
+        let mut best = f64::MAX;
+        let mut total = 0.0;
+        let mut error = None;
+        for _ in 0..REPEATS {
+            let run = run_solver(solver, &input, None);
+            let ms = run.elapsed.as_secs_f64() * 1000.0;
+            best = best.min(ms);
+            total += ms;
+            error = run.answer.err();
+            if total > REPEAT_MS {
+                break;
+            }
+        }
+        points.push(Point {
+            size,
+            bytes: input.len(),
+            ms: best,
+            error,
+        });
+        if best > budget {
+            break;
+        }
+    }
+    points
+}
+
+// the slope of the least squares line through (ln size, ln ms), or None
+// without two sizes to fit
+fn fit(points: &[Point]) -> Option<f64> {
+    let timed: Vec<&Point> = points.iter().filter(|p| p.ms >= NOISE_MS).collect();
+    let points: Vec<&Point> = if timed.len() >= 2 {
+        timed
+    } else {
+        points.iter().collect()
+    };
+    if points.len() < 2 {
+        return None;
+    }
+    let xy: Vec<(f64, f64)> = points
+        .iter()
+        .map(|p| ((p.size as f64).ln(), p.ms.max(1e-6).ln()))
+        .collect();
+    let n = xy.len() as f64;
+    let mean_x = xy.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = xy.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = xy.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = xy.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    if sxx == 0.0 {
+        return None;
+    }
+    Some(sxy / sxx)
+}
+
+fn print_points(points: &[Point]) {
+    println!("  {:>8} {:>10} {:>12}", "size", "bytes", "time");
+    for point in points {
+        let error = match &point.error {
+            Some(why) => format!("  error: {}", why),
+            None => "".to_string(),
+        };
+        println!(
+            "  {:>8} {:>10} {:>10.3}ms{}",
+            point.size, point.bytes, point.ms, error
+        );
+    }
+}
+
+fn describe(exponent: Option<f64>) -> String {
+    match exponent {
+        Some(exponent) => format!("time ~ n^{:.2}", exponent),
+        None => "not enough sizes to fit".to_string(),
+    }
+}
+
+// scales the day, or every day with a generator, and returns the exit code
+pub fn run(year: u32, day: Option<u32>) -> i32 {
+    let steps: u32 = cli::flag_value("--steps").map_or(DEFAULT_STEPS, |s| {
+        s.parse().expect("--steps should be a number")
+    });
+    let budget: f64 = cli::flag_value("--budget").map_or(DEFAULT_BUDGET_MS, |b| {
+        b.parse().expect("--budget should be a number")
+    });
+    let generators: Vec<&(u32, u32, Generate, usize)> = GENERATORS
+        .iter()
+        .filter(|(y, d, _, _)| *y == year && day.is_none_or(|day| *d == day))
+        .collect();
+    if generators.is_empty() {
+        eprintln!("there is no generator for {} day {:?}", year, day);
+        return 1;
+    }
+
+    let solvers = solvers::builtin(&solvers::Params::default());
+    let mut growths: Vec<Growth> = Vec::new();
+    for (year, day, generate, start) in generators {
+        let parts = solvers.iter().filter(|s| s.year == *year && s.day == *day);
+        for solver in parts {
+            println!("{} day {} part {}", year, day, solver.part);
+            let points = measure(solver, *generate, *start, steps, budget);
+            print_points(&points);
+            let exponent = fit(&points);
+            println!("  {}", describe(exponent));
+            growths.push(Growth {
+                year: *year,
+                day: *day,
+                part: solver.part,
+                exponent,
+            });
+        }
+    }
+
+    if growths.len() > 1 {
+        growths.sort_by(|a, b| {
+            let a = a.exponent.unwrap_or(f64::MIN);
+            b.exponent.unwrap_or(f64::MIN).total_cmp(&a)
+        });
+        println!("fastest growing first:");
+        for g in growths {
+            println!(
+                "  {} day {:>2} part {}  {}",
+                g.year,
+                g.day,
+                g.part,
+                describe(g.exponent)
+            );
+        }
+    }
+    0
+}
+
+// a number from 0 up to (but not including) n, as a usize
+fn below(rng: &mut Rng, n: usize) -> usize {
+    rng.below(n as u64) as usize
+}
+
+fn calories(rng: &mut Rng, elves: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..elves {
+        for _ in 0..rng.range(1, 5) {
+            s += &format!("{}\n", rng.range(1000, 60000));
+        }
+        s += "\n";
+    }
+    s
+}
+
+fn strategy(rng: &mut Rng, rounds: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..rounds {
+        let them = ["A", "B", "C"][below(rng, 3)];
+        let me = ["X", "Y", "Z"][below(rng, 3)];
+        s += &format!("{} {}\n", them, me);
+    }
+    s
+}
+
+const LETTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// groups of three rucksacks whose only item in common is the badge
+fn rucksacks(rng: &mut Rng, groups: usize) -> String {
+    let letters: Vec<char> = LETTERS.chars().collect();
+    let mut s = String::new();
+    for _ in 0..groups {
+        let badge = letters[below(rng, letters.len())];
+        let others: Vec<char> = letters.iter().copied().filter(|c| *c != badge).collect();
+        // each elf of the group has its own third of the other letters
+        for elf in others.chunks(others.len() / 3).take(3) {
+            let mut sack: Vec<char> = (0..rng.range(5, 15))
+                .map(|_| elf[below(rng, elf.len())])
+                .collect();
+            sack.push(badge);
+            // both compartments share an item too
+            sack.push(sack[0]);
+            let sack: String = sack.into_iter().collect();
+            s += &sack;
+            s += "\n";
+        }
+    }
+    s
+}
+
+// the sections grow with the number of pairs
+fn assignments(rng: &mut Rng, pairs: usize) -> String {
+    let mut s = String::new();
+    let mut section = || {
+        let a = rng.range(1, pairs as i64);
+        let b = rng.range(a, pairs as i64);
+        format!("{}-{}", a, b)
+    };
+    for _ in 0..pairs {
+        let first = section();
+        s += &format!("{},{}\n", first, section());
+    }
+    s
+}
+
+// nine stacks, with as many crates as commands
+fn crates(rng: &mut Rng, commands: usize) -> String {
+    const STACKS: usize = 9;
+    let height = commands / STACKS + 1;
+    let mut s = String::new();
+    for _ in 0..height {
+        let row: Vec<String> = (0..STACKS)
+            .map(|_| format!("[{}]", (b'A' + below(rng, 26) as u8) as char))
+            .collect();
+        s += &row.join(" ");
+        s += "\n";
+    }
+    let numbers: Vec<String> = (1..=STACKS).map(|n| format!(" {} ", n)).collect();
+    s += &numbers.join(" ");
+    s += "\n\n";
+    let mut heights = [height; STACKS];
+    for _ in 0..commands {
+        let from = loop {
+            let from = below(rng, STACKS);
+            if heights[from] > 0 {
+                break from;
+            }
+        };
+        let to = (from + 1 + below(rng, STACKS - 1)) % STACKS;
+        let n = 1 + below(rng, heights[from].min(5));
+        heights[from] -= n;
+        heights[to] += n;
+        s += &format!("move {} from {} to {}\n", n, from + 1, to + 1);
+    }
+    s
+}
+
+// no marker until the very end
+fn signal(rng: &mut Rng, length: usize) -> String {
+    let mut s: String = (0..length)
+        .map(|_| ['a', 'b', 'c'][below(rng, 3)])
+        .collect();
+    s += "abcdefghijklmn\n";
+    s
+}
+
+// a tree of directories, walked depth first
+fn terminal(rng: &mut Rng, dirs: usize) -> String {
+    // each directory's parent is one made before it
+    let mut children: Vec<Vec<usize>> = vec![vec![]; dirs];
+    for dir in 1..dirs {
+        children[below(rng, dir)].push(dir);
+    }
+    let mut s = "$ cd /\n".to_string();
+    let mut stack: Vec<Option<usize>> = vec![Some(0)];
+    while let Some(next) = stack.pop() {
+        let dir = match next {
+            Some(dir) => dir,
+            None => {
+                s += "$ cd ..\n";
+                continue;
+            }
+        };
+        if dir != 0 {
+            s += &format!("$ cd d{}\n", dir);
+        }
+        s += "$ ls\n";
+        for child in &children[dir] {
+            s += &format!("dir d{}\n", child);
+        }
+        for file in 0..rng.range(1, 3) {
+            s += &format!("{} f{}.txt\n", rng.range(1000, 300000), file);
+        }
+        if dir != 0 {
+            stack.push(None);
+        }
+        for child in children[dir].iter().rev() {
+            stack.push(Some(*child));
+        }
+    }
+    s
+}
+
+// a square grid of about as many trees
+fn forest(rng: &mut Rng, trees: usize) -> String {
+    let side = (trees as f64).sqrt() as usize;
+    let mut s = String::new();
+    for _ in 0..side {
+        let row: String = (0..side)
+            .map(|_| char::from(b'0' + below(rng, 10) as u8))
+            .collect();
+        s += &row;
+        s += "\n";
+    }
+    s
+}
+
+fn motions(rng: &mut Rng, moves: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..moves {
+        let direction = ["U", "D", "L", "R"][below(rng, 4)];
+        s += &format!("{} {}\n", direction, rng.range(1, 20));
+    }
+    s
+}
+
+fn program(rng: &mut Rng, instructions: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..instructions {
+        if rng.chance(3) {
+            s += "noop\n";
+        } else {
+            s += &format!("addx {}\n", rng.range(-10, 10));
+        }
+    }
+    s
+}
+
+// eight monkeys like the puzzle's, each with its own prime, sharing the items
+fn monkeys(rng: &mut Rng, items: usize) -> String {
+    const DIVISORS: [u32; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+    let count = DIVISORS.len();
+    let mut held: Vec<Vec<String>> = vec![vec![]; count];
+    for _ in 0..items {
+        held[below(rng, count)].push(rng.range(50, 99).to_string());
+    }
+    let mut s = String::new();
+    for (monkey, divisor) in DIVISORS.iter().enumerate() {
+        let operation = match below(rng, 3) {
+            0 => format!("old + {}", rng.range(1, 8)),
+            1 => format!("old * {}", rng.range(2, 19)),
+            _ => "old * old".to_string(),
+        };
+        let mut other = || (monkey + 1 + below(rng, count - 1)) % count;
+        let (if_true, if_false) = (other(), other());
+        s += &format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey {}\n\n",
+            monkey,
+            held[monkey].join(", "),
+            operation,
+            divisor,
+            if_true,
+            if_false
+        );
+    }
+    s
+}
+
+// a square grid of about as many squares, a slope from S in one corner up to
+// E in the other with some bumps on the way
+fn heightmap(rng: &mut Rng, squares: usize) -> String {
+    let side = (squares as f64).sqrt() as usize;
+    let mut s = String::new();
+    for y in 0..side {
+        for x in 0..side {
+            let c = if (x, y) == (0, 0) {
+                'S'
+            } else if (x, y) == (side - 1, side - 1) {
+                'E'
+            } else {
+                let height = (x + y) * 25 / (2 * side - 2);
+                // the top row and the right side are a path that can always be climbed
+                let bump = if y == 0 || x == side - 1 {
+                    0
+                } else {
+                    below(rng, 2)
+                };
+                char::from(b'a' + height.saturating_sub(bump) as u8)
+            };
+            s.push(c);
+        }
+        s += "\n";
+    }
+    s
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items: Vec<String> = (0..below(rng, 5))
+        .map(|_| {
+            if depth < 3 && rng.chance(3) {
+                packet(rng, depth + 1)
+            } else {
+                rng.range(0, 10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn packets(rng: &mut Rng, pairs: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..pairs {
+        let left = packet(rng, 0);
+        s += &format!("{}\n{}\n\n", left, packet(rng, 0));
+    }
+    s
+}
+
+// the cave gets deeper with the number of paths, so there is more sand
+fn rocks(rng: &mut Rng, paths: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..paths {
+        let mut x = rng.range(480, 520);
+        let mut y = rng.range(5, 5 + paths as i64);
+        let mut points = vec![format!("{},{}", x, y)];
+        for turn in 0..rng.range(1, 3) {
+            if turn % 2 == 0 {
+                x += rng.range(-5, 5);
+            } else {
+                y += rng.range(1, 5);
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        s += &points.join(" -> ");
+        s += "\n";
+    }
+    s
+}
+
+// small enough to be searched like the example, 0..=20
+fn sensors(rng: &mut Rng, count: usize) -> String {
+    let mut s = String::new();
+    for _ in 0..count {
+        let (x, y) = (rng.range(0, 40), rng.range(0, 40));
+        s += &format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+            x,
+            y,
+            x + rng.range(-3, 3),
+            y + rng.range(-3, 3)
+        );
+    }
+    s
+}
+
+fn valve_name(n: usize) -> String {
+    let letter = |i: usize| char::from(b'A' + (i % 26) as u8);
+    format!("{}{}", letter(n / 26), letter(n))
+}
+
+// a ring of valves with a few shortcuts, half of them with a flow rate
+fn valves(rng: &mut Rng, count: usize) -> String {
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; count];
+    for valve in 0..count {
+        let mut connect = |a: usize, b: usize| {
+            if a != b && !tunnels[a].contains(&b) {
+                tunnels[a].push(b);
+                tunnels[b].push(a);
+            }
+        };
+        connect(valve, (valve + 1) % count);
+        if rng.chance(3) {
+            connect(valve, below(rng, count));
+        }
+    }
+    let mut s = String::new();
+    for (valve, to) in tunnels.iter().enumerate() {
+        let flow_rate = if valve > 0 && rng.chance(2) {
+            rng.range(1, 25)
+        } else {
+            0
+        };
+        let names: Vec<String> = to.iter().map(|t| valve_name(*t)).collect();
+        let tunnels = match names.len() {
+            1 => format!("tunnel leads to valve {}", names[0]),
+            _ => format!("tunnels lead to valves {}", names.join(", ")),
+        };
+        s += &format!(
+            "Valve {} has flow rate={}; {}\n",
+            valve_name(valve),
+            flow_rate,
+            tunnels
+        );
+    }
+    s
+}
+
+// the rocks don't start repeating until the jets have been round at least once
+fn jets(rng: &mut Rng, length: usize) -> String {
+    let mut s: String = (0..length).map(|_| ['<', '>'][below(rng, 2)]).collect();
+    s += "\n";
+    s
+}
+
+// different cubes scattered through a box about twice as wide as a solid
+// cube of them, so there are air pockets inside as well as around it
+fn droplet(rng: &mut Rng, cubes: usize) -> String {
+    let side = 2 * (cubes as f64).cbrt().ceil() as usize;
+    let mut placed: HashSet<(usize, usize, usize)> = HashSet::new();
+    let mut s = String::new();
+    while placed.len() < cubes {
+        let cube = (below(rng, side), below(rng, side), below(rng, side));
+        if placed.insert(cube) {
+            s += &format!("{},{},{}\n", cube.0, cube.1, cube.2);
+        }
+    }
+    s
+}
+
+// costs in the same ranges as the puzzle's, so each blueprint takes about as
+// long to search as a real one and the time grows with the count
+fn blueprints(rng: &mut Rng, count: usize) -> String {
+    let mut s = String::new();
+    for id in 1..=count {
+        s += &format!(
+            "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+             Each obsidian robot costs {} ore and {} clay. \
+             Each geode robot costs {} ore and {} obsidian.\n",
+            id,
+            2 + below(rng, 3),
+            2 + below(rng, 3),
+            2 + below(rng, 3),
+            5 + below(rng, 16),
+            2 + below(rng, 3),
+            5 + below(rng, 16)
+        );
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::answer::Answer;
+
+    #[test]
+    fn test_generated_inputs_are_valid() {
+        for (year, day, generate, start) in GENERATORS {
+            let lint = solvers::linter(*year, *day).unwrap();
+            for size in [*start, start * 2] {
+                let input = generate(&mut Rng::new(7), size);
+                assert_eq!(
+                    lint(&input),
+                    Vec::<String>::new(),
+                    "day {} size {}",
+                    day,
+                    size
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let solvers = solvers::builtin(&solvers::Params::default());
+        for (year, day, generate, start) in GENERATORS {
+            let input = generate(&mut Rng::new(7), *start);
+            for solver in solvers.iter().filter(|s| s.year == *year && s.day == *day) {
+                let answer: Result<Answer, String> = (solver.solve)(&input);
+                assert!(
+                    answer.is_ok(),
+                    "day {} part {}: {:?}",
+                    day,
+                    solver.part,
+                    answer
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit() {
+        let point = |size: usize, ms: f64| Point {
+            size,
+            bytes: size * 10,
+            ms,
+            error: None,
+        };
+        let quadratic: Vec<Point> = [100, 200, 400, 800]
+            .iter()
+            .map(|n| point(*n, (*n as f64).powi(2) / 1000.0))
+            .collect();
+        assert!((fit(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+        let linear: Vec<Point> = [100, 200, 400]
+            .iter()
+            .map(|n| point(*n, *n as f64))
+            .collect();
+        assert!((fit(&linear).unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(fit(&linear[..1]), None);
+    }
+}
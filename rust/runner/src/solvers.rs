@@ -0,0 +1,178 @@
+// The built-in solvers, registered by year.
+//
+// The years live outside the runner, in rust/YYYY next to their days' crates,
+// and rust/years.rs lists them (see there for what a year provides). Adding a
+// year is its directory and its line in years.rs, the runner doesn't change.
+
+#[path = "../../years.rs"]
+mod years;
+
+use years::{LINTERS, PLAYERS, YEARS};
+
+use crate::shared::answer::Answer;
+
+// solves a part from the input. Send and Sync so batch can share the solvers
+// between its threads
+pub type Solve = dyn Fn(&str) -> Result<Answer, String> + Send + Sync;
+
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub name: String,
+    pub solve: Box<Solve>,
+}
+
+impl Solver {
+    pub fn new(
+        year: u32,
+        day: u32,
+        part: u32,
+        solve: impl Fn(&str) -> Result<Answer, String> + Send + Sync + 'static,
+    ) -> Solver {
+        Solver {
+            year,
+            day,
+            part,
+            name: "built-in".to_string(),
+            solve: Box::new(solve),
+        }
+    }
+}
+
+// what a solver is given besides the input. max is how far a search goes, for
+// the days whose example searches less than the real input does (2022 day 15),
+// and each of those days has its own default
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Params {
+    pub max: Option<i64>,
+}
+
+// builds a year's solvers
+pub type YearSolvers = fn(&Params) -> Vec<Solver>;
+
+// the problems with an input, each as "line N: ..." when it is on a line
+pub type Linter = fn(&str) -> Vec<String>;
+
+// a year's linter for a day, if it has one
+pub type YearLinters = fn(u32) -> Option<Linter>;
+
+// draws a recording at a speed, from a step, up to a step (see replay.rs)
+pub type Player = fn(&str, u32, usize, Option<usize>) -> Result<(), String>;
+
+// each kind of recording a year's days make, and the player for it
+pub type YearPlayers = &'static [(&'static str, Player)];
+
+pub fn linter(year: u32, day: u32) -> Option<Linter> {
+    LINTERS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .and_then(|(_, linter)| linter(day))
+}
+
+// the player for a kind of recording, from whichever year's day makes them
+pub fn player(kind: &str) -> Option<Player> {
+    PLAYERS
+        .iter()
+        .flat_map(|(_, players)| players.iter())
+        .find(|(k, _)| *k == kind)
+        .map(|(_, player)| *player)
+}
+
+pub fn latest_year() -> u32 {
+    YEARS.last().map(|(year, _)| *year).unwrap_or(0)
+}
+
+pub fn builtin(params: &Params) -> Vec<Solver> {
+    YEARS
+        .iter()
+        .flat_map(|(_, solvers)| solvers(params))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::event::Answers;
+
+    #[test]
+    fn test_years_are_registered_once_in_order() {
+        let years: Vec<u32> = YEARS.iter().map(|(year, _)| *year).collect();
+        let mut sorted = years.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(years, sorted);
+        assert_eq!(latest_year(), 2022);
+    }
+
+    #[test]
+    fn test_solvers_have_their_years() {
+        for (year, solvers) in YEARS {
+            let params = Params::default();
+            assert!(solvers(&params).iter().all(|s| s.year == *year));
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        let answers = Answers::parse("# day part answer\n1 1 24000\n10 2 EHPZPJGL\n\n").unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Answer::Int(24000)));
+        assert_eq!(
+            answers.get(10, 2),
+            Some(&Answer::Text("EHPZPJGL".to_string()))
+        );
+        assert_eq!(answers.get(1, 2), None);
+        assert!(Answers::parse("1 24000\n").is_err());
+        assert!(Answers::parse("one 1 24000\n").is_err());
+    }
+
+    #[test]
+    fn test_answer_kinds() {
+        assert_eq!(Answer::parse(" 024000\n"), Answer::from(24000u16));
+        assert_eq!(Answer::from(56000011i128).to_string(), "56000011");
+        assert_eq!(
+            Answer::from("MCD".to_string()),
+            Answer::Text("MCD".to_string())
+        );
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::parse(&u128::MAX.to_string())
+        );
+
+        let crt = Answer::image(["##..", ".##."]);
+        assert_eq!(Answer::parse("##..\n.##.\n"), crt);
+        assert_eq!(Answer::parse("██  \n ██ "), crt);
+        assert_eq!(crt.to_string(), "##..\n.##.");
+        assert_eq!(crt.to_json(), "[\"##..\",\".##.\"]");
+        // rows of different widths aren't a picture
+        assert_eq!(Answer::parse("##\n.").kind(), "string");
+    }
+
+    #[test]
+    fn test_day07_sizes_past_u32() {
+        let input = "$ cd /\n$ ls\n3000000000 a.bin\n3000000000 b.bin\n";
+        let part2 = builtin(&Params::default())
+            .into_iter()
+            .find(|s| s.year == 2022 && s.day == 7 && s.part == 2)
+            .unwrap();
+        assert_eq!((part2.solve)(input), Ok(Answer::Int(6_000_000_000)));
+    }
+
+    #[test]
+    fn test_checked_overflow_names_the_value() {
+        use crate::shared::checked;
+        checked::set_enabled(true);
+        assert_eq!(checked::sum("a small sum", [100u8, 155]), 255);
+        let panicked = std::panic::catch_unwind(|| checked::mul("a big product", 16u8, 16));
+        let why = panicked.unwrap_err();
+        assert_eq!(
+            why.downcast_ref::<String>().unwrap(),
+            "overflow in a big product: 16 * 16"
+        );
+        let panicked = std::panic::catch_unwind(|| checked::sub("a difference", 1u8, 2));
+        assert_eq!(
+            panicked.unwrap_err().downcast_ref::<String>().unwrap(),
+            "overflow in a difference: 1 - 2"
+        );
+    }
+}
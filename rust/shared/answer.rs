@@ -0,0 +1,151 @@
+// The answer to a puzzle, so answers can be compared whatever kind they are.
+//
+// Most answers are numbers, of whatever width the day needed (a u16 sum of
+// priorities, an i128 tuning frequency). Some are strings (the crates on top
+// of the stacks), and some are pictures the puzzle draws and you read letters
+// off (the CRT), which are kept as rows of pixels.
+//
+// parse() is how an answer written down is read back, from answers.txt, a
+// batch .expected file or a plugin, and the same answer always parses to the
+// same value:
+//   - a whole number is an Int, so "024000" and "24000" are the same
+//   - two or more rows of # and . (or █ and space) the same width are an Image
+//   - anything else is Text, with the whitespace around it trimmed
+// A number too big for an i128 is kept as Text. Converting a String into an
+// Answer parses it too.
+//
+// Display gives the canonical form, an image being its rows of # and . with a
+// newline between them.
+
+use std::fmt;
+
+const LIT: char = '#';
+const DARK: char = '.';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    // rows of LIT and DARK pixels, all the same width
+    Image(Vec<String>),
+}
+
+impl Answer {
+    pub fn parse(s: &str) -> Answer {
+        if let Ok(n) = s.trim().parse::<i128>() {
+            return Answer::Int(n);
+        }
+        // only the blank lines around an image are trimmed, a dark pixel can be a space
+        let rows: Vec<&str> = s.trim_matches(|c| c == '\n' || c == '\r').lines().collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let is_pixel = |c: char| c == LIT || c == DARK || c == '█' || c == ' ';
+        let is_image = rows.len() > 1
+            && width > 0
+            && rows
+                .iter()
+                .all(|row| row.chars().count() == width && row.chars().all(is_pixel));
+        if is_image {
+            return Answer::image(rows);
+        }
+        Answer::Text(s.trim().to_string())
+    }
+
+    // an image from its rows, with # or █ for a lit pixel and anything else dark
+    pub fn image<S: AsRef<str>>(rows: impl IntoIterator<Item = S>) -> Answer {
+        Answer::Image(
+            rows.into_iter()
+                .map(|row| {
+                    row.as_ref()
+                        .chars()
+                        .map(|c| if c == LIT || c == '█' { LIT } else { DARK })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Int(_) => "int",
+            Answer::Text(_) => "string",
+            Answer::Image(_) => "image",
+        }
+    }
+
+    // a number, a string, or an array of the rows
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Int(n) => n.to_string(),
+            Answer::Text(s) => json_string(s),
+            Answer::Image(rows) => {
+                let rows: Vec<String> = rows.iter().map(|row| json_string(row)).collect();
+                format!("[{}]", rows.join(","))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    // pads like a string, so answers can be lined up with {:<20}
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => f.pad(&n.to_string()),
+            Answer::Text(s) => f.pad(s),
+            Answer::Image(rows) => f.pad(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty)*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(u8 u16 u32 u64 usize i8 i16 i32 i64 isize i128);
+
+// too big for an i128 is kept as its digits, as parse does
+impl From<u128> for Answer {
+    fn from(n: u128) -> Answer {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Text(n.to_string()),
+        }
+    }
+}
+
+// a string is parsed, so "24000" is the same answer as 24000
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::parse(&s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::parse(s)
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
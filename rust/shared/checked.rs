@@ -0,0 +1,119 @@
+// Checked arithmetic for the sums and products the solvers build up.
+//
+// The answers are accumulated with add, sub, mul and sum from here, each given
+// a name for the value saying which solver it belongs to ("day07 directory
+// size"). Normally they are plain +, - and *. With --checked every one of them
+// is checked instead, and an overflow stops the solver with the name and the
+// values that overflowed:
+//
+//     overflow in day07 directory size: 18446744073709551615 + 1
+//
+// so a generated input that is bigger than a width allows says so rather than
+// giving a wrong answer. The runner passes --checked on to every solver.
+
+use std::fmt::Display;
+use std::ops::{Add, Mul, Sub};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use super::cli;
+
+// not read from the command line yet, off, on
+const UNKNOWN: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+pub trait Number:
+    Copy + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! number {
+    ($($t:ty)*) => {
+        $(
+            impl Number for $t {
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: $t) -> Option<$t> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: $t) -> Option<$t> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+number!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize);
+
+// whether --checked was given, or what set_enabled last said
+pub fn is_enabled() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            let on = cli::has_flag("--checked");
+            set_enabled(on);
+            on
+        }
+        mode => mode == ON,
+    }
+}
+
+pub fn set_enabled(on: bool) {
+    MODE.store(if on { ON } else { OFF }, Ordering::Relaxed);
+}
+
+fn overflowed<T: Number>(what: &str, a: T, op: &str, b: T) -> ! {
+    panic!("overflow in {}: {} {} {}", what, a, op, b)
+}
+
+pub fn add<T: Number>(what: &str, a: T, b: T) -> T {
+    if !is_enabled() {
+        return a + b;
+    }
+    match a.checked_add(b) {
+        Some(n) => n,
+        None => overflowed(what, a, "+", b),
+    }
+}
+
+pub fn sub<T: Number>(what: &str, a: T, b: T) -> T {
+    if !is_enabled() {
+        return a - b;
+    }
+    match a.checked_sub(b) {
+        Some(n) => n,
+        None => overflowed(what, a, "-", b),
+    }
+}
+
+pub fn mul<T: Number>(what: &str, a: T, b: T) -> T {
+    if !is_enabled() {
+        return a * b;
+    }
+    match a.checked_mul(b) {
+        Some(n) => n,
+        None => overflowed(what, a, "*", b),
+    }
+}
+
+// starting from zero
+pub fn sum<T: Number + Default>(what: &str, values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::default(), |total, n| add(what, total, n))
+}
+
+// starting from one
+pub fn product<T: Number + From<u8>>(what: &str, values: impl IntoIterator<Item = T>) -> T {
+    values
+        .into_iter()
+        .fold(T::from(1), |total, n| mul(what, total, n))
+}
@@ -0,0 +1,153 @@
+// Checkpoints for the long simulations, so a run can be stopped and picked up
+// again later with --resume.
+//
+// A model implements Checkpoint to turn itself into plain text and back. The
+// file starts with a header naming the kind of model so a day 11 checkpoint
+// can't be loaded into day 14 by mistake:
+//
+//     # aoc checkpoint monkeys
+//     round 1234
+//     ...
+//
+// Flags:
+//   --checkpoint[=path]        save a checkpoint every so often while running
+//   --checkpoint-every=secs    how often to save (default 30 seconds)
+//   --resume[=path]            start from a saved checkpoint (and keep saving to it)
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use super::cli;
+
+const HEADER: &str = "# aoc checkpoint";
+const DEFAULT_INTERVAL_SECS: u64 = 30;
+
+pub trait Checkpoint: Sized {
+    // written into the header and checked when loading
+    const KIND: &'static str;
+
+    fn to_checkpoint(&self) -> String;
+    fn from_checkpoint(data: &str) -> Result<Self, String>;
+}
+
+pub struct Checkpointer {
+    path: String,
+    enabled: bool,
+    resume: bool,
+    interval: Duration,
+    last_saved: Instant,
+}
+
+impl Checkpointer {
+    pub fn from_args(default_path: &str) -> Checkpointer {
+        let path = cli::flag_value("--resume")
+            .or(cli::flag_value("--checkpoint"))
+            .unwrap_or(default_path.to_string());
+        let interval = match cli::flag_value("--checkpoint-every") {
+            Some(secs) => Duration::from_secs(secs.parse::<u64>().unwrap_or(DEFAULT_INTERVAL_SECS)),
+            None => Duration::from_secs(DEFAULT_INTERVAL_SECS),
+        };
+        let resume = cli::has_flag("--resume");
+        Checkpointer {
+            path,
+            enabled: resume || cli::has_flag("--checkpoint"),
+            resume,
+            interval,
+            last_saved: Instant::now(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // None if --resume wasn't given, otherwise the loaded model
+    pub fn resume<T: Checkpoint>(&self) -> Option<Result<T, String>> {
+        if !self.resume {
+            return None;
+        }
+        Some(load(&self.path))
+    }
+
+    // call this every step, it only writes the file when the interval is up
+    pub fn maybe_save<T: Checkpoint>(&mut self, state: &T) {
+        if self.enabled && self.last_saved.elapsed() >= self.interval {
+            if let Err(why) = self.save(state) {
+                eprintln!("couldn't save checkpoint {}: {}", self.path, why);
+            }
+        }
+    }
+
+    pub fn save<T: Checkpoint>(&mut self, state: &T) -> Result<(), String> {
+        self.last_saved = Instant::now();
+        save(&self.path, state)
+    }
+}
+
+pub fn save<T: Checkpoint>(path: &str, state: &T) -> Result<(), String> {
+    let data = format!("{} {}\n{}", HEADER, T::KIND, state.to_checkpoint());
+    // write to a temporary file first so a crash part way through writing
+    // doesn't leave a broken checkpoint behind
+    let tmp = format!("{}.tmp", path);
+    fs::write(&tmp, data).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+pub fn load<T: Checkpoint>(path: &str) -> Result<T, String> {
+    let data = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?;
+    let (header, body) = match data.split_once('\n') {
+        Some((header, body)) => (header, body),
+        None => (data.as_str(), ""),
+    };
+    let expected = format!("{} {}", HEADER, T::KIND);
+    if header.trim() != expected {
+        return Err(format!(
+            "{} is not a {} checkpoint (header was {:?})",
+            path,
+            T::KIND,
+            header
+        ));
+    }
+    T::from_checkpoint(body)
+}
+
+// checkpoints are "key value" lines, this finds the value for a key
+pub fn value<'a>(data: &'a str, key: &str) -> Result<&'a str, String> {
+    values(data, key)
+        .into_iter()
+        .next()
+        .ok_or(format!("checkpoint is missing {}", key))
+}
+
+// all the values for a key that can appear more than once
+pub fn values<'a>(data: &'a str, key: &str) -> Vec<&'a str> {
+    data.lines()
+        .filter_map(|line| match line.split_once(' ') {
+            Some((k, v)) if k == key => Some(v),
+            None if line == key => Some(""),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn parse<T: std::str::FromStr>(s: &str, what: &str) -> Result<T, String> {
+    s.trim()
+        .parse::<T>()
+        .map_err(|_| format!("checkpoint has a bad {}: {:?}", what, s))
+}
+
+pub fn parse_list<T: std::str::FromStr>(s: &str, what: &str) -> Result<Vec<T>, String> {
+    s.split_whitespace().map(|n| parse(n, what)).collect()
+}
+
+pub fn join_list<T: ToString>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,62 @@
+// Command line handling shared by the days.
+//
+// The first argument that doesn't start with "--" is the input file. Everything
+// else is a flag, either on its own (--json) or with a value (--resume=state.txt).
+//
+// Flags can also be kept in a parameters file given with --params=FILE, one per
+// line, with # for comments. The ones on the command line win, and the file is
+// read again each time so --watch picks up changes to it.
+
+pub fn args() -> Vec<String> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let prefix = "--params=";
+    if let Some(path) = args.iter().find(|a| a.starts_with(prefix)) {
+        let params = match std::fs::read_to_string(&path[prefix.len()..]) {
+            Err(why) => panic!("couldn't read {}: {}", &path[prefix.len()..], why),
+            Ok(params) => params,
+        };
+        args.extend(params_flags(&params));
+    }
+    args
+}
+
+// the flags in a parameters file. it can't name another one, or turn on --watch
+fn params_flags(s: &str) -> Vec<String> {
+    s.lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("--"))
+        .filter(|line| *line != "--watch" && !line.starts_with("--params="))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+pub fn filename() -> Option<String> {
+    args().into_iter().find(|a| !a.starts_with("--"))
+}
+
+// every argument that isn't a flag, for commands like "batch --day=1 DIR"
+pub fn positionals() -> Vec<String> {
+    args()
+        .into_iter()
+        .filter(|a| !a.starts_with("--"))
+        .collect()
+}
+
+pub fn has_flag(name: &str) -> bool {
+    args()
+        .iter()
+        .any(|a| a == name || a.starts_with(&format!("{}=", name)))
+}
+
+pub fn flag_value(name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    args()
+        .into_iter()
+        .find(|a| a.starts_with(&prefix))
+        .map(|a| a[prefix.len()..].to_string())
+}
+
+// machine readable output, nothing but the answers should be printed
+pub fn is_json() -> bool {
+    has_flag("--json")
+}
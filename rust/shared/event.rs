@@ -0,0 +1,81 @@
+// The event (year) a puzzle belongs to, and where its inputs and answers live.
+//
+// Nothing else in shared/ is tied to a year, so a new year's days can pull in
+// this same directory. Each day's library says which year it is with its YEAR
+// and DAY consts, and the runner keeps a registry of the years it has solvers
+// for. Inputs aren't kept in the repo, they go under an inputs directory laid
+// out by year:
+//
+//     inputs/2022/day01.txt
+//     inputs/2022/answers.txt
+//
+// answers.txt has the answers that are already known, one per line as
+// "day part answer", so the runner can say whether a solver got it right.
+// Answers drawn over several lines (2022 day 10 part 2) are written with \n
+// between the lines. Each is read as an Answer, so it's compared the same
+// way whatever kind of answer it is.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::answer::Answer;
+
+pub fn banner(year: u32, day: u32, part: Option<u32>) -> String {
+    match part {
+        Some(part) => format!("Advent of code {}, Day {} Part {}", year, day, part),
+        None => format!("Advent of code {}, Day {}", year, day),
+    }
+}
+
+pub fn input_path(inputs: &Path, year: u32, day: u32) -> PathBuf {
+    inputs
+        .join(year.to_string())
+        .join(format!("day{:02}.txt", day))
+}
+
+pub fn answers_path(inputs: &Path, year: u32) -> PathBuf {
+    inputs.join(year.to_string()).join("answers.txt")
+}
+
+// the known answers for one year, by day and part
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(u32, u32), Answer>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Answers, String> {
+        let mut answers: HashMap<(u32, u32), Answer> = HashMap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(3, ' ').collect();
+            let (day, part) = match fields[..] {
+                [day, part, _] => match (day.parse::<u32>(), part.parse::<u32>()) {
+                    (Ok(day), Ok(part)) => (day, part),
+                    _ => return Err(format!("line {}: bad day or part in {:?}", i + 1, line)),
+                },
+                _ => return Err(format!("line {}: expected \"day part answer\"", i + 1)),
+            };
+            let answer = Answer::parse(&fields[2].replace("\\n", "\n"));
+            answers.insert((day, part), answer);
+        }
+        Ok(Answers { answers })
+    }
+
+    // a year with no answers file just has no answers yet
+    pub fn load(inputs: &Path, year: u32) -> Result<Answers, String> {
+        let path = answers_path(inputs, year);
+        match fs::read_to_string(&path) {
+            Err(_) => Ok(Answers::default()),
+            Ok(s) => Answers::parse(&s).map_err(|why| format!("{}: {}", path.display(), why)),
+        }
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
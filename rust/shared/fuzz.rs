@@ -0,0 +1,298 @@
+// A small fuzzer for the input parsers.
+//
+// Each day has a parser that takes the puzzle input as a string and either
+// gives back its model or an error. Running a day with --fuzz throws random
+// and mutated inputs at that parser and reports any input that makes it panic.
+//
+//   --fuzz[=N]     run N inputs (default 10000)
+//   --seed=N       seed for the random number generator, to repeat a run
+//
+// The input file (if there is one) is used as the starting point for the
+// mutations, which finds a lot more than purely random text does. The
+// mutations work on characters and lines rather than bytes, so every input
+// is UTF-8 and gets as far as the parser. Inputs that panic are written to
+// crash-<name>-<n>.txt so they can be run again.
+//
+// fuzz_one() is the same shape as a libFuzzer target, so it can be called from
+// a `fuzz_target!(|data: &[u8]| { ... })` as well.
+
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::cli;
+use super::rng::Rng;
+
+const DEFAULT_ITERATIONS: u64 = 10000;
+const MAX_INPUT_LEN: usize = 4096;
+
+#[derive(Debug, PartialEq)]
+pub enum FuzzOutcome {
+    Parsed,
+    Rejected(String),
+    NotUtf8,
+    Panicked(String),
+}
+
+// runs one input through the parser, catching any panic
+pub fn fuzz_one<T>(data: &[u8], parse: &T) -> FuzzOutcome
+where
+    T: Fn(&str) -> Result<(), String>,
+{
+    let input = match std::str::from_utf8(data) {
+        Ok(s) => s,
+        Err(_) => return FuzzOutcome::NotUtf8,
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| parse(input))) {
+        Ok(Ok(())) => FuzzOutcome::Parsed,
+        Ok(Err(why)) => FuzzOutcome::Rejected(why),
+        Err(payload) => {
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "unknown panic".to_string()
+            };
+            FuzzOutcome::Panicked(message)
+        }
+    }
+}
+
+// how a run of the fuzzer went
+#[derive(Debug, Default, PartialEq)]
+pub struct FuzzCounts {
+    pub parsed: u64,
+    pub rejected: u64,
+    pub not_utf8: u64,
+    // the number of each input that panicked, the input and the panic message
+    pub crashes: Vec<(u64, Vec<u8>, String)>,
+}
+
+// runs iterations generated inputs through the parser
+pub fn run<T>(rng: &mut Rng, iterations: u64, corpus: &[Vec<u8>], parse: &T) -> FuzzCounts
+where
+    T: Fn(&str) -> Result<(), String>,
+{
+    let mut counts = FuzzCounts::default();
+    for i in 0..iterations {
+        let data = generate(rng, corpus);
+        match fuzz_one(&data, parse) {
+            FuzzOutcome::Parsed => counts.parsed += 1,
+            FuzzOutcome::Rejected(_) => counts.rejected += 1,
+            FuzzOutcome::NotUtf8 => counts.not_utf8 += 1,
+            FuzzOutcome::Panicked(message) => counts.crashes.push((i, data, message)),
+        }
+    }
+    counts
+}
+
+// the --fuzz entry point for a day, returns the number of inputs that panicked
+pub fn run_from_args<T>(name: &str, seed_file: &str, parse: T) -> u64
+where
+    T: Fn(&str) -> Result<(), String>,
+{
+    let iterations: u64 = match cli::flag_value("--fuzz") {
+        Some(n) => n.parse().expect("--fuzz should be a number"),
+        None => DEFAULT_ITERATIONS,
+    };
+    let seed: u64 = match cli::flag_value("--seed") {
+        Some(n) => n.parse().expect("--seed should be a number"),
+        None => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(1),
+    };
+    let corpus: Vec<Vec<u8>> = match fs::read(seed_file) {
+        Ok(data) => vec![data],
+        Err(_) => vec![],
+    };
+
+    println!(
+        "Fuzzing {} parser: {} inputs, seed {}, {} seed file(s)",
+        name,
+        iterations,
+        seed,
+        corpus.len()
+    );
+
+    // keep the default hook quiet while fuzzing, panics are reported below
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut rng = Rng::new(seed);
+    let counts = run(&mut rng, iterations, &corpus, &parse);
+    for (i, data, message) in &counts.crashes {
+        let crash_file = format!("crash-{}-{}.txt", name, i);
+        let _ = fs::write(&crash_file, data);
+        eprintln!("PANIC on input {} ({}): {}", i, crash_file, message);
+    }
+
+    panic::set_hook(hook);
+
+    println!(
+        "Parsed: {}, rejected: {}, not utf-8: {}, panicked: {}",
+        counts.parsed,
+        counts.rejected,
+        counts.not_utf8,
+        counts.crashes.len()
+    );
+    counts.crashes.len() as u64
+}
+
+// an input made up from nothing, or a mutation of one from the corpus. a
+// corpus file that isn't UTF-8 has its bad bytes replaced first
+fn generate(rng: &mut Rng, corpus: &[Vec<u8>]) -> Vec<u8> {
+    // a quarter of the time (or when there is nothing to mutate) use random characters
+    if corpus.is_empty() || rng.below(4) == 0 {
+        let len = rng.below(256) as usize;
+        let s: String = (0..len).map(|_| random_char(rng)).collect();
+        return s.into_bytes();
+    }
+    let seed = &corpus[rng.below(corpus.len() as u64) as usize];
+    let mut data: Vec<char> = String::from_utf8_lossy(seed).chars().collect();
+    let mutations = 1 + rng.below(8);
+    for _ in 0..mutations {
+        mutate(rng, &mut data);
+    }
+    data.truncate(MAX_INPUT_LEN);
+    data.into_iter().collect::<String>().into_bytes()
+}
+
+fn mutate(rng: &mut Rng, data: &mut Vec<char>) {
+    let len = data.len() as u64;
+    match rng.below(7) {
+        // change a character
+        0 if len > 0 => {
+            let i = rng.below(len) as usize;
+            data[i] = random_char(rng);
+        }
+        // insert a character
+        1 => {
+            let i = rng.below(len + 1) as usize;
+            let c = random_char(rng);
+            data.insert(i, c);
+        }
+        // remove a run of characters
+        2 if len > 0 => {
+            let i = rng.below(len) as usize;
+            let n = (1 + rng.below(16) as usize).min(data.len() - i);
+            data.drain(i..i + n);
+        }
+        // cut the input short
+        3 => {
+            let i = rng.below(len + 1) as usize;
+            data.truncate(i);
+        }
+        // duplicate a line, at the start of a line
+        4 if len > 0 => {
+            let lines: Vec<&[char]> = data.split(|c| *c == '\n').collect();
+            let mut line = lines[rng.below(lines.len() as u64) as usize].to_vec();
+            line.push('\n');
+            let starts: Vec<usize> = (0..data.len())
+                .filter(|i| *i == 0 || data[i - 1] == '\n')
+                .collect();
+            let i = starts[rng.below(starts.len() as u64) as usize];
+            data.splice(i..i, line);
+        }
+        // insert an interesting number
+        5 => {
+            let numbers = [
+                "0",
+                "-1",
+                "1",
+                "255",
+                "65536",
+                "2147483648",
+                "99999999999999999999",
+                "",
+            ];
+            let n = numbers[rng.below(numbers.len() as u64) as usize];
+            let i = rng.below(len + 1) as usize;
+            data.splice(i..i, n.chars());
+        }
+        // throw away a line break, or add one
+        _ => {
+            if let Some(i) = data.iter().position(|c| *c == '\n') {
+                data.remove(i);
+            } else {
+                data.push('\n');
+            }
+        }
+    }
+}
+
+// mostly characters that turn up in the puzzle inputs, and now and then any
+// character at all
+fn random_char(rng: &mut Rng) -> char {
+    let interesting: Vec<char> = "0123456789-,;:=[]() \n\r\tabcxyzABCXYZSE.#$"
+        .chars()
+        .collect();
+    match rng.below(10) {
+        // a surrogate isn't a char, so it stands for one that didn't decode
+        0 => char::from_u32(rng.below(0x11_0000) as u32).unwrap_or('\u{fffd}'),
+        _ => interesting[rng.below(interesting.len() as u64) as usize],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a parser with a bug: a number over 200 panics
+    fn parse_small_numbers(s: &str) -> Result<(), String> {
+        for line in s.lines() {
+            let n: u32 = line
+                .parse()
+                .map_err(|_| format!("{:?} is not a number", line))?;
+            assert!(n <= 200, "{} is too big", n);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzz_one() {
+        let parse = parse_small_numbers;
+        assert_eq!(fuzz_one(b"1\n2\n", &parse), FuzzOutcome::Parsed);
+        assert_eq!(
+            fuzz_one(b"x", &parse),
+            FuzzOutcome::Rejected("\"x\" is not a number".to_string())
+        );
+        assert_eq!(fuzz_one(b"\xff", &parse), FuzzOutcome::NotUtf8);
+        assert_eq!(
+            fuzz_one(b"300", &parse),
+            FuzzOutcome::Panicked("300 is too big".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_over_a_seeded_corpus() {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|_| {}));
+        let corpus = [b"1\n20\n3\n".to_vec(), b"100\n\n7\n".to_vec()];
+        let counts = run(&mut Rng::new(7), 2000, &corpus, &parse_small_numbers);
+        let again = run(&mut Rng::new(7), 2000, &corpus, &parse_small_numbers);
+        panic::set_hook(hook);
+
+        // every input is UTF-8, and some of each of the rest turn up
+        assert_eq!(counts.not_utf8, 0);
+        assert!(counts.parsed > 0);
+        assert!(counts.rejected > 0);
+        assert!(!counts.crashes.is_empty());
+        assert!(counts
+            .crashes
+            .iter()
+            .all(|(_, _, why)| why.ends_with("is too big")));
+        // the same seed makes the same inputs
+        assert_eq!(counts, again);
+    }
+
+    #[test]
+    fn test_a_corpus_that_isnt_utf8_gives_utf8() {
+        let mut rng = Rng::new(1);
+        for _ in 0..200 {
+            let data = generate(&mut rng, &[b"1\xff2\n".to_vec()]);
+            assert!(std::str::from_utf8(&data).is_ok());
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Breadth first search, for the days that find their way through a grid, a
+// droplet's cubes or a cave's tunnels where every step costs the same.
+//
+// A day doesn't build the graph, it gives the start and a function from a
+// node to the nodes one step on, leaving out any it can't step to (a wall,
+// a climb that is too steep, a cube of lava).
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+// every node that can be reached from start, with the fewest steps to it
+pub fn distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances: HashMap<N, usize> = HashMap::new();
+    let mut queue: VecDeque<N> = VecDeque::new();
+    distances.insert(start.clone(), 0);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        let steps = distances[&node] + 1;
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+// the fewest steps from start to a node is_end accepts, without searching
+// any further than that. None if there is no way there
+pub fn steps_to<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    is_end: impl Fn(&N) -> bool,
+) -> Option<usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances: HashMap<N, usize> = HashMap::new();
+    let mut queue: VecDeque<N> = VecDeque::new();
+    distances.insert(start.clone(), 0);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        let steps = distances[&node];
+        if is_end(&node) {
+            return Some(steps);
+        }
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a line of 10 nodes, 0 to 9, that can only be walked up
+    fn up(n: &u32) -> Option<u32> {
+        Some(n + 1).filter(|n| *n < 10)
+    }
+
+    #[test]
+    fn test_distances() {
+        let line = distances(3, up);
+        assert_eq!(line.len(), 7);
+        assert_eq!(line[&3], 0);
+        assert_eq!(line[&9], 6);
+        assert!(!line.contains_key(&2));
+
+        // both ways round a ring of 6, the furthest is 3 steps either way
+        let ring = distances(0, |n: &u32| [(n + 1) % 6, (n + 5) % 6]);
+        assert_eq!(ring.values().max(), Some(&3));
+        assert_eq!(ring[&5], 1);
+    }
+
+    #[test]
+    fn test_steps_to() {
+        assert_eq!(steps_to(3, up, |n| *n == 7), Some(4));
+        assert_eq!(steps_to(3, up, |n| *n == 3), Some(0));
+        assert_eq!(steps_to(3, up, |n| *n == 2), None);
+    }
+}
@@ -0,0 +1,157 @@
+// A rectangle of cells read from the input a row per line, a character per
+// cell, like day 8's tree heights or day 12's hill. x is the column and y the
+// row, both from 0 at the top left.
+//
+// The day says how a character becomes a cell, and what a cell is called for
+// the errors, and parse() checks that every row is there and as wide as the
+// first. It doesn't know what the cells mean, so what can be walked onto from
+// where is left to the day (see graph.rs for searching it).
+
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // row by row
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn parse(s: &str, what: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, String> {
+        let mut cells: Vec<T> = vec![];
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        for (i, line) in s.lines().enumerate() {
+            let before = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                match cell(c) {
+                    Some(cell) => cells.push(cell),
+                    None => {
+                        return Err(format!(
+                            "line {}: {:?} in column {} is not {}",
+                            i + 1,
+                            c,
+                            column + 1,
+                            what
+                        ))
+                    }
+                }
+            }
+            let row = cells.len() - before;
+            match width {
+                _ if row == 0 => return Err(format!("line {}: the row is empty", i + 1)),
+                None => width = Some(row),
+                Some(width) if row != width => {
+                    return Err(format!(
+                        "line {}: is {} wide, the first row is {}",
+                        i + 1,
+                        row,
+                        width
+                    ))
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        match width {
+            None => Err("the grid is empty".to_string()),
+            Some(width) => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    // every x and y, a row at a time from the top
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // the first cell, reading a row at a time, that is what the day is after
+    pub fn find(&self, is: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.positions()
+            .find(|&(x, y)| is(&self.cells[y * self.width + x]))
+    }
+
+    // the cells above, below, left and right of x and y, leaving out the ones
+    // off the edge
+    pub fn neighbours(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut neighbours = vec![];
+        if y > 0 {
+            neighbours.push((x, y - 1));
+        }
+        if y + 1 < self.height {
+            neighbours.push((x, y + 1));
+        }
+        if x > 0 {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < self.width {
+            neighbours.push((x + 1, y));
+        }
+        neighbours
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, String> {
+        Grid::parse(s, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        let rows: Vec<&[u32]> = grid.rows().collect();
+        assert_eq!(rows, [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.find(|&n| n > 4), Some((1, 1)));
+
+        assert_eq!(
+            digits("123\n4x6\n").err().unwrap(),
+            "line 2: 'x' in column 2 is not a digit"
+        );
+        assert_eq!(
+            digits("123\n45\n").err().unwrap(),
+            "line 2: is 2 wide, the first row is 3"
+        );
+        assert_eq!(
+            digits("123\n\n456\n").err().unwrap(),
+            "line 2: the row is empty"
+        );
+        assert_eq!(digits("").err().unwrap(), "the grid is empty");
+    }
+
+    #[test]
+    fn test_neighbours_stay_on_the_grid() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        assert_eq!(grid.neighbours(0, 0), [(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours(1, 1), [(1, 0), (1, 2), (0, 1), (2, 1)]);
+        assert_eq!(grid.neighbours(2, 2), [(2, 1), (1, 2)]);
+        assert_eq!(grid.positions().count(), 9);
+    }
+}
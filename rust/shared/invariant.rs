@@ -0,0 +1,52 @@
+// Invariants the days check while they run, with --check.
+//
+// Some things have to stay true however the input looks: moving crates
+// doesn't make or lose any, a rope's knots stay next to each other. A day
+// states them with check(), naming the invariant and saying what it was
+// looking at:
+//
+//     invariant::check(
+//         "day05 crates are conserved",
+//         || after == before,
+//         || format!("move {} from {} to {}: {} crates before, {} after", ...),
+//     );
+//
+// Normally this does nothing, not even the test, which can be costly. With
+// --check the test is made, and if it fails the day stops there with both:
+//
+//     invariant broken, day05 crates are conserved: move 3 from 1 to 2: ...
+//
+// The runner passes --check on to every solver, like --checked.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use super::cli;
+
+// not read from the command line yet, off, on
+const UNKNOWN: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+// whether --check was given, or what set_enabled last said
+pub fn is_enabled() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        UNKNOWN => {
+            let on = cli::has_flag("--check");
+            set_enabled(on);
+            on
+        }
+        mode => mode == ON,
+    }
+}
+
+pub fn set_enabled(on: bool) {
+    MODE.store(if on { ON } else { OFF }, Ordering::Relaxed);
+}
+
+pub fn check(what: &str, holds: impl FnOnce() -> bool, context: impl FnOnce() -> String) {
+    if is_enabled() && !holds() {
+        panic!("invariant broken, {}: {}", what, context());
+    }
+}
@@ -0,0 +1,65 @@
+// Checking an input's structure before solving it.
+//
+// A parser stops at the first thing it can't read, and some mistakes (a
+// command for a stack that isn't there) get through it and only show up as a
+// panic or a wrong answer later. A day's lint function reads the input
+// against the day's grammar instead and keeps going, so every problem is
+// reported at once, each with the line it is on:
+//
+//     line 3: 'x' is not a tree height
+//     line 9: has 4 trees, the first row has 5
+//
+// `runner lint --day=N FILE` prints them. Days without a lint function are
+// checked with their parser, so only the first problem is found.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    // 1-based, None for a problem with the whole input (no start, say)
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+// collects the problems as a lint function finds them
+#[derive(Debug, Default)]
+pub struct Lint {
+    problems: Vec<Problem>,
+}
+
+impl Lint {
+    pub fn new() -> Lint {
+        Lint::default()
+    }
+
+    // a problem on a line, given as its 0-based index from enumerate()
+    pub fn at(&mut self, index: usize, message: impl Into<String>) {
+        self.problems.push(Problem {
+            line: Some(index + 1),
+            message: message.into(),
+        });
+    }
+
+    pub fn whole(&mut self, message: impl Into<String>) {
+        self.problems.push(Problem {
+            line: None,
+            message: message.into(),
+        });
+    }
+
+    // in the order of the input, with the whole-input problems last
+    pub fn problems(self) -> Vec<Problem> {
+        let mut problems = self.problems;
+        problems.sort_by_key(|p| p.line.unwrap_or(usize::MAX));
+        problems
+    }
+}
@@ -0,0 +1,232 @@
+// Allocation and peak memory accounting.
+//
+// A day opts in by installing the counting allocator in its main.rs
+//
+//     #[global_allocator]
+//     static ALLOCATOR: shared::memory::CountingAllocator = shared::memory::CountingAllocator;
+//
+// and wrapping each part of the work in a phase:
+//
+//     let phase = memory::Phase::start("parse");
+//     ...
+//     phase.finish();
+//
+// Nothing is counted or printed unless the day is run with --memory. Then each
+// phase prints its time, the number of allocations, the bytes allocated and the
+// peak bytes in use while it ran, to stderr, e.g.
+//
+//     [memory] parse: 1.2ms, 5040 allocations, 120.3 KiB allocated, peak 48.0 KiB
+//
+// The peak is the most that was in use at once during the phase out of what
+// has been allocated since counting started, so it includes anything from
+// earlier phases that is still around. It isn't the process's resident memory:
+// whatever was allocated before counting started isn't in it. The peak is
+// reset at the start of each phase, so phases shouldn't overlap.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU64, Ordering};
+use std::time::Instant;
+
+use super::cli;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+// memory freed that was allocated before counting started makes this go below
+// zero, which is why it is signed
+static IN_USE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // counted as freeing the old block and allocating the new one
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES_ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+    let in_use = IN_USE.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+    PEAK.fetch_max(in_use, Ordering::Relaxed);
+}
+
+fn record_dealloc(size: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    IN_USE.fetch_sub(size as isize, Ordering::Relaxed);
+}
+
+// counting is switched on by --memory, the first time a phase is started
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryStats {
+    pub allocations: u64,
+    pub bytes_allocated: u64,
+    pub peak_bytes: u64,
+}
+
+pub fn snapshot() -> MemoryStats {
+    MemoryStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes_allocated: BYTES_ALLOCATED.load(Ordering::Relaxed),
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0) as u64,
+    }
+}
+
+pub struct Phase {
+    name: String,
+    started: Instant,
+    start: Option<MemoryStats>, // None when counting is off
+}
+
+impl Phase {
+    pub fn start(name: &str) -> Phase {
+        if !is_enabled() && cli::has_flag("--memory") {
+            enable();
+        }
+        let start = if is_enabled() {
+            PEAK.store(IN_USE.load(Ordering::Relaxed), Ordering::Relaxed);
+            Some(snapshot())
+        } else {
+            None
+        };
+        Phase {
+            name: name.to_string(),
+            started: Instant::now(),
+            start,
+        }
+    }
+
+    // the counts since the phase started
+    pub fn stats(&self) -> Option<MemoryStats> {
+        let start = self.start?;
+        let now = snapshot();
+        Some(MemoryStats {
+            allocations: now.allocations - start.allocations,
+            bytes_allocated: now.bytes_allocated - start.bytes_allocated,
+            peak_bytes: now.peak_bytes,
+        })
+    }
+
+    pub fn finish(self) -> Option<MemoryStats> {
+        let stats = self.stats()?;
+        eprintln!(
+            "[memory] {}: {:.1?}, {} allocations, {} allocated, peak {}",
+            self.name,
+            self.started.elapsed(),
+            stats.allocations,
+            format_bytes(stats.bytes_allocated),
+            format_bytes(stats.peak_bytes)
+        );
+        Some(stats)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the allocator is called directly, as it isn't the test's global
+    // allocator, so nothing else is counted
+    #[test]
+    fn test_phase_counts_what_is_allocated_during_it() {
+        enable();
+        let kib = Layout::from_size_align(1024, 8).unwrap();
+        let half = Layout::from_size_align(512, 8).unwrap();
+        unsafe {
+            let before = CountingAllocator.alloc(kib);
+            let phase = Phase::start("test");
+            let a = CountingAllocator.alloc(kib);
+            let b = CountingAllocator.alloc_zeroed(half);
+            CountingAllocator.dealloc(a, kib);
+            let c = CountingAllocator.realloc(b, half, 1024);
+            let stats = phase.finish().unwrap();
+            assert_eq!(
+                stats,
+                MemoryStats {
+                    allocations: 3,
+                    bytes_allocated: 1024 + 512 + 1024,
+                    // the block from before the phase was still in use
+                    peak_bytes: 1024 + 1024 + 512,
+                }
+            );
+
+            // a new phase only counts from its start, and the peak starts
+            // at what is in use then
+            let phase = Phase::start("after");
+            CountingAllocator.dealloc(before, kib);
+            CountingAllocator.dealloc(c, kib);
+            let stats = phase.finish().unwrap();
+            assert_eq!(
+                stats,
+                MemoryStats {
+                    allocations: 0,
+                    bytes_allocated: 0,
+                    peak_bytes: 1024 + 1024,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(1000), "1000 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
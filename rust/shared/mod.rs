@@ -0,0 +1,33 @@
+// Code that is shared between the days.
+//
+// There is no separate crate for this, each day (in rust/YYYY/dayNN) pulls the
+// whole directory in with
+//
+//     #[path = "../../../shared/mod.rs"]
+//     mod shared;
+//
+// so not every day uses every part of it.
+#![allow(dead_code)]
+
+pub mod answer;
+pub mod checked;
+pub mod checkpoint;
+pub mod cli;
+pub mod event;
+pub mod fuzz;
+pub mod graph;
+pub mod grid;
+pub mod invariant;
+pub mod lint;
+pub mod memory;
+pub mod parse;
+pub mod plugin;
+pub mod progress;
+pub mod property;
+pub mod replay;
+pub mod rng;
+pub mod search;
+pub mod simulation;
+pub mod trace;
+pub mod viewport;
+pub mod watch;
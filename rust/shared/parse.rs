@@ -0,0 +1,74 @@
+// Reading the pieces most inputs are made of, for the days' parsers.
+//
+// These only split the input up. Checking that a piece means something, and
+// saying on which line it doesn't, is still the day's job, so each piece
+// comes with the number of the line it starts on.
+
+// the groups of lines between blank lines (day 1's elves, day 11's monkeys),
+// each with the number of its first line. A line of only spaces is blank too
+pub fn blocks(s: &str) -> Vec<(usize, Vec<&str>)> {
+    let mut blocks: Vec<(usize, Vec<&str>)> = vec![];
+    let mut block: Option<(usize, Vec<&str>)> = None;
+    for (i, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            blocks.extend(block.take());
+            continue;
+        }
+        block.get_or_insert((i + 1, vec![])).1.push(line);
+    }
+    blocks.extend(block);
+    blocks
+}
+
+// every whole number in a line, in order and with its sign, skipping the words
+// around them: "Sensor at x=2, y=-18" is [2, -18]. A number too big for an
+// i64 is left out
+pub fn numbers(line: &str) -> Vec<i64> {
+    let mut numbers = vec![];
+    let mut number = String::new();
+    for c in line.chars().chain([' ']) {
+        if c.is_ascii_digit() || (c == '-' && number.is_empty()) {
+            number.push(c);
+            continue;
+        }
+        if let Ok(n) = number.parse() {
+            numbers.push(n);
+        }
+        number.clear();
+        if c == '-' {
+            number.push(c);
+        }
+    }
+    numbers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(
+            blocks("1000\n2000\n\n4000\n  \n\n5000\n6000\n"),
+            [
+                (1, vec!["1000", "2000"]),
+                (4, vec!["4000"]),
+                (7, vec!["5000", "6000"])
+            ]
+        );
+        assert!(blocks("\n\n").is_empty());
+        assert_eq!(blocks("\n1\n2")[0], (2, vec!["1", "2"]));
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            numbers("Sensor at x=2, y=-18: closest beacon is at x=-2, y=15"),
+            [2, -18, -2, 15]
+        );
+        assert_eq!(numbers("move 13 from 1 to 2"), [13, 1, 2]);
+        assert_eq!(numbers("a-b - 3--4"), [3, -4]);
+        assert_eq!(numbers("99999999999999999999 1"), [1]);
+        assert!(numbers("no numbers here").is_empty());
+    }
+}
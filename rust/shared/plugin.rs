@@ -0,0 +1,129 @@
+// The C ABI for solver plugins.
+//
+// A plugin is a cdylib that exports one function,
+//
+//     const struct aoc_plugin_info *aoc_plugin_info(void);
+//
+// returning a pointer to a static PluginInfo: the ABI version, the year, day
+// and part it solves, a name, and the solve function. The runner finds plugins in its
+// plugins directory and runs them alongside the built-in solvers.
+//
+// solve is given the input bytes and a buffer for the answer. It works like
+// snprintf: it writes as much of the answer as fits and returns the answer's
+// full length, so the caller can try again with a bigger buffer if it didn't
+// fit. A negative return is an error, and the buffer holds the message, which
+// is -return bytes long (again, as much as fits).
+//
+// A plugin written in Rust only needs a solve function and
+//
+//     #[path = "../../../shared/mod.rs"]
+//     mod shared;
+//
+//     export_plugin!(2022, 1, 2, "day01 top three", solve);
+
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+
+// bump this when PluginInfo or SolveFn change
+pub const ABI_VERSION: u32 = 2;
+
+pub const INFO_SYMBOL: &str = "aoc_plugin_info";
+
+// calling it is only safe with the buffers call_solve asks for
+pub type SolveFn = unsafe extern "C" fn(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+) -> i64;
+
+pub type InfoFn = extern "C" fn() -> *const PluginInfo;
+
+#[repr(C)]
+pub struct PluginInfo {
+    pub abi_version: u32,
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub name: *const c_char, // NUL terminated, and lives as long as the plugin
+    pub solve: SolveFn,
+}
+
+// the info is only ever a static that nothing changes
+unsafe impl Sync for PluginInfo {}
+
+/// The body of a plugin's solve function: reads the input, runs the solver and
+/// writes the answer (or the error) back, without letting a panic unwind into
+/// the caller.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` bytes that can be read for the whole
+/// call (it may be anything, even null, when `input_len` is 0). `answer` must
+/// point to `answer_cap` bytes that can be written and that don't overlap the
+/// input (it may be anything when `answer_cap` is 0). These are the rules the
+/// runner follows when it calls a plugin.
+pub unsafe fn call_solve(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut u8,
+    answer_cap: usize,
+    solve: impl FnOnce(&str) -> Result<String, String>,
+) -> i64 {
+    let bytes: &[u8] = if input_len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(input, input_len)
+    };
+    let result = match std::str::from_utf8(bytes) {
+        Err(_) => Err("the input is not UTF-8".to_string()),
+        // nothing the solver had is looked at again after a panic
+        Ok(s) => match panic::catch_unwind(AssertUnwindSafe(|| solve(s))) {
+            Ok(result) => result,
+            Err(_) => Err("the solver panicked".to_string()),
+        },
+    };
+    let (text, sign) = match &result {
+        Ok(text) => (text, 1),
+        Err(why) => (why, -1),
+    };
+    let written = text.len().min(answer_cap);
+    if written > 0 {
+        std::ptr::copy_nonoverlapping(text.as_ptr(), answer, written);
+    }
+    sign * text.len() as i64
+}
+
+// exports a solver as a plugin, e.g. export_plugin!(2022, 16, 1, "day16 bitmask", solve)
+// where solve is a fn(&str) -> Result<String, String>
+#[macro_export]
+macro_rules! export_plugin {
+    ($year:expr, $day:expr, $part:expr, $name:expr, $solve:path) => {
+        // the caller has to keep to the ABI, which has the same rules as call_solve
+        unsafe extern "C" fn aoc_plugin_solve(
+            input: *const u8,
+            input_len: usize,
+            answer: *mut u8,
+            answer_cap: usize,
+        ) -> i64 {
+            unsafe {
+                $crate::shared::plugin::call_solve(input, input_len, answer, answer_cap, $solve)
+            }
+        }
+
+        static AOC_PLUGIN_INFO: $crate::shared::plugin::PluginInfo =
+            $crate::shared::plugin::PluginInfo {
+                abi_version: $crate::shared::plugin::ABI_VERSION,
+                year: $year,
+                day: $day,
+                part: $part,
+                name: concat!($name, "\0").as_ptr() as *const std::os::raw::c_char,
+                solve: aoc_plugin_solve,
+            };
+
+        #[no_mangle]
+        pub extern "C" fn aoc_plugin_info() -> *const $crate::shared::plugin::PluginInfo {
+            &AOC_PLUGIN_INFO
+        }
+    };
+}
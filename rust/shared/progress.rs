@@ -0,0 +1,267 @@
+// Progress reporting for the long running loops (day 11 rounds, day 14 sand,
+// day 15 rows).
+//
+// A loop creates a Progress with a label and (if it knows it) a total, then
+// calls set() or inc() as it goes. How it is shown depends on where the output
+// is going:
+//   - a terminal gets a single progress bar that is redrawn in place, with an ETA
+//   - anything else (a pipe or a file) gets a log line every few seconds
+//   - --json gets nothing at all, so the output stays parseable
+// Everything is written to stderr so it never mixes with the answers.
+
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use super::cli;
+
+const BAR_WIDTH: usize = 30;
+const BAR_INTERVAL: Duration = Duration::from_millis(100);
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressMode {
+    Bar,
+    Log,
+    Silent,
+}
+
+impl ProgressMode {
+    pub fn detect() -> ProgressMode {
+        if cli::is_json() {
+            ProgressMode::Silent
+        } else if io::stderr().is_terminal() {
+            ProgressMode::Bar
+        } else {
+            ProgressMode::Log
+        }
+    }
+}
+
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    current: u64,
+    mode: ProgressMode,
+    started: Instant,
+    last_report: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: Option<u64>) -> Progress {
+        Progress::with_mode(label, total, ProgressMode::detect())
+    }
+
+    pub fn with_mode(label: &str, total: Option<u64>, mode: ProgressMode) -> Progress {
+        Progress {
+            label: label.to_string(),
+            total,
+            current: 0,
+            mode,
+            started: Instant::now(),
+            last_report: None,
+        }
+    }
+
+    pub fn current(&self) -> u64 {
+        self.current
+    }
+
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    pub fn inc(&mut self, n: u64) {
+        self.set(self.current + n);
+    }
+
+    pub fn set(&mut self, current: u64) {
+        self.current = current;
+        let interval = match self.mode {
+            ProgressMode::Bar => BAR_INTERVAL,
+            ProgressMode::Log => LOG_INTERVAL,
+            ProgressMode::Silent => return,
+        };
+        let due = match self.last_report {
+            Some(last) => last.elapsed() >= interval,
+            None => true,
+        };
+        if due {
+            self.report();
+        }
+    }
+
+    // items per second since the loop started
+    pub fn rate(&self) -> f64 {
+        let secs = self.started.elapsed().as_secs_f64();
+        if secs > 0.0 {
+            self.current as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    pub fn eta(&self) -> Option<Duration> {
+        let total = self.total?;
+        let rate = self.rate();
+        if rate <= 0.0 || self.current > total {
+            return None;
+        }
+        Some(Duration::from_secs_f64(
+            (total - self.current) as f64 / rate,
+        ))
+    }
+
+    // always draws the final state, so a bar ends at 100% and a log ends with
+    // the total time taken
+    pub fn finish(&mut self) {
+        if self.mode == ProgressMode::Silent {
+            return;
+        }
+        self.report();
+        if self.mode == ProgressMode::Bar {
+            eprintln!();
+        }
+    }
+
+    fn report(&mut self) {
+        self.last_report = Some(Instant::now());
+        let line = match self.mode {
+            ProgressMode::Bar => format!("\r\x1B[K{}", self.bar_line()),
+            ProgressMode::Log => format!("{}\n", self.log_line()),
+            ProgressMode::Silent => return,
+        };
+        let mut stderr = io::stderr();
+        let _ = stderr.write_all(line.as_bytes());
+        let _ = stderr.flush();
+    }
+
+    fn bar_line(&self) -> String {
+        match self.total {
+            Some(total) => {
+                let fraction = if total > 0 {
+                    (self.current as f64 / total as f64).min(1.0)
+                } else {
+                    1.0
+                };
+                let filled = (fraction * BAR_WIDTH as f64) as usize;
+                format!(
+                    "{} [{}{}] {:>3}% {}/{} {:.0}/s ETA {}",
+                    self.label,
+                    "#".repeat(filled),
+                    ".".repeat(BAR_WIDTH - filled),
+                    (fraction * 100.0) as u32,
+                    self.current,
+                    total,
+                    self.rate(),
+                    format_duration(self.eta())
+                )
+            }
+            None => format!(
+                "{} {} {:.0}/s elapsed {}",
+                self.label,
+                self.current,
+                self.rate(),
+                format_duration(Some(self.started.elapsed()))
+            ),
+        }
+    }
+
+    fn log_line(&self) -> String {
+        match self.total {
+            Some(total) => format!(
+                "[progress] {}: {}/{} ({:.0}/s, ETA {})",
+                self.label,
+                self.current,
+                total,
+                self.rate(),
+                format_duration(self.eta())
+            ),
+            None => format!(
+                "[progress] {}: {} ({:.0}/s, elapsed {})",
+                self.label,
+                self.current,
+                self.rate(),
+                format_duration(Some(self.started.elapsed()))
+            ),
+        }
+    }
+}
+
+fn format_duration(d: Option<Duration>) -> String {
+    match d {
+        Some(d) => {
+            let secs = d.as_secs();
+            format!(
+                "{:02}:{:02}:{:02}",
+                secs / 3600,
+                (secs / 60) % 60,
+                secs % 60
+            )
+        }
+        None => "--:--:--".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a silent progress that started secs ago
+    fn started_ago(total: Option<u64>, secs: u64) -> Progress {
+        let mut progress = Progress::with_mode("Rows", total, ProgressMode::Silent);
+        progress.started = Instant::now() - Duration::from_secs(secs);
+        progress
+    }
+
+    #[test]
+    fn test_rate_and_eta() {
+        let mut progress = started_ago(Some(100), 10);
+        progress.set(50);
+        assert!((progress.rate() - 5.0).abs() < 0.01, "{}", progress.rate());
+        // half done in 10 seconds leaves another 10
+        assert_eq!(progress.eta().map(|eta| eta.as_secs()), Some(10));
+        progress.set(100);
+        assert_eq!(progress.eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_no_eta_without_a_rate_or_total() {
+        // nothing done yet, so there's no rate to go on
+        assert_eq!(started_ago(Some(100), 10).eta(), None);
+        let mut progress = started_ago(None, 10);
+        progress.set(50);
+        assert_eq!(progress.eta(), None);
+        // past the total
+        let mut progress = started_ago(Some(100), 10);
+        progress.set(150);
+        assert_eq!(progress.eta(), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let mut progress = started_ago(Some(200), 10);
+        progress.set(50);
+        assert_eq!(
+            progress.bar_line(),
+            format!(
+                "Rows [{}{}]  25% 50/200 5/s ETA 00:00:30",
+                "#".repeat(7),
+                ".".repeat(23)
+            )
+        );
+        assert_eq!(
+            progress.log_line(),
+            "[progress] Rows: 50/200 (5/s, ETA 00:00:30)"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Some(Duration::from_secs(3725))), "01:02:05");
+        assert_eq!(
+            format_duration(Some(Duration::from_millis(999))),
+            "00:00:00"
+        );
+        assert_eq!(format_duration(None), "--:--:--");
+    }
+}
@@ -0,0 +1,55 @@
+// Property tests: make up a lot of random cases and check that something holds
+// for every one of them, usually that a fast solver agrees with a slow one that
+// is obviously right.
+//
+//     property::check("overlaps agree", 1000, |rng| random_pair(rng), |pair| {
+//         if fast(pair) == slow(pair) { Ok(()) } else { Err("...".to_string()) }
+//     });
+//
+// The seed is fixed so a failure repeats on the next run. PROPERTY_SEED and
+// PROPERTY_CASES in the environment change the seed and the number of cases,
+// e.g. to run a lot more cases now and then. A failure panics with the seed, the
+// case number and the case itself (there is no shrinking, so keep the generated
+// cases small).
+
+use std::env;
+use std::fmt::Debug;
+
+use super::rng::Rng;
+
+const DEFAULT_SEED: u64 = 2022;
+
+pub fn check<T, G, P>(name: &str, cases: u64, generate: G, property: P)
+where
+    T: Debug,
+    G: Fn(&mut Rng) -> T,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let seed: u64 = match env::var("PROPERTY_SEED") {
+        Ok(s) => s.parse().expect("PROPERTY_SEED should be a number"),
+        Err(_) => DEFAULT_SEED,
+    };
+    let cases: u64 = match env::var("PROPERTY_CASES") {
+        Ok(s) => s.parse().expect("PROPERTY_CASES should be a number"),
+        Err(_) => cases,
+    };
+    let mut rng = Rng::new(seed);
+    for i in 0..cases {
+        let case = generate(&mut rng);
+        if let Err(why) = property(&case) {
+            panic!(
+                "property {:?} failed on case {} (seed {}): {}\n{:#?}",
+                name, i, seed, why, case
+            );
+        }
+    }
+}
+
+// for the common case of comparing two answers
+pub fn agree<T: Debug + PartialEq>(fast: T, slow: T) -> Result<(), String> {
+    if fast == slow {
+        Ok(())
+    } else {
+        Err(format!("fast gave {:?}, slow gave {:?}", fast, slow))
+    }
+}
@@ -0,0 +1,182 @@
+// Recordings of simulation runs, so an interesting run can be shared and
+// watched again without solving the puzzle again.
+//
+// With --record=FILE a simulation day writes its run to FILE: a header naming
+// the kind of model, the state it started in, and then one line per step with
+// only what that step changed.
+//
+//     # aoc recording rope
+//     0,0 0,0
+//     ---
+//     1,0 0,0
+//     2,0 1,0
+//     ...
+//
+// A model implements Record to write those lines, read them back and draw
+// itself, and `runner replay FILE` plays a recording back:
+//   --speed=N    steps per second (default 10, 0 for as fast as possible)
+//   --seek=N     skip straight to step N before drawing anything
+//   --stop=N     stop after step N
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::thread;
+use std::time::Duration;
+
+use super::cli;
+use super::simulation::{Observer, Simulation, StopReason};
+
+const HEADER: &str = "# aoc recording";
+// between the starting state and the steps
+const SEPARATOR: &str = "---";
+
+pub trait Record: Simulation + Sized {
+    // written into the header and checked when replaying
+    const KIND: &'static str;
+
+    // the state before the first step, any number of lines (but not "---")
+    fn record_start(&self) -> String;
+    // what the last step changed, on one line
+    fn record_step(&self) -> String;
+
+    fn replay_start(start: &str) -> Result<Self, String>;
+    fn replay_step(&mut self, step: &str) -> Result<(), String>;
+    // what is drawn after each step
+    fn render(&self) -> String;
+}
+
+// the kind of model a recording is of
+pub fn kind(data: &str) -> Result<&str, String> {
+    match data
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(HEADER))
+    {
+        Some(kind) => Ok(kind.trim()),
+        None => Err("this isn't a recording".to_string()),
+    }
+}
+
+// the starting state and the steps
+fn split<'a>(data: &'a str, kind: &str) -> Result<(String, Vec<&'a str>), String> {
+    let found = self::kind(data)?;
+    if found != kind {
+        return Err(format!("this is a {} recording, not a {} one", found, kind));
+    }
+    let mut lines = data.lines().skip(1);
+    let mut start: Vec<&str> = vec![];
+    loop {
+        match lines.next() {
+            Some(SEPARATOR) => break,
+            Some(line) => start.push(line),
+            None => {
+                return Err(format!(
+                    "there is no {} after the starting state",
+                    SEPARATOR
+                ))
+            }
+        }
+    }
+    Ok((start.join("\n"), lines.collect()))
+}
+
+// the model as it was after `steps` steps, or at the end if there aren't that many
+pub fn replay_to<R: Record>(data: &str, steps: usize) -> Result<R, String> {
+    let (start, lines) = split(data, R::KIND)?;
+    let mut model = R::replay_start(&start)?;
+    for (i, line) in lines.iter().take(steps).enumerate() {
+        model
+            .replay_step(line)
+            .map_err(|why| format!("step {}: {}", i + 1, why))?;
+    }
+    Ok(model)
+}
+
+fn draw<R: Record>(model: &R, step: usize, steps: usize) {
+    print!("\x1b[2J\x1b[H");
+    println!("{}", model.render());
+    println!("[replay] step {} of {}", step, steps);
+    let _ = std::io::stdout().flush();
+}
+
+// clears the screen and draws the model after each step from `seek` on
+pub fn play<R: Record>(
+    data: &str,
+    speed: u32,
+    seek: usize,
+    stop: Option<usize>,
+) -> Result<(), String> {
+    let (_, lines) = split(data, R::KIND)?;
+    let last = stop.unwrap_or(lines.len()).min(lines.len());
+    let seek = seek.min(last);
+    let mut model: R = replay_to(data, seek)?;
+    draw(&model, seek, lines.len());
+    for i in seek..last {
+        if speed > 0 {
+            thread::sleep(Duration::from_secs(1) / speed);
+        }
+        model
+            .replay_step(lines[i])
+            .map_err(|why| format!("step {}: {}", i + 1, why))?;
+        draw(&model, i + 1, lines.len());
+    }
+    Ok(())
+}
+
+// writes the run to the file given with --record, if there is one
+pub struct Recorder {
+    path: String,
+    out: Option<BufWriter<File>>,
+}
+
+impl Recorder {
+    pub fn from_args() -> Recorder {
+        let path = cli::flag_value("--record").unwrap_or_default();
+        let out = match path.as_str() {
+            "" => None,
+            path => match File::create(path) {
+                Err(why) => panic!("couldn't create {}: {}", path, why),
+                Ok(file) => Some(BufWriter::new(file)),
+            },
+        };
+        Recorder { path, out }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.out.is_some()
+    }
+
+    // stops recording if the file can't be written
+    fn write(&mut self, s: &str) {
+        if let Some(out) = self.out.as_mut() {
+            if let Err(why) = writeln!(out, "{}", s) {
+                eprintln!("couldn't write to {}: {}", self.path, why);
+                self.out = None;
+            }
+        }
+    }
+}
+
+impl<S: Record> Observer<S> for Recorder {
+    fn on_start(&mut self, sim: &S) {
+        if self.is_enabled() {
+            self.write(&format!("{} {}", HEADER, S::KIND));
+            self.write(sim.record_start().trim_end());
+            self.write(SEPARATOR);
+        }
+    }
+
+    fn on_step(&mut self, sim: &S) {
+        if self.is_enabled() {
+            self.write(&sim.record_step());
+        }
+    }
+
+    fn on_finish(&mut self, _sim: &S, _reason: &StopReason) {
+        if let Some(out) = self.out.as_mut() {
+            if let Err(why) = out.flush() {
+                eprintln!("couldn't write to {}: {}", self.path, why);
+            }
+        }
+    }
+}
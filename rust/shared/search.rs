@@ -0,0 +1,88 @@
+// Branch and bound: finding the best of a tree of choices without looking at
+// all of it.
+//
+// A day describes its choices as a Problem: what a node (a state partway
+// through the choices) is worth if nothing more is done, the most anything
+// below it could be worth, and the nodes one choice on from it. best() goes
+// through the tree depth first and cuts off every node whose bound can't beat
+// the best value found so far, so the sooner a good answer turns up the more
+// is cut off, and children that look best should come first.
+//
+// The same node is often reached by making the same choices in a different
+// order (building a clay robot and then an ore robot, or the other way round).
+// Every node reached is remembered with the node it was reached from, so a
+// repeat is skipped, and the path to the best node can be followed back.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+pub trait Problem {
+    type Node: Clone + Eq + Hash;
+
+    // what the node is worth if no more choices are made
+    fn value(&self, node: &Self::Node) -> i64;
+    // no node below this one can be worth more than this
+    fn bound(&self, node: &Self::Node) -> i64;
+    // the nodes one choice on, the most promising first
+    fn children(&self, node: &Self::Node) -> Vec<Self::Node>;
+}
+
+// how much of the tree was looked at
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    pub visited: u64,
+    pub cut_off: u64,
+    pub repeats: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Best<N> {
+    pub value: i64,
+    // from the start to the best node, both included
+    pub path: Vec<N>,
+    pub stats: Stats,
+}
+
+pub fn best<P: Problem>(problem: &P, start: P::Node) -> Best<P::Node> {
+    let mut stats = Stats::default();
+    // every node reached, with the node it was first reached from
+    let mut parents: HashMap<P::Node, Option<P::Node>> = HashMap::new();
+    let mut best: (i64, P::Node) = (problem.value(&start), start.clone());
+
+    parents.insert(start.clone(), None);
+    let mut stack: Vec<P::Node> = vec![start];
+    while let Some(node) = stack.pop() {
+        // something better may have been found since it was pushed
+        if problem.bound(&node) <= best.0 {
+            stats.cut_off += 1;
+            continue;
+        }
+        stats.visited += 1;
+        let value = problem.value(&node);
+        if value > best.0 {
+            best = (value, node.clone());
+        }
+        // pushed backwards so the most promising child is the next popped
+        for child in problem.children(&node).into_iter().rev() {
+            if parents.contains_key(&child) {
+                stats.repeats += 1;
+            } else if problem.bound(&child) <= best.0 {
+                stats.cut_off += 1;
+            } else {
+                parents.insert(child.clone(), Some(node.clone()));
+                stack.push(child);
+            }
+        }
+    }
+
+    let mut path = vec![best.1];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    Best {
+        value: best.0,
+        path,
+        stats,
+    }
+}
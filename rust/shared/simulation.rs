@@ -0,0 +1,349 @@
+// A common shape for the days that are step by step simulations (05 crates,
+// 09 rope, 10 cpu, 11 monkeys, 14 sand, 17 rocks).
+//
+// A day implements Simulation for its model and then hands it to a runner,
+// which does the looping. Anything that wants to watch the run (progress,
+// checkpoints, printing) is an Observer and gets told about every step.
+//
+// run_from_args() gives every simulation the same command line options:
+//   --steps=N           stop after N steps
+//   --stop-when=TEXT    stop once the state (as printed with {:?}) contains TEXT
+//   --record-every=K    print the state every K steps
+
+use std::fmt::Debug;
+use std::io::{self, Write};
+
+use super::checkpoint::{Checkpoint, Checkpointer};
+use super::cli;
+use super::progress::Progress;
+
+pub trait Simulation {
+    type State: Debug;
+
+    // move the simulation on by one step
+    fn step(&mut self);
+    // true once there is nothing left to simulate
+    fn is_done(&self) -> bool;
+    // a snapshot of the interesting parts of the model
+    fn state(&self) -> Self::State;
+    fn steps_taken(&self) -> u64;
+
+    // how many steps a full run takes, if it is known up front
+    fn total_steps(&self) -> Option<u64> {
+        None
+    }
+}
+
+pub trait Observer<S: Simulation> {
+    fn on_start(&mut self, _sim: &S) {}
+    fn on_step(&mut self, _sim: &S) {}
+    fn on_finish(&mut self, _sim: &S, _reason: &StopReason) {}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    Done,
+    StepLimit,
+    Predicate,
+}
+
+pub struct Runner<'a, S: Simulation> {
+    observers: Vec<Box<dyn Observer<S> + 'a>>,
+}
+
+impl<'a, S: Simulation> Default for Runner<'a, S> {
+    fn default() -> Runner<'a, S> {
+        Runner::new()
+    }
+}
+
+impl<'a, S: Simulation> Runner<'a, S> {
+    pub fn new() -> Runner<'a, S> {
+        Runner { observers: vec![] }
+    }
+
+    pub fn observe(mut self, observer: impl Observer<S> + 'a) -> Runner<'a, S> {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn run_until_done(&mut self, sim: &mut S) -> StopReason {
+        self.run(sim, None, &mut |_| false)
+    }
+
+    pub fn run_steps(&mut self, sim: &mut S, steps: u64) -> StopReason {
+        self.run(sim, Some(steps), &mut |_| false)
+    }
+
+    pub fn run_until(&mut self, sim: &mut S, predicate: impl FnMut(&S) -> bool) -> StopReason {
+        let mut predicate = predicate;
+        self.run(sim, None, &mut predicate)
+    }
+
+    // runs to the end, keeping a copy of the state every `every` steps
+    // (and always the first and last state)
+    pub fn record_every(&mut self, sim: &mut S, every: u64) -> Vec<(u64, S::State)> {
+        let mut recorded = vec![(sim.steps_taken(), sim.state())];
+        self.run(sim, None, &mut |s| {
+            if every > 0 && s.steps_taken().is_multiple_of(every) {
+                recorded.push((s.steps_taken(), s.state()));
+            }
+            false
+        });
+        if recorded.last().map(|r| r.0) != Some(sim.steps_taken()) {
+            recorded.push((sim.steps_taken(), sim.state()));
+        }
+        recorded
+    }
+
+    pub fn run(
+        &mut self,
+        sim: &mut S,
+        max_steps: Option<u64>,
+        stop: &mut dyn FnMut(&S) -> bool,
+    ) -> StopReason {
+        for o in self.observers.iter_mut() {
+            o.on_start(sim);
+        }
+        let mut steps: u64 = 0;
+        let reason = loop {
+            if sim.is_done() {
+                break StopReason::Done;
+            }
+            if max_steps.is_some() && steps >= max_steps.unwrap() {
+                break StopReason::StepLimit;
+            }
+            sim.step();
+            steps += 1;
+            for o in self.observers.iter_mut() {
+                o.on_step(sim);
+            }
+            if stop(sim) {
+                break StopReason::Predicate;
+            }
+        };
+        for o in self.observers.iter_mut() {
+            o.on_finish(sim, &reason);
+        }
+        reason
+    }
+}
+
+// the command line options every simulation takes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Options {
+    pub steps: Option<u64>,
+    pub stop_when: Option<String>,
+    pub record_every: Option<u64>,
+}
+
+impl Options {
+    pub fn from_args() -> Options {
+        Options {
+            steps: cli::flag_value("--steps")
+                .map(|n| n.parse().expect("--steps should be a number")),
+            stop_when: cli::flag_value("--stop-when"),
+            record_every: cli::flag_value("--record-every")
+                .map(|n| n.parse().expect("--record-every should be a number")),
+        }
+    }
+}
+
+// runs the simulation using the options given on the command line
+pub fn run_from_args<'a, S: Simulation>(sim: &mut S, runner: Runner<'a, S>) -> StopReason {
+    run_with_options(sim, runner, &Options::from_args(), &mut io::stdout())
+}
+
+// runs the simulation with the options, writing the recorded states (and why
+// it stopped, if it didn't finish) to out
+pub fn run_with_options<'a, S: Simulation>(
+    sim: &mut S,
+    runner: Runner<'a, S>,
+    options: &Options,
+    out: &mut dyn Write,
+) -> StopReason {
+    let mut runner = runner;
+    let mut stop = |s: &S| -> bool {
+        let mut stop = false;
+        if let Some(every) = options.record_every {
+            if every > 0 && s.steps_taken().is_multiple_of(every) {
+                let _ = writeln!(out, "[step {:>6}] {:?}", s.steps_taken(), s.state());
+            }
+        }
+        if let Some(text) = &options.stop_when {
+            stop = format!("{:?}", s.state()).contains(text.as_str());
+        }
+        stop
+    };
+    let reason = runner.run(sim, options.steps, &mut stop);
+    if reason != StopReason::Done {
+        let _ = writeln!(
+            out,
+            "Stopped after {} steps ({:?}): {:?}",
+            sim.steps_taken(),
+            reason,
+            sim.state()
+        );
+    }
+    reason
+}
+
+// shows the progress of the run, using the step count
+pub struct ProgressObserver {
+    label: String,
+    progress: Option<Progress>,
+}
+
+impl ProgressObserver {
+    pub fn new(label: &str) -> ProgressObserver {
+        ProgressObserver {
+            label: label.to_string(),
+            progress: None,
+        }
+    }
+}
+
+impl<S: Simulation> Observer<S> for ProgressObserver {
+    fn on_start(&mut self, sim: &S) {
+        // the total isn't known until the simulation is ready to run
+        let mut progress = Progress::new(&self.label, sim.total_steps());
+        progress.set(sim.steps_taken());
+        self.progress = Some(progress);
+    }
+
+    fn on_step(&mut self, sim: &S) {
+        if let Some(progress) = self.progress.as_mut() {
+            progress.set(sim.steps_taken());
+        }
+    }
+
+    fn on_finish(&mut self, sim: &S, _reason: &StopReason) {
+        if let Some(progress) = self.progress.as_mut() {
+            progress.set(sim.steps_taken());
+            progress.finish();
+        }
+    }
+}
+
+// saves a checkpoint every so often, and once more at the end
+pub struct CheckpointObserver<'a> {
+    checkpointer: &'a mut Checkpointer,
+}
+
+impl<'a> CheckpointObserver<'a> {
+    pub fn new(checkpointer: &'a mut Checkpointer) -> CheckpointObserver<'a> {
+        CheckpointObserver { checkpointer }
+    }
+}
+
+impl<'a, S: Simulation + Checkpoint> Observer<S> for CheckpointObserver<'a> {
+    fn on_step(&mut self, sim: &S) {
+        self.checkpointer.maybe_save(sim);
+    }
+
+    fn on_finish(&mut self, sim: &S, _reason: &StopReason) {
+        if self.checkpointer.is_enabled() {
+            if let Err(why) = self.checkpointer.save(sim) {
+                eprintln!(
+                    "couldn't save checkpoint {}: {}",
+                    self.checkpointer.path(),
+                    why
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts up to a number, one at a time
+    struct Count {
+        n: u64,
+        to: u64,
+    }
+
+    impl Simulation for Count {
+        type State = u64;
+
+        fn step(&mut self) {
+            self.n += 1;
+        }
+
+        fn is_done(&self) -> bool {
+            self.n == self.to
+        }
+
+        fn state(&self) -> u64 {
+            self.n
+        }
+
+        fn steps_taken(&self) -> u64 {
+            self.n
+        }
+    }
+
+    // runs a count to 10 with the options, and gives back why it stopped,
+    // where it got to and what it printed
+    fn run(options: Options) -> (StopReason, u64, String) {
+        let mut count = Count { n: 0, to: 10 };
+        let mut out: Vec<u8> = vec![];
+        let reason = run_with_options(&mut count, Runner::default(), &options, &mut out);
+        (reason, count.n, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_options() {
+        assert_eq!(
+            run(Options::default()),
+            (StopReason::Done, 10, "".to_string())
+        );
+
+        let steps = Options {
+            steps: Some(3),
+            ..Options::default()
+        };
+        assert_eq!(
+            run(steps),
+            (
+                StopReason::StepLimit,
+                3,
+                "Stopped after 3 steps (StepLimit): 3\n".to_string()
+            )
+        );
+
+        let stop_when = Options {
+            stop_when: Some("7".to_string()),
+            ..Options::default()
+        };
+        assert_eq!(
+            run(stop_when),
+            (
+                StopReason::Predicate,
+                7,
+                "Stopped after 7 steps (Predicate): 7\n".to_string()
+            )
+        );
+
+        let record_every = Options {
+            record_every: Some(4),
+            ..Options::default()
+        };
+        assert_eq!(
+            run(record_every),
+            (
+                StopReason::Done,
+                10,
+                "[step      4] 4\n[step      8] 8\n".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_record_every() {
+        let mut count = Count { n: 0, to: 10 };
+        let recorded = Runner::new().record_every(&mut count, 4);
+        assert_eq!(recorded, vec![(0, 0), (4, 4), (8, 8), (10, 10)]);
+    }
+}
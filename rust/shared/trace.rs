@@ -0,0 +1,110 @@
+// Structured traces of how a solver got to its answer.
+//
+// Instead of uncommenting prints when an answer is wrong, a solver records
+// events as it makes its decisions: a kind ("compare", "open", ...) and a
+// payload of named values. Recording is off unless the trace was made with
+// Trace::new(true), and then nothing is formatted, so the solvers can always
+// take a trace.
+//
+// Each day that records events has a narrate function that turns one event
+// into a line of the puzzle-style narrative, and --explain prints
+//
+//     trace.render(y2022_day09::narrate)
+//
+// Events can be nested (enter/leave) and the narrative is indented to match.
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub kind: &'static str,
+    pub depth: usize,
+    pub payload: Vec<(&'static str, String)>,
+}
+
+impl Event {
+    // the payload value with this name, or "" if there isn't one
+    pub fn get(&self, name: &str) -> &str {
+        self.payload
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or("")
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Trace {
+    enabled: bool,
+    depth: usize,
+    events: Vec<Event>,
+}
+
+impl Trace {
+    pub fn new(enabled: bool) -> Trace {
+        Trace {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    // a trace that records when --explain is given
+    pub fn from_args() -> Trace {
+        Trace::new(super::cli::has_flag("--explain"))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn event(&mut self, kind: &'static str, payload: &[(&'static str, &dyn Display)]) {
+        if !self.enabled {
+            return;
+        }
+        self.events.push(Event {
+            kind,
+            depth: self.depth,
+            payload: payload
+                .iter()
+                .map(|(name, value)| (*name, value.to_string()))
+                .collect(),
+        });
+    }
+
+    // the events recorded until the matching leave are nested under the last one
+    pub fn enter(&mut self) {
+        self.depth += 1;
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    // the narrative, one line per event (or more if narrate gives more),
+    // indented two spaces per level
+    pub fn render(&self, narrate: fn(&Event) -> String) -> String {
+        let mut s = String::new();
+        for event in &self.events {
+            let indent = "  ".repeat(event.depth);
+            for line in narrate(event).lines() {
+                s.push_str(&indent);
+                s.push_str(line);
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+// a narrate for events that don't have their own words: the kind and payload
+pub fn describe(event: &Event) -> String {
+    let mut s = event.kind.to_string();
+    for (name, value) in &event.payload {
+        s.push_str(&format!(" {}={}", name, value));
+    }
+    s
+}
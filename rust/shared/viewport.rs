@@ -0,0 +1,196 @@
+// Drawing a window onto a grid that is too big for the terminal.
+//
+// A day that draws a grid implements Grid for it, and draws it through a
+// Viewport when one of these is given:
+//
+//   --view=WxH     the size of the window in characters (80x24 unless given)
+//   --centre=X,Y   the cell in the middle of the window
+//   --follow       keep the day's focus in the middle, the last grain of sand
+//                  in day14 or the rope's head in day09
+//   --zoom=N       each character is N by N cells
+//
+// Without any of them a day draws its whole grid as it always has.
+//
+// Zoomed out, a character shows the most important glyph among its cells, in
+// the order the grid gives (the source, then sand, then rock, then air for
+// day14). Looking at every cell gets slow when a character covers millions of
+// them, so at most SAMPLES by SAMPLES of them are looked at, and the few
+// points that mustn't be missed (a sensor) are given as marks, which are
+// always drawn.
+
+use super::cli;
+
+const DEFAULT_WIDTH: i64 = 80;
+const DEFAULT_HEIGHT: i64 = 24;
+const SAMPLES: i64 = 16;
+
+pub trait Grid {
+    // the glyphs, most important first, glyphs not in here are the least important
+    const PRIORITY: &'static str;
+
+    // the smallest and largest x and y, both included
+    fn bounds(&self) -> ((i64, i64), (i64, i64));
+
+    fn glyph(&self, x: i64, y: i64) -> char;
+
+    // where --follow keeps the window
+    fn focus(&self) -> Option<(i64, i64)> {
+        None
+    }
+
+    fn marks(&self) -> Vec<((i64, i64), char)> {
+        Vec::new()
+    }
+
+    // whether y goes up the screen rather than down it
+    fn y_up(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Viewport {
+    width: i64,
+    height: i64,
+    centre: Option<(i64, i64)>,
+    follow: bool,
+    zoom: i64,
+}
+
+impl Viewport {
+    pub fn new(width: i64, height: i64) -> Viewport {
+        Viewport {
+            width: width.max(1),
+            height: height.max(1),
+            centre: None,
+            follow: false,
+            zoom: 1,
+        }
+    }
+
+    // None unless one of the viewport flags was given
+    pub fn from_args() -> Option<Viewport> {
+        let given = ["--view", "--centre", "--follow", "--zoom"];
+        if !given.iter().any(|flag| cli::has_flag(flag)) {
+            return None;
+        }
+        let mut view = Viewport::new(DEFAULT_WIDTH, DEFAULT_HEIGHT);
+        if let Some(size) = cli::flag_value("--view") {
+            let (width, height) = parse_pair(&size, 'x').expect("--view should be WxH");
+            view = Viewport::new(width, height);
+        }
+        if let Some(centre) = cli::flag_value("--centre") {
+            let (x, y) = parse_pair(&centre, ',').expect("--centre should be X,Y");
+            view = view.centre(x, y);
+        }
+        if let Some(zoom) = cli::flag_value("--zoom") {
+            view = view.zoom(zoom.parse().expect("--zoom should be a number"));
+        }
+        if cli::has_flag("--follow") {
+            view = view.follow();
+        }
+        Some(view)
+    }
+
+    pub fn centre(mut self, x: i64, y: i64) -> Viewport {
+        self.centre = Some((x, y));
+        self
+    }
+
+    pub fn zoom(mut self, zoom: i64) -> Viewport {
+        self.zoom = zoom.max(1);
+        self
+    }
+
+    pub fn follow(mut self) -> Viewport {
+        self.follow = true;
+        self
+    }
+
+    // the smallest x and y in the window and how many characters across and
+    // down it is, which is less than the viewport when the grid is smaller
+    fn window<G: Grid>(&self, grid: &G) -> ((i64, i64), (i64, i64)) {
+        let ((min_x, min_y), (max_x, max_y)) = grid.bounds();
+        let centre = match (self.centre, self.follow) {
+            (Some(centre), _) => centre,
+            (None, true) => grid
+                .focus()
+                .unwrap_or(((min_x + max_x) / 2, (min_y + max_y) / 2)),
+            (None, false) => ((min_x + max_x) / 2, (min_y + max_y) / 2),
+        };
+        // one axis at a time: the first cell and the number of characters
+        let axis = |min: i64, max: i64, centre: i64, chars: i64| {
+            let cells = max - min + 1;
+            let chars = chars.min((cells + self.zoom - 1) / self.zoom);
+            let span = chars * self.zoom;
+            // kept inside the grid, so the focus can be at the edge
+            let first = (centre - span / 2).min(max + 1 - span).max(min);
+            (first, chars)
+        };
+        let (x, width) = axis(min_x, max_x, centre.0, self.width);
+        let (y, height) = axis(min_y, max_y, centre.1, self.height);
+        ((x, y), (width, height))
+    }
+
+    // a line saying which cells are shown, and then the window
+    pub fn render<G: Grid>(&self, grid: &G) -> String {
+        let ((x, y), (width, height)) = self.window(grid);
+        let ((_, _), (max_x, max_y)) = grid.bounds();
+        let rank = |c: char| G::PRIORITY.find(c).unwrap_or(G::PRIORITY.len());
+        let step = (self.zoom / SAMPLES).max(1);
+
+        let mut chars: Vec<Vec<char>> = vec![vec![' '; width as usize]; height as usize];
+        for (row, line) in chars.iter_mut().enumerate() {
+            let top = y + row as i64 * self.zoom;
+            for (column, c) in line.iter_mut().enumerate() {
+                let left = x + column as i64 * self.zoom;
+                let mut best: Option<char> = None;
+                for cy in (top..(top + self.zoom).min(max_y + 1)).step_by(step as usize) {
+                    for cx in (left..(left + self.zoom).min(max_x + 1)).step_by(step as usize) {
+                        let glyph = grid.glyph(cx, cy);
+                        if best.is_none_or(|b| rank(glyph) < rank(b)) {
+                            best = Some(glyph);
+                        }
+                    }
+                }
+                *c = best.unwrap_or(' ');
+            }
+        }
+        for ((mx, my), glyph) in grid.marks() {
+            let (column, row) = (
+                (mx - x).div_euclid(self.zoom),
+                (my - y).div_euclid(self.zoom),
+            );
+            if mx < x || my < y || column >= width || row >= height {
+                continue;
+            }
+            let c = &mut chars[row as usize][column as usize];
+            if rank(glyph) <= rank(*c) {
+                *c = glyph;
+            }
+        }
+        if grid.y_up() {
+            chars.reverse();
+        }
+
+        let mut s = format!(
+            "x {}..={}, y {}..={}, {} by {} cells a character\n",
+            x,
+            (x + width * self.zoom - 1).min(max_x),
+            y,
+            (y + height * self.zoom - 1).min(max_y),
+            self.zoom,
+            self.zoom
+        );
+        for line in chars {
+            s.extend(line);
+            s.push('\n');
+        }
+        s
+    }
+}
+
+fn parse_pair(s: &str, separator: char) -> Option<(i64, i64)> {
+    let (a, b) = s.split_once(separator)?;
+    Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
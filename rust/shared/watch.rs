@@ -0,0 +1,93 @@
+// Watch mode: solve again whenever the input or the parameters change.
+//
+// With --watch a day runs itself again as a child process (with the same
+// arguments, less --watch) each time the input file or the --params file is
+// modified. The screen is cleared first, so the answers and any grids the day
+// draws are redrawn in place. A run that is still going when a file changes is
+// stopped and started again. It only polls the files' modification times, so
+// it works the same everywhere.
+//
+// Each day only needs, once it knows its input file,
+//
+//     if shared::cli::has_flag("--watch") {
+//         shared::watch::run_from_args(&filename);
+//         return;
+//     }
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::cli;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    let _ = std::io::stdout().flush();
+}
+
+fn start(args: &[String]) -> Child {
+    let exe = match std::env::current_exe() {
+        Err(why) => panic!("couldn't find this program to run it again: {}", why),
+        Ok(exe) => exe,
+    };
+    match Command::new(exe).args(args).spawn() {
+        Err(why) => panic!("couldn't run this program again: {}", why),
+        Ok(child) => child,
+    }
+}
+
+// never returns, it's stopped with Ctrl-C
+pub fn run_from_args(filename: &str) {
+    let mut paths = vec![PathBuf::from(filename)];
+    if let Some(params) = cli::flag_value("--params") {
+        paths.push(PathBuf::from(params));
+    }
+    let names: Vec<String> = paths.iter().map(|p| p.display().to_string()).collect();
+    // the command line as it was given, the params file is read by the child
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| a != "--watch")
+        .collect();
+
+    let mut seen = modified_times(&paths);
+    loop {
+        clear_screen();
+        println!("[watch] {} (Ctrl-C to stop)\n", names.join(", "));
+        let mut child = start(&args);
+        let mut running = true;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if running {
+                if let Ok(Some(status)) = child.try_wait() {
+                    running = false;
+                    if !status.success() {
+                        println!("\n[watch] {}, waiting for a change", status);
+                    }
+                }
+            }
+            let now = modified_times(&paths);
+            if now != seen {
+                seen = now;
+                break;
+            }
+        }
+        // a run that has finished was already waited on, so this returns
+        // straight away, and one that hasn't is stopped first
+        if running {
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+    }
+}
@@ -0,0 +1,21 @@
+// The years there are solvers for, included into the runner's registry (see
+// runner/src/solvers.rs).
+//
+// Each year is a directory, rust/YYYY, with its days' crates in it (named
+// yYYYY_dayNN, so years can share day numbers) and a solvers.rs that has the
+// year's YEAR, solvers(), linter() and PLAYERS. Adding a year is its directory,
+// its days' crates linked in, and its line in each of the lists here.
+
+use super::{Linter, Params, Player, Solver, YearLinters, YearPlayers, YearSolvers};
+
+#[path = "2022/solvers.rs"]
+mod y2022;
+
+// every year there are solvers for, oldest first
+pub const YEARS: &[(u32, YearSolvers)] = &[(y2022::YEAR, y2022::solvers)];
+
+// each year's linters, by day (see lint.rs)
+pub const LINTERS: &[(u32, YearLinters)] = &[(y2022::YEAR, y2022::linter)];
+
+// each year's recordings and their players (see replay.rs)
+pub const PLAYERS: &[(u32, YearPlayers)] = &[(y2022::YEAR, y2022::PLAYERS)];